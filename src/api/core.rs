@@ -4,61 +4,262 @@
 //! - `prove()`    - Generate a zero-knowledge proof
 //! - `verify()`   - Verify a proof
 //! - `estimate()` - Estimate circuit requirements
+//! - `check()`    - Check constraints without generating a proof
+//! - `evaluate()` - Compute the circuit output and intermediate signals, without constraints or a proof
+//! - `prove_batch()` - Generate proofs for many requests, reusing keys
+//! - `prove_batch_parallel()` - Same, proving requests concurrently (`parallel` feature, native only)
+//! - `prove_with_registry()` - Generate a proof, reusing keys cached in a `CircuitRegistry`
+//! - `decode_verify_context()` - Decode the circuit description a proof commits to
+//! - `prove_timed()` / `verify_timed()` - Same as `prove()` / `verify()`, also reporting phase durations
+//! - `prove_with_params()` - Generate a proof with params loaded from a file instead of generated
+//! - `clear_prove_cache()` - Drop the params and proving keys `prove()` keeps between calls
 //!
 //! Both CLI and WASM bindings use these functions as their core implementation.
 
 use crate::circuit::{
-    Circuit, CircuitAuto, CircuitBoolean, CircuitBitD, CircuitLookup,
-    estimate_circuit_requirements_with_strategy, validate_strategy_compatibility,
+    Circuit, CircuitAuto, CircuitBoolean, CircuitBitD, CircuitLookup, CircuitRegistry, Statement,
+    balance_chains, circuit_fingerprint, estimate_circuit_requirements_with_strategy, lint_circuit, validate_strategy_compatibility,
 };
-use crate::api::{ProveRequest, ProveResponse, VerifyRequest, VerifyResponse, DebugInfo, PublicSignal, VerifyContext};
+use crate::api::transcript::{Keccak256Read, Keccak256Write, TranscriptHash};
+use crate::api::{DEFAULT_MAX_K, ProveRequest, ProveResponse, VerifyRequest, VerifyResponse, VerifyErrorKind, CheckResponse, EvaluateResponse, DebugInfo, ComparisonBound, BoundKind, PublicSignal, VerifyContext, Timings};
 use halo2_proofs::pasta::{Fp, EqAffine};
 use halo2_proofs::poly::commitment::Params;
-use halo2_proofs::plonk::{Circuit as PlonkCircuit, keygen_vk, keygen_pk, create_proof, verify_proof, SingleVerifier};
-use halo2_proofs::transcript::{Blake2bWrite, Blake2bRead, Challenge255};
-use rand_core::OsRng;
+use halo2_proofs::plonk::{Circuit as PlonkCircuit, ProvingKey, keygen_vk, create_proof, verify_proof, SingleVerifier};
+use halo2_proofs::transcript::{Blake2bWrite, Blake2bRead, Challenge255, Transcript, TranscriptWrite};
+use rand_core::{CryptoRng, OsRng, RngCore};
 use indexmap::IndexMap;
 use crate::api::program::Signal;
+use crate::encoding::ValueEncoding;
+use crate::error::ZkplexError;
+use crate::parser::Expression;
+use once_cell::sync::Lazy;
+use std::time::Instant;
+
+/// Params and proving keys shared by every `prove()` call in the process
+///
+/// Only key material is cached (see [`CircuitRegistry::setup`]); witnesses and
+/// proof randomness are per call. The registry locks itself only to look up and
+/// insert keys, so concurrent calls prove in parallel.
+static PROVE_CACHE: Lazy<CircuitRegistry> = Lazy::new(CircuitRegistry::new);
+
+/// Drop the params and proving keys cached by `prove()` and `prove_timed()`
+///
+/// The cache keeps the [`DEFAULT_REGISTRY_CAPACITY`](crate::circuit::DEFAULT_REGISTRY_CAPACITY)
+/// most recently used circuit shapes; this releases their memory early.
+pub fn clear_prove_cache() {
+    PROVE_CACHE.clear();
+}
 
 /// Generate a zero-knowledge proof
 ///
+/// Params and proving keys are kept in a process-wide cache keyed by circuit
+/// fingerprint, so only the first proof of each circuit shape pays for key
+/// generation. The cache is only locked to look up and insert keys, so calls
+/// from several threads prove concurrently.
+///
+/// # Arguments
+/// * `request` - Proof generation request containing circuit and signals
+///
+/// # Returns
+/// * `Ok(ProveResponse)` - Proof and verification context
+/// * `Err(ZkplexError)` - Why proof generation failed
+pub fn prove(request: ProveRequest) -> Result<ProveResponse, ZkplexError> {
+    prove_inner(&PROVE_CACHE, request, None, &mut Timings::default())
+}
+
+/// Generate a zero-knowledge proof and report how long each phase took
+///
+/// Produces the same response as `prove()` and shares its key cache; the
+/// timings cover VK generation, PK generation (both `None` on a cache hit),
+/// and proof creation.
+pub fn prove_timed(request: ProveRequest) -> Result<(ProveResponse, Timings), ZkplexError> {
+    let mut timings = Timings::default();
+    let response = prove_inner(&PROVE_CACHE, request, None, &mut timings)?;
+    Ok((response, timings))
+}
+
+/// Generate a zero-knowledge proof with serialized params instead of fresh ones
+///
+/// Skips `Params::new(k)`, which dominates setup time for large circuits.
+/// Params are deterministic per `k`, so the proof is the same as `prove()`'s and
+/// verifies without the file.
+///
+/// # Arguments
+/// * `request` - Proof generation request containing circuit and signals
+/// * `params_bytes` - Params from [`params_to_bytes`](crate::api::params_to_bytes)
+///
+/// # Returns
+/// * `Err(ZkplexError)` - Also if the params are not for the `k` the circuit requires
+pub fn prove_with_params(request: ProveRequest, params_bytes: &[u8]) -> Result<ProveResponse, ZkplexError> {
+    prove_with_params_timed(request, params_bytes).map(|(response, _)| response)
+}
+
+/// Same as `prove_with_params()`, also reporting phase durations
+pub fn prove_with_params_timed(request: ProveRequest, params_bytes: &[u8]) -> Result<(ProveResponse, Timings), ZkplexError> {
+    let mut timings = Timings::default();
+    let response = prove_inner(&CircuitRegistry::new(), request, Some(params_bytes), &mut timings)?;
+    Ok((response, timings))
+}
+
+/// Generate zero-knowledge proofs for many requests
+///
+/// Requests that share a circuit shape (same statements, public signal layout,
+/// strategy, and size) reuse the params and proving key of the first such request.
+/// A failing request does not abort the batch; its error is returned in place.
+///
+/// # Arguments
+/// * `requests` - Proof generation requests, in order
+///
+/// # Returns
+/// * One result per request, in the same order
+pub fn prove_batch(requests: Vec<ProveRequest>) -> Vec<Result<ProveResponse, ZkplexError>> {
+    let mut registry = CircuitRegistry::new();
+    requests
+        .into_iter()
+        .map(|request| prove_with_registry(&mut registry, request))
+        .collect()
+}
+
+/// Generate zero-knowledge proofs for many requests concurrently
+///
+/// Runs on a rayon pool of `jobs` threads (0 = one per CPU). Each worker keeps its
+/// own `CircuitRegistry`, so requests sharing a circuit shape reuse keys within a
+/// worker. Proof randomness comes from `OsRng`, which reads the OS generator on
+/// every call and is safe to use from any thread.
+///
+/// # Arguments
+/// * `requests` - Proof generation requests, in order
+/// * `jobs` - Maximum number of proofs generated at once
+///
+/// # Returns
+/// * One result per request, in the same order as `prove_batch()`
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+pub fn prove_batch_parallel(requests: Vec<ProveRequest>, jobs: usize) -> Vec<Result<ProveResponse, ZkplexError>> {
+    use rayon::prelude::*;
+
+    let pool = match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool,
+        // Could not spawn worker threads, prove serially instead
+        Err(_) => return prove_batch(requests),
+    };
+
+    pool.install(|| {
+        requests
+            .into_par_iter()
+            .map_init(CircuitRegistry::new, prove_with_registry)
+            .collect()
+    })
+}
+
+/// Generate a zero-knowledge proof, reusing params and proving key from a registry
+///
+/// Keys are looked up by circuit fingerprint (statements, public signal layout,
+/// strategy, `k`, max bits). Signal values are not part of the fingerprint, so
+/// requests with different inputs to the same circuit share one key generation.
+///
 /// # Arguments
+/// * `registry` - Cache of params and proving keys, populated on miss
 /// * `request` - Proof generation request containing circuit and signals
 ///
 /// # Returns
 /// * `Ok(ProveResponse)` - Proof and verification context
-/// * `Err(String)` - Error message if proof generation fails
-pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
+/// * `Err(ZkplexError)` - Why proof generation failed
+pub fn prove_with_registry(
+    registry: &mut CircuitRegistry,
+    request: ProveRequest,
+) -> Result<ProveResponse, ZkplexError> {
+    prove_inner(registry, request, None, &mut Timings::default())
+}
+
+/// Same as `prove_with_registry()`, optionally with serialized params, also reporting phase durations
+///
+/// The params end up in `registry` either way, so callers can save the ones a
+/// proof was made with via [`CircuitRegistry::params`].
+pub fn prove_with_registry_timed(
+    registry: &CircuitRegistry,
+    request: ProveRequest,
+    params_bytes: Option<&[u8]>,
+) -> Result<(ProveResponse, Timings), ZkplexError> {
+    let mut timings = Timings::default();
+    let response = prove_inner(registry, request, params_bytes, &mut timings)?;
+    Ok((response, timings))
+}
+
+/// Shared implementation of the prove functions, recording phase durations in `timings`
+fn prove_inner(
+    registry: &CircuitRegistry,
+    request: ProveRequest,
+    params_bytes: Option<&[u8]>,
+    timings: &mut Timings,
+) -> Result<ProveResponse, ZkplexError> {
+    // Balanced statements replace the originals everywhere, including the verify context.
+    // Inline hash calls are not circuit syntax, so they are hoisted into preprocessing first
+    let mut request = request;
+    if request.balance_chains {
+        let (preprocess, circuit) = crate::preprocess::hoist_inline_hashes(&request.preprocess, &request.circuit);
+        request.preprocess = preprocess;
+        request.circuit = circuit.iter()
+            .map(|stmt| balance_statement(stmt))
+            .collect::<Result<_, _>>()?;
+    }
+
     // Convert request to Program, then build circuit
     let program = request.to_program();
-    let circuit = Circuit::from_program(&program)
-        .map_err(|e| format!("Failed to build circuit: {}", e))?;
+
+    // Reject undeclared signals up front; unused ones are reported as warnings
+    let usage_warnings = program.check_signal_usage().map_err(|errors| errors.join("; "))?;
+
+    let mut circuit = Circuit::from_program(&program)
+        .map_err(|e| e.context("Failed to build circuit"))?;
+
+    if let Some(bits) = request.max_bits {
+        circuit.force_max_bits(bits)?;
+    }
 
     // Validate strategy compatibility with circuit operations
-    validate_strategy_compatibility(&circuit, request.strategy)?;
+    validate_strategy_compatibility(&circuit, request.strategy)
+        .map_err(ZkplexError::UnsupportedOperation)?;
 
     // Estimate circuit requirements to determine k automatically based on strategy
     let estimate = estimate_circuit_requirements_with_strategy(&circuit, Some(request.strategy));
     let k = estimate.k;
 
-    // Generate universal parameters for the circuit size
-    let params: Params<EqAffine> = Params::new(k);
+    // Reject oversized circuits before allocating params for them
+    let max_k = request.max_k.unwrap_or(DEFAULT_MAX_K);
+    if k > max_k {
+        return Err(ZkplexError::CircuitTooLarge { k, max_k });
+    }
+
+    // Supplied params must be for the size this circuit needs
+    if let Some(bytes) = params_bytes {
+        let params_k = crate::api::params_k(bytes)?;
+        if params_k != k {
+            return Err(format!("params are for k={} but the circuit requires k={}", params_k, k).into());
+        }
+        registry.insert_params(k, crate::api::params_from_bytes(bytes)?);
+    }
+
+    let seed = request.seed.as_deref().map(parse_seed).transpose()?;
+    let transcript = TranscriptHash::from_option(request.transcript.as_deref())?;
+    let options = ProofOptions { seed: seed.as_deref(), transcript, metadata: request.metadata.as_deref() };
 
     // Find all output signals (public signals with no value or empty value or "?")
+    // Placeholders naming an assignment are exposed intermediates, not the circuit output
     let output_signals: Vec<String> = request.signals.iter()
         .filter(|(_, sig)| sig.public && sig.value.as_ref().map(|v| v.is_empty() || v == "?").unwrap_or(true))
+        .filter(|(name, _)| !circuit.public_signal_names.contains(name))
         .map(|(name, _)| name.clone())
         .collect();
 
     // Validate that exactly one output signal exists
     if output_signals.is_empty() {
-        return Err("No output signal found. At least one public signal must have no value (or '?') to receive the circuit result.".to_string());
+        return Err("No output signal found. At least one public signal must have no value (or '?') to receive the circuit result.".to_string().into());
     }
     if output_signals.len() > 1 {
         return Err(format!(
             "Multiple output signals found: {}. Only one public signal can have no value (or '?') to receive the circuit result.",
             output_signals.join(", ")
-        ));
+        ).into());
     }
 
     let output_signal_name = output_signals[0].clone();
@@ -66,41 +267,54 @@ pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
     // Collect public signal values (exclude output signal, it will be added separately)
     let mut public_inputs: Vec<Fp> = circuit.public_signal_names.iter()
         .filter(|name| *name != &output_signal_name)
-        .filter_map(|name| circuit.signals.get(name).copied())
+        .flat_map(|name| circuit.public_values(name))
         .collect();
 
     // The output signal value comes from circuit_output, not from circuit.signals
     // circuit_output contains the evaluated result of the circuit expression
     let output_signal_value = circuit.circuit_output
-        .ok_or_else(|| "Circuit did not produce an output value".to_string())?;
+        .ok_or_else(|| ZkplexError::Synthesis("Circuit did not produce an output value".to_string()))?;
 
     // Append circuit_output as the last public input (required for constraint)
     public_inputs.push(output_signal_value);
 
+    // Params and proving key are shared by all requests with the same circuit shape
+    let fingerprint = circuit_fingerprint(&circuit, request.strategy, k);
+
     // Generate proof using the appropriate circuit wrapper based on strategy
     use crate::circuit::Strategy;
     let proof_bytes = match request.strategy {
         Strategy::Boolean => {
             let circuit_wrapped = CircuitBoolean(circuit.clone());
-            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &params)?
+            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &fingerprint, k, &options, registry, timings)
+                .map_err(ZkplexError::Synthesis)?
         }
         Strategy::BitD => {
             let circuit_wrapped = CircuitBitD(circuit.clone());
-            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &params)?
+            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &fingerprint, k, &options, registry, timings)
+                .map_err(ZkplexError::Synthesis)?
         }
         Strategy::Lookup => {
             let circuit_wrapped = CircuitLookup(circuit.clone());
-            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &params)?
+            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &fingerprint, k, &options, registry, timings)
+                .map_err(ZkplexError::Synthesis)?
         }
         Strategy::Auto => {
             let circuit_wrapped = CircuitAuto(circuit.clone());
-            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &params)?
+            generate_proof_for_circuit(circuit_wrapped, public_inputs.clone(), &fingerprint, k, &options, registry, timings)
+                .map_err(ZkplexError::Synthesis)?
         }
     };
 
     // Encode proof with ASCII85 (Adobe standard, compatible with online decoders)
     let proof_encoded = ascii85::encode(&proof_bytes);
 
+    // Collect secret signal names for circuit reconstruction during verification
+    let secret_signals: Vec<String> = request.signals.iter()
+        .filter(|(_, sig)| !sig.public)
+        .map(|(name, _)| name.clone())
+        .collect();
+
     // Check for privacy warnings
     let mut warnings = Vec::new();
     let has_secret_concrete_values = request.signals.iter()
@@ -115,12 +329,28 @@ pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
         );
     }
 
+    // Flag always-true/always-false sub-expressions
+    warnings.extend(lint_circuit(&circuit).iter().map(|w| w.to_string()));
+    warnings.extend(usage_warnings);
+    warnings.extend(secret_leak_warnings(&circuit, &secret_signals, &output_signal_name));
+
+    if !circuit.constants.is_empty() {
+        let names: Vec<&str> = circuit.constants.keys().map(String::as_str).collect();
+        warnings.push(format!(
+            "Constant signal(s) {} are part of the verifying key: changing their values requires a new verifying key",
+            names.join(", ")
+        ));
+    }
+
     // Prepare public signals output with encoding information
-    let public_signals_output: IndexMap<String, PublicSignal> = request.signals.iter()
-        .filter(|(_, sig)| sig.public)
+    let mut public_signals_output: IndexMap<String, PublicSignal> = request.signals.iter()
+        .filter(|(_, sig)| sig.public && !sig.constant)
         .map(|(name, sig)| {
             let value = if name == &output_signal_name {
                 field_to_u64(&output_signal_value).to_string()
+            } else if sig.value.as_ref().map(|v| v.is_empty() || v == "?").unwrap_or(true) {
+                // Exposed intermediate: report the value the circuit computed
+                circuit.signals.get(name).map(field_to_decimal).unwrap_or_default()
             } else {
                 sig.value.clone().unwrap_or_default()
             };
@@ -131,14 +361,16 @@ pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
         })
         .collect();
 
-    // Collect secret signal names for circuit reconstruction during verification
-    let secret_signals: Vec<String> = request.signals.iter()
-        .filter(|(_, sig)| !sig.public)
-        .map(|(name, _)| name.clone())
-        .collect();
+    // `pub` assignments not declared as signals are reported after the declared ones
+    for name in &circuit.public_signal_names {
+        if !public_signals_output.contains_key(name) {
+            let value = circuit.signals.get(name).map(field_to_decimal).unwrap_or_default();
+            public_signals_output.insert(name.clone(), PublicSignal { value, encoding: None });
+        }
+    }
 
     // Create verification context
-    let verify_context = VerifyContext {
+    let mut verify_context = VerifyContext {
         k,
         preprocess: request.preprocess.clone(),
         circuit: request.circuit.clone(),
@@ -146,7 +378,17 @@ pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
         secret_signals: secret_signals.clone(),
         output_signal: output_signal_name.clone(),
         cached_max_bits: circuit.cached_max_bits,
+        signal_bits: circuit.signal_bits.clone(),
+        wide_signals: circuit.wide_signals.clone(),
+        array_lengths: circuit.array_lengths.clone(),
+        constants: circuit.constants.iter().map(|(name, value)| (name.clone(), field_to_decimal(value))).collect(),
+        // The default is left out, so contexts of BLAKE2b proofs are unchanged
+        transcript: (transcript != TranscriptHash::default()).then(|| transcript.to_string()),
+        metadata: request.metadata.clone(),
+        circuit_hash: String::new(),
+        public_inputs_hash: public_inputs_hash(&public_inputs),
     };
+    verify_context.circuit_hash = verify_context.compute_circuit_hash();
 
     // Serialize verification context to JSON
     let verify_context_json = serde_json::to_string(&verify_context)
@@ -165,6 +407,7 @@ pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
         secret_signals,
         output_signal: output_signal_name,
         warnings: if warnings.is_empty() { None } else { Some(warnings) },
+        comparison_bounds: comparison_bounds(&circuit),
     };
 
     // Create response
@@ -173,6 +416,7 @@ pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
         proof: proof_encoded,
         verify_context: verify_context_encoded,
         public_signals: public_signals_output,
+        circuit_hash: verify_context.circuit_hash,
         debug: Some(debug_info),
     })
 }
@@ -183,18 +427,76 @@ pub fn prove(request: ProveRequest) -> Result<ProveResponse, String> {
 /// * `request` - Verification request containing proof, context and public signals
 ///
 /// # Returns
-/// * `Ok(VerifyResponse)` - Verification result (valid/invalid)
-/// * `Err(String)` - Error message if verification fails
-pub fn verify(request: VerifyRequest) -> Result<VerifyResponse, String> {
-    // Decode verification context
-    let verify_context_bytes = ascii85::decode(&request.verify_context)
+/// * `Ok(VerifyResponse)` - Verification result (valid/invalid). A proof that is
+///   empty or not valid Base85 is invalid, with an error starting with
+///   "malformed proof encoding:". So is a proof of a circuit other than
+///   `request.expected_circuit_hash`, when set. `error_kind` classifies the
+///   failure; proofs from before `public_inputs_hash` was recorded report a
+///   wrong public signal as `ProofInvalid`, since the two cannot be told apart.
+/// * `Err(ZkplexError)` - Why the proof could not be checked (e.g. an undecodable context)
+pub fn verify(request: VerifyRequest) -> Result<VerifyResponse, ZkplexError> {
+    verify_inner(request, &mut Timings::default())
+}
+
+/// Verify a zero-knowledge proof and report how long each phase took
+///
+/// Produces the same response as `verify()`; the timings cover VK generation
+/// and verification.
+pub fn verify_timed(request: VerifyRequest) -> Result<(VerifyResponse, Timings), ZkplexError> {
+    let mut timings = Timings::default();
+    let response = verify_inner(request, &mut timings)?;
+    Ok((response, timings))
+}
+
+/// Decode a proof's verification context without verifying the proof
+///
+/// The context holds everything the verifying key is derived from: circuit and
+/// preprocess statements, strategy, `k`, secret signal names, output signal,
+/// and max bits. Auditors can use it to inspect what circuit a proof commits to.
+///
+/// # Arguments
+/// * `verify_context` - Base85-encoded context, as in `ProveResponse::verify_context`
+pub fn decode_verify_context(verify_context: &str) -> Result<VerifyContext, String> {
+    let verify_context_bytes = ascii85::decode(verify_context)
         .map_err(|e| format!("Failed to decode verification context: {}", e))?;
 
     let verify_context_json = String::from_utf8(verify_context_bytes)
         .map_err(|e| format!("Failed to decode verification context as UTF-8: {}", e))?;
 
-    let verify_context: VerifyContext = serde_json::from_str(&verify_context_json)
-        .map_err(|e| format!("Failed to parse verification context: {}", e))?;
+    serde_json::from_str(&verify_context_json)
+        .map_err(|e| format!("Failed to parse verification context: {}", e))
+}
+
+/// Shared implementation of the verify functions, recording phase durations in `timings`
+fn verify_inner(request: VerifyRequest, timings: &mut Timings) -> Result<VerifyResponse, ZkplexError> {
+    // A proof that cannot be decoded is invalid, not an error, and needs no VK
+    let proof_bytes = match decode_proof(&request.proof) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return Ok(invalid_proof(VerifyErrorKind::DecodeError, format!("malformed proof encoding: {}", e)));
+        }
+    };
+
+    let verify_context = decode_verify_context(&request.verify_context)?;
+    let transcript = TranscriptHash::from_option(verify_context.transcript.as_deref())?;
+
+    // The hash is recomputed rather than trusted, so an edited context cannot
+    // claim the hash of another circuit
+    let circuit_hash = verify_context.compute_circuit_hash();
+    if !verify_context.circuit_hash.is_empty() && verify_context.circuit_hash != circuit_hash {
+        return Ok(invalid_proof(
+            VerifyErrorKind::CircuitReconstructionFailed,
+            "verification context does not match its circuit hash".to_string(),
+        ));
+    }
+    if let Some(expected) = &request.expected_circuit_hash {
+        if !expected.trim().eq_ignore_ascii_case(&circuit_hash) {
+            return Ok(invalid_proof(
+                VerifyErrorKind::CircuitReconstructionFailed,
+                format!("circuit hash {} does not match the expected {}", circuit_hash, expected.trim()),
+            ));
+        }
+    }
 
     // Convert to program and build circuit
 
@@ -212,6 +514,8 @@ pub fn verify(request: VerifyRequest) -> Result<VerifyResponse, String> {
         public_sigs.insert(name.clone(), Signal {
             value: Some(public_sig.value.clone()),
             encoding: public_sig.encoding,
+            bits: verify_context.signal_bits.get(name).copied(),
+            constant: false,
         });
     }
 
@@ -221,6 +525,18 @@ pub fn verify(request: VerifyRequest) -> Result<VerifyResponse, String> {
         secret_sigs.insert(name.clone(), Signal {
             value: None,  // No value - will be skipped during circuit building
             encoding: None,
+            bits: verify_context.signal_bits.get(name).copied(),
+            constant: false,
+        });
+    }
+
+    // Constants are rebuilt into the circuit from the values the prover used
+    for (name, value) in &verify_context.constants {
+        public_sigs.insert(name.clone(), Signal {
+            value: Some(value.clone()),
+            encoding: Some(ValueEncoding::Decimal),
+            bits: None,
+            constant: true,
         });
     }
 
@@ -230,15 +546,31 @@ pub fn verify(request: VerifyRequest) -> Result<VerifyResponse, String> {
         public: public_sigs,
         preprocess: verify_context.preprocess.clone(),
         circuit: verify_context.circuit.clone(),
+        strategy: verify_context.strategy,
     };
 
-    let mut circuit = Circuit::from_program(&program)
-        .map_err(|e| format!("Failed to build circuit: {}", e))?;
+    let mut circuit = match Circuit::from_program(&program) {
+        Ok(circuit) => circuit,
+        Err(e) => {
+            let error = e.context("Failed to build circuit").to_string();
+            return Ok(invalid_proof(VerifyErrorKind::CircuitReconstructionFailed, error));
+        }
+    };
 
     // Restore cached_max_bits from verify context (needed for range check table size)
     // This is essential because circuit.signals may be empty during verification
     circuit.cached_max_bits = verify_context.cached_max_bits;
 
+    // Secret wide signals are unknown here, so rebuild their limb-wise equalities
+    circuit.split_wide_equalities(&verify_context.wide_signals);
+
+    // Secret arrays have no values here, so expand reductions from their lengths
+    // and restore the widths declared for their elements
+    if let Err(e) = circuit.expand_arrays(&verify_context.array_lengths) {
+        return Ok(invalid_proof(VerifyErrorKind::CircuitReconstructionFailed, e));
+    }
+    circuit.signal_bits.extend(verify_context.signal_bits.iter().map(|(name, bits)| (name.clone(), *bits)));
+
     // Generate params with the same k used during proof generation
     let params: Params<EqAffine> = Params::new(verify_context.k);
 
@@ -246,48 +578,140 @@ pub fn verify(request: VerifyRequest) -> Result<VerifyResponse, String> {
     // IMPORTANT: Exclude output signal from public_signal_names, as it will be added separately
     let mut public_inputs: Vec<Fp> = circuit.public_signal_names.iter()
         .filter(|name| *name != &verify_context.output_signal)
-        .filter_map(|name| circuit.signals.get(name).copied())
+        .flat_map(|name| circuit.public_values(name))
         .collect();
 
     // Add output signal value from public signals
-    let output_str = request.public_signals.get(&verify_context.output_signal)
-        .map(|sig| &sig.value)
-        .ok_or_else(|| format!("Missing output signal '{}' in public signals", verify_context.output_signal))?;
+    let Some(output_str) = request.public_signals.get(&verify_context.output_signal).map(|sig| &sig.value) else {
+        return Ok(invalid_proof(
+            VerifyErrorKind::PublicInputMismatch,
+            format!("Missing output signal '{}' in public signals", verify_context.output_signal),
+        ));
+    };
 
-    let output_u64: u64 = output_str.parse()
-        .map_err(|_| "Failed to parse output value from proof".to_string())?;
+    let Ok(output_u64) = output_str.parse::<u64>() else {
+        return Ok(invalid_proof(
+            VerifyErrorKind::PublicInputMismatch,
+            format!("Failed to parse output value '{}' of signal '{}'", output_str, verify_context.output_signal),
+        ));
+    };
     let output_fp = Fp::from(output_u64);
     public_inputs.push(output_fp);
 
-    // Generate VK for the same strategy as was used during proving
-    let vk = generate_vk_for_strategy(&circuit, verify_context.strategy, &params)?;
-
-    // Decode proof
-    let proof_bytes = ascii85::decode(&request.proof)
-        .map_err(|e| format!("Failed to decode proof: {}", e))?;
+    // Generate VK for the same strategy as was used during proving.
+    // It is regenerated on every call: the halo2 fork we build on has no
+    // VerifyingKey read/write (its fields are private), so a VK cannot be
+    // saved to a file and loaded back here.
+    let vk_start = Instant::now();
+    let vk = match generate_vk_for_strategy(&circuit, verify_context.strategy, &params) {
+        Ok(vk) => vk,
+        Err(e) => return Ok(invalid_proof(VerifyErrorKind::CircuitReconstructionFailed, e)),
+    };
+    timings.vk_generation = Some(vk_start.elapsed());
 
-    // Verify the proof
+    // Verify the proof with the transcript it was created with
     let strategy = SingleVerifier::new(&params);
-    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(&proof_bytes[..]);
 
     let public_inputs_slice: &[Fp] = &public_inputs;
     let public_inputs_for_verification: &[&[Fp]] = &[public_inputs_slice];
 
-    let verification_result = verify_proof(
-        &params,
-        &vk,
-        strategy,
-        &[public_inputs_for_verification],
-        &mut transcript,
-    );
+    let verify_start = Instant::now();
+    let verification_result = match transcript {
+        TranscriptHash::Blake2b => {
+            let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(&proof_bytes[..]);
+            absorb_metadata(&mut transcript, verify_context.metadata.as_deref())?;
+            verify_proof(&params, &vk, strategy, &[public_inputs_for_verification], &mut transcript)
+        }
+        TranscriptHash::Keccak256 => {
+            let mut transcript = Keccak256Read::<_, EqAffine, Challenge255<_>>::init(&proof_bytes[..]);
+            absorb_metadata(&mut transcript, verify_context.metadata.as_deref())?;
+            verify_proof(&params, &vk, strategy, &[public_inputs_for_verification], &mut transcript)
+        }
+    };
+    timings.verification = Some(verify_start.elapsed());
+
+    // The output is the last public input, which a valid proof is bound to
+    let output = verification_result.is_ok().then(|| PublicSignal {
+        value: public_inputs.last().map(field_to_decimal).unwrap_or_default(),
+        encoding: None,
+    });
+
+    let metadata = verification_result.is_ok().then(|| verify_context.metadata.clone()).flatten();
+
+    // Halo2 cannot say why a proof failed; the recorded hash tells whether the
+    // public inputs are the ones it was created with
+    let error_kind = verification_result.is_err().then(|| {
+        let recorded = &verify_context.public_inputs_hash;
+        if !recorded.is_empty() && *recorded != public_inputs_hash(&public_inputs) {
+            VerifyErrorKind::PublicInputMismatch
+        } else {
+            VerifyErrorKind::ProofInvalid
+        }
+    });
 
     // Create response
     Ok(VerifyResponse {
         valid: verification_result.is_ok(),
         error: verification_result.err().map(|e| format!("{:?}", e)),
+        error_kind,
+        output,
+        metadata,
     })
 }
 
+/// Response for a proof rejected before or instead of running the verifier
+fn invalid_proof(kind: VerifyErrorKind, error: String) -> VerifyResponse {
+    VerifyResponse {
+        valid: false,
+        error: Some(error),
+        error_kind: Some(kind),
+        output: None,
+        metadata: None,
+    }
+}
+
+/// SHA-256 (hex) over the canonical encodings of the public inputs, in order
+fn public_inputs_hash(public_inputs: &[Fp]) -> String {
+    use ff::PrimeField;
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for input in public_inputs {
+        hasher.update(input.to_repr());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Decode a Base85 proof, rejecting empty, truncated and non-Base85 input
+fn decode_proof(proof: &str) -> Result<Vec<u8>, String> {
+    let start = proof.len() - proof.trim_start().len();
+    let trimmed = proof.trim();
+    if trimmed.is_empty() {
+        return Err("proof is empty".to_string());
+    }
+
+    let (body, offset) = match trimmed.strip_prefix("<~") {
+        Some(rest) => {
+            let body = rest.strip_suffix("~>")
+                .ok_or_else(|| "proof is truncated (missing the closing '~>')".to_string())?;
+            (body, start + 2)
+        }
+        None => (trimmed, start),
+    };
+
+    if let Some((index, c)) = body.char_indices()
+        .find(|(_, c)| !c.is_whitespace() && !('!'..='u').contains(c) && *c != 'z')
+    {
+        return Err(format!("character '{}' at index {} is not in the Base85 alphabet", c, offset + index));
+    }
+
+    let bytes = ascii85::decode(trimmed).map_err(|e| e.to_string())?;
+    if bytes.is_empty() {
+        return Err("proof is empty".to_string());
+    }
+    Ok(bytes)
+}
+
 /// Estimate circuit requirements
 ///
 /// # Arguments
@@ -318,6 +742,104 @@ pub fn estimate(request: ProveRequest) -> Result<crate::api::EstimateResponse, S
         params_size_bytes: estimate.params_size_bytes,
         proof_size_bytes: estimate.proof_size_bytes,
         vk_size_bytes: estimate.vk_size_bytes,
+        critical_path_depth: estimate.critical_path_depth,
+    })
+}
+
+/// Check whether the witness satisfies all constraints, without generating a proof
+///
+/// Builds the circuit and runs Halo2's `MockProver` at the estimated `k`. This skips
+/// parameter, key, and proof generation, so it is fast enough for live validation.
+///
+/// # Arguments
+/// * `request` - Same request as for `prove()`
+///
+/// # Returns
+/// * `Ok(CheckResponse)` - Whether constraints hold, the computed output, and any failures
+/// * `Err(String)` - Error message if the circuit cannot be built or synthesized
+pub fn check(request: ProveRequest) -> Result<CheckResponse, String> {
+    // Convert request to Program, then build circuit
+    let program = request.to_program();
+    let circuit = Circuit::from_program(&program)
+        .map_err(|e| format!("Failed to build circuit: {}", e))?;
+
+    // Validate strategy compatibility
+    validate_strategy_compatibility(&circuit, request.strategy)?;
+
+    let k = estimate_circuit_requirements_with_strategy(&circuit, Some(request.strategy)).k;
+
+    // Public inputs in the same layout as prove(): public signals, then circuit output
+    let mut public_inputs: Vec<Fp> = circuit.public_signal_names.iter()
+        .flat_map(|name| circuit.public_values(name))
+        .collect();
+    if let Some(output) = circuit.circuit_output {
+        public_inputs.push(output);
+    }
+
+    use crate::circuit::Strategy;
+    let failures = match request.strategy {
+        Strategy::Boolean => mock_prove_for_circuit(CircuitBoolean(circuit.clone()), public_inputs, k)?,
+        Strategy::BitD => mock_prove_for_circuit(CircuitBitD(circuit.clone()), public_inputs, k)?,
+        Strategy::Lookup => mock_prove_for_circuit(CircuitLookup(circuit.clone()), public_inputs, k)?,
+        Strategy::Auto => mock_prove_for_circuit(CircuitAuto(circuit.clone()), public_inputs, k)?,
+    };
+
+    Ok(CheckResponse {
+        satisfied: failures.is_empty(),
+        k,
+        output: circuit.circuit_output.as_ref().map(field_to_decimal),
+        failures,
+    })
+}
+
+/// Compute a program's circuit output and intermediate signals, without proving
+///
+/// Only builds the circuit, which already evaluates every statement, so it
+/// is essentially free. Constraints are not checked: use `check()` for that.
+///
+/// # Arguments
+/// * `program` - Program with values for all input signals
+///
+/// # Returns
+/// * `Ok(EvaluateResponse)` - Output in decimal (and in the output signal's encoding)
+///   and the values of preprocess outputs and circuit assignments
+/// * `Err(ZkplexError)` - Why the circuit could not be built
+pub fn evaluate(program: &crate::api::Program) -> Result<EvaluateResponse, ZkplexError> {
+    use ff::PrimeField;
+
+    let circuit = Circuit::from_program(program)
+        .map_err(|e| e.context("Failed to build circuit"))?;
+
+    // The output signal is the public signal left without a value, as in prove()
+    let output_encoding = program.public.iter()
+        .filter(|(name, _)| !circuit.public_signal_names.contains(name))
+        .find(|(_, sig)| sig.value.as_ref().map(|v| v.is_empty() || v == "?").unwrap_or(true))
+        .and_then(|(_, sig)| sig.encoding)
+        .filter(|encoding| *encoding != ValueEncoding::Decimal);
+    let encoded_output = circuit.circuit_output.zip(output_encoding).map(|(value, encoding)| {
+        let bytes = num_bigint::BigUint::from_bytes_le(value.to_repr().as_ref()).to_bytes_be();
+        PublicSignal {
+            value: crate::encoding::encode_bytes(&bytes, encoding),
+            encoding: Some(encoding),
+        }
+    });
+
+    // Wide preprocess outputs are compared limb by limb, so they have no single field value
+    let preprocess_names = program.preprocess.iter()
+        .filter_map(|stmt| stmt.split_once("<==").map(|(name, _)| name.trim().to_string()))
+        .filter(|name| !circuit.wide_signals.contains_key(name));
+    let assignment_names = circuit.statements.iter().filter_map(|stmt| match stmt {
+        Statement::Assignment { name, .. } => Some(name.clone()),
+        Statement::Expression(_) => None,
+    });
+    let signals = preprocess_names.chain(assignment_names)
+        .filter_map(|name| circuit.signals.get(&name).map(|value| (name, field_to_decimal(value))))
+        .collect();
+
+    Ok(EvaluateResponse {
+        output: circuit.circuit_output.as_ref().map(field_to_decimal),
+        encoded_output,
+        signals,
     })
 }
 
@@ -325,37 +847,281 @@ pub fn estimate(request: ProveRequest) -> Result<crate::api::EstimateResponse, S
 // Helper functions
 // ============================================================================
 
+/// Run MockProver for a specific circuit type and collect constraint failures
+fn mock_prove_for_circuit<C>(
+    circuit: C,
+    public_inputs: Vec<Fp>,
+    k: u32,
+) -> Result<Vec<String>, String>
+where
+    C: PlonkCircuit<Fp>,
+{
+    use halo2_proofs::dev::MockProver;
+
+    let prover = MockProver::run(k, &circuit, vec![public_inputs])
+        .map_err(|e| format!("Failed to synthesize circuit: {:?}", e))?;
+
+    Ok(match prover.verify() {
+        Ok(()) => Vec::new(),
+        Err(failures) => failures.iter().map(|f| f.to_string()).collect(),
+    })
+}
+
+/// How a proof is created: randomness seed and transcript hash
+struct ProofOptions<'a> {
+    /// Seed for deterministic randomness (`OsRng` when None)
+    seed: Option<&'a [u8]>,
+    /// Fiat–Shamir transcript hash
+    transcript: TranscriptHash,
+    /// Caller metadata absorbed into the transcript (nothing absorbed when None)
+    metadata: Option<&'a str>,
+}
+
+/// Rewrite a circuit statement (`expr` or `name <== expr`) with balanced operator chains
+fn balance_statement(stmt: &str) -> Result<String, ZkplexError> {
+    let (target, expr_str) = match stmt.find("<==") {
+        Some(pos) => (Some(stmt[..pos].trim()), stmt[pos + 3..].trim()),
+        None => (None, stmt.trim()),
+    };
+    let expr = crate::parser::parse_circuit(expr_str)
+        .map_err(|e| ZkplexError::Parse(format!("Failed to parse expression '{}': {}", expr_str, e)))?;
+    let balanced = balance_chains(&expr);
+
+    Ok(match target {
+        Some(name) => format!("{} <== {}", name, balanced),
+        None => balanced.to_string(),
+    })
+}
+
 /// Generate proof for a specific circuit type
+///
+/// Params and PK come from `registry`, generated on first use of `fingerprint`.
+/// Key generation is only timed when it actually ran (a cache miss).
+/// With a `seed` the proof randomness is deterministic, otherwise it comes from `OsRng`.
 fn generate_proof_for_circuit<C>(
     circuit: C,
     public_inputs: Vec<Fp>,
-    params: &Params<EqAffine>,
+    fingerprint: &str,
+    k: u32,
+    options: &ProofOptions,
+    registry: &CircuitRegistry,
+    timings: &mut Timings,
 ) -> Result<Vec<u8>, String>
 where
-    C: PlonkCircuit<Fp> + Clone,
+    C: PlonkCircuit<Fp>,
 {
-    let empty_wrapped = circuit.clone().without_witnesses();
-
-    // Generate VK
-    let vk = keygen_vk(params, &empty_wrapped)
-        .map_err(|e| format!("Failed to generate VK: {:?}", e))?;
+    let (keys, keygen) = registry.setup(fingerprint, &circuit, k)?;
+    if let Some((vk_time, pk_time)) = keygen {
+        timings.vk_generation = Some(vk_time);
+        timings.pk_generation = Some(pk_time);
+    }
+    let (params, pk) = (&keys.params, &keys.pk);
 
-    // Generate PK
-    let pk = keygen_pk(params, vk.clone(), &empty_wrapped)
-        .map_err(|e| format!("Failed to generate PK: {:?}", e))?;
+    let public_inputs_slice: &[Fp] = &public_inputs;
+    let public_inputs_for_circuit: &[&[Fp]] = &[public_inputs_slice];
 
     // Create proof
-    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    let proof_start = Instant::now();
+    let proof = match options.transcript {
+        TranscriptHash::Blake2b => {
+            let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+            write_proof(params, pk, circuit, public_inputs_for_circuit, options, &mut transcript)?;
+            transcript.finalize()
+        }
+        TranscriptHash::Keccak256 => {
+            let mut transcript = Keccak256Write::<_, EqAffine, Challenge255<_>>::init(vec![]);
+            write_proof(params, pk, circuit, public_inputs_for_circuit, options, &mut transcript)?;
+            transcript.finalize()
+        }
+    };
+    timings.proof_creation = Some(proof_start.elapsed());
 
-    let public_inputs_slice: &[Fp] = &public_inputs;
-    let public_inputs_for_circuit: &[&[Fp]] = &[public_inputs_slice];
+    Ok(proof)
+}
+
+/// Create a proof into `transcript`, with seeded or OS randomness
+fn write_proof<C, T>(
+    params: &Params<EqAffine>,
+    pk: &ProvingKey<EqAffine>,
+    circuit: C,
+    instances: &[&[Fp]],
+    options: &ProofOptions,
+    transcript: &mut T,
+) -> Result<(), String>
+where
+    C: PlonkCircuit<Fp>,
+    T: TranscriptWrite<EqAffine, Challenge255<EqAffine>>,
+{
+    absorb_metadata(transcript, options.metadata)?;
+    let result = match options.seed {
+        Some(seed) => create_proof(params, pk, &[circuit], &[instances], SeededRng::new(seed), transcript),
+        None => create_proof(params, pk, &[circuit], &[instances], OsRng, transcript),
+    };
+    result.map_err(|e| format!("Failed to create proof: {:?}", e))
+}
+
+/// Absorb caller metadata into a transcript before the proof is written or read
+///
+/// The SHA-256 of the metadata, reduced into a field element, is a common
+/// scalar: every challenge depends on it, so a proof only verifies with the
+/// metadata it was created with. Without metadata nothing is absorbed and
+/// proofs are unchanged.
+fn absorb_metadata<T>(transcript: &mut T, metadata: Option<&str>) -> Result<(), String>
+where
+    T: Transcript<EqAffine, Challenge255<EqAffine>>,
+{
+    use ff::PrimeField;
+    use sha2::{Digest, Sha256};
+
+    let Some(metadata) = metadata else {
+        return Ok(());
+    };
+    let mut repr = [0u8; 32];
+    repr.copy_from_slice(&Sha256::digest(metadata.as_bytes()));
+    // Clear the top bits so the little-endian value is below the modulus
+    repr[31] &= 0x3f;
+    let scalar = Option::<Fp>::from(Fp::from_repr(repr))
+        .ok_or_else(|| "Failed to encode metadata as a field element".to_string())?;
+    transcript.common_scalar(scalar)
+        .map_err(|e| format!("Failed to absorb metadata into transcript: {}", e))
+}
+
+/// Warn about public signals that are a plain copy of a secret signal
+///
+/// A heuristic privacy guard: it only follows direct assignments
+/// (`result<==secret`, `t<==secret; pub out<==t`), not arithmetic on secrets.
+fn secret_leak_warnings(circuit: &Circuit, secret_signals: &[String], output_signal: &str) -> Vec<String> {
+    // Assignments copying another signal: target -> source
+    let copies: std::collections::HashMap<&str, &str> = circuit.statements.iter()
+        .filter_map(|stmt| match stmt {
+            Statement::Assignment { name, expression: Expression::Variable(source) } => Some((name.as_str(), source.as_str())),
+            _ => None,
+        })
+        .collect();
+
+    // Follow copies back to a secret signal the value originates from
+    let secret_origin = |name: &str| {
+        let mut source = name.to_string();
+        for _ in 0..copies.len() {
+            match copies.get(source.as_str()) {
+                Some(copied) => source = copied.to_string(),
+                None => break,
+            }
+        }
+        secret_signals.contains(&source).then_some(source)
+    };
+
+    let mut leaks = Vec::new();
+
+    // The circuit output is the value of the main expression or last statement
+    let output_source = match (&circuit.expression, circuit.statements.last()) {
+        (Some(Expression::Variable(name)), _) => Some(name.as_str()),
+        (_, Some(Statement::Expression(Expression::Variable(name)))) => Some(name.as_str()),
+        (_, Some(Statement::Assignment { name, .. })) => Some(name.as_str()),
+        _ => None,
+    };
+    if let Some(secret) = output_source.and_then(secret_origin) {
+        leaks.push((output_signal.to_string(), secret));
+    }
+
+    for name in &circuit.public_signal_names {
+        if name != output_signal && copies.contains_key(name.as_str()) {
+            if let Some(secret) = secret_origin(name) {
+                leaks.push((name.clone(), secret));
+            }
+        }
+    }
+
+    leaks.into_iter()
+        .map(|(public, secret)| format!(
+            "Public signal '{}' equals secret signal '{}', so the proof reveals the secret value.",
+            public, secret
+        ))
+        .collect()
+}
+
+/// List the comparison operands that are constants or public signals
+///
+/// Constants are fixed cells of the circuit, so changing one changes the
+/// verifying key; public signals are instance values and do not.
+fn comparison_bounds(circuit: &Circuit) -> Vec<ComparisonBound> {
+    fn collect(expr: &Expression, public: &[String], bounds: &mut Vec<ComparisonBound>) {
+        match expr {
+            Expression::Comparison { left, right, .. } => {
+                for operand in [left, right] {
+                    let kind = match operand.as_ref() {
+                        Expression::Constant(_) | Expression::Boolean(_) => Some(BoundKind::Constant),
+                        Expression::Variable(name) if public.contains(name) => Some(BoundKind::Public),
+                        _ => None,
+                    };
+                    if let Some(kind) = kind {
+                        bounds.push(ComparisonBound { comparison: expr.to_string(), bound: operand.to_string(), kind });
+                    }
+                    collect(operand, public, bounds);
+                }
+            }
+            Expression::BinaryOp { left, right, .. } | Expression::BooleanOp { left, right, .. } => {
+                collect(left, public, bounds);
+                collect(right, public, bounds);
+            }
+            Expression::UnaryOp { operand, .. } => collect(operand, public, bounds),
+            Expression::Call { argument, .. } => collect(argument, public, bounds),
+            Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => {}
+        }
+    }
+
+    let mut bounds = Vec::new();
+    let expressions = circuit.expression.iter().chain(circuit.statements.iter().map(|stmt| match stmt {
+        Statement::Assignment { expression, .. } | Statement::Expression(expression) => expression,
+    }));
+    for expr in expressions {
+        collect(expr, &circuit.public_signal_names, &mut bounds);
+    }
+    bounds
+}
+
+/// Decode a hex proof seed (`0x` prefix optional)
+fn parse_seed(seed: &str) -> Result<Vec<u8>, String> {
+    let bytes = hex::decode(seed.strip_prefix("0x").unwrap_or(seed))
+        .map_err(|e| format!("Invalid seed '{}': {}", seed, e))?;
+    if bytes.is_empty() {
+        return Err("Seed must not be empty".to_string());
+    }
+    Ok(bytes)
+}
+
+/// Deterministic proof randomness: the BLAKE3 output stream keyed by a seed
+struct SeededRng(blake3::OutputReader);
+
+impl SeededRng {
+    fn new(seed: &[u8]) -> Self {
+        let mut hasher = blake3::Hasher::new_derive_key("zkplex-core proof seed");
+        hasher.update(seed);
+        SeededRng(hasher.finalize_xof())
+    }
+}
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
 
-    create_proof(params, &pk, &[circuit], &[public_inputs_for_circuit], OsRng, &mut transcript)
-        .map_err(|e| format!("Failed to create proof: {:?}", e))?;
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.fill(dest);
+    }
 
-    Ok(transcript.finalize())
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
 }
 
+impl CryptoRng for SeededRng {}
+
 /// Generate VK for a specific strategy
 fn generate_vk_for_strategy(
     circuit: &Circuit,
@@ -398,4 +1164,901 @@ fn field_to_u64(f: &Fp) -> u64 {
         value |= (bytes.as_ref()[i] as u64) << (i * 8);
     }
     value
-}
\ No newline at end of file
+}
+
+/// Convert field element to its full decimal representation
+fn field_to_decimal(f: &Fp) -> String {
+    use ff::PrimeField;
+    num_bigint::BigUint::from_bytes_le(f.to_repr().as_ref()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prove_division_by_zero_error() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["A / B"],
+            "signals": {
+                "A": { "value": "10" },
+                "B": { "value": "0" },
+                "out": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let err = prove(request).err().unwrap();
+        assert!(matches!(err, ZkplexError::DivisionByZero(_)), "{:?}", err);
+        assert!(err.to_string().contains("division by zero in expression 'A / B'"), "{}", err);
+    }
+
+    #[test]
+    fn test_check_satisfied() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["A // B == 6"],
+            "signals": {
+                "A": { "value": "20" },
+                "B": { "value": "3" },
+                "out": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let response = check(request).unwrap();
+        assert!(response.satisfied);
+        assert!(response.failures.is_empty());
+        assert_eq!(response.output.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn test_check_output_wider_than_u64() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["A * B"],
+            "signals": {
+                "A": { "value": "1099511627776" },
+                "B": { "value": "1099511627776" },
+                "out": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        // 2^40 * 2^40 = 2^80
+        let response = check(request).unwrap();
+        assert!(response.satisfied);
+        assert_eq!(response.output.as_deref(), Some("1208925819614629174706176"));
+    }
+
+    #[test]
+    fn test_check_reports_mock_prover_failure() {
+        // 2 is not boolean, so the is_bool constraint cannot be satisfied and
+        // MockProver must report the failing constraint
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["is_bool(flag)"],
+            "signals": {
+                "flag": { "value": "2" },
+                "out": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let response = check(request).unwrap();
+        assert!(!response.satisfied);
+        assert!(!response.failures.is_empty());
+
+        // An operand past the 64-bit range table is rejected before MockProver runs
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["A > B"],
+            "signals": {
+                "A": { "value": "18446744073709551617" },
+                "B": { "value": "5" },
+                "out": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let err = check(request).unwrap_err();
+        assert!(err.contains("Value exceeds 2^64 for ordering comparison: 'A' in 'A > B' needs 65 bits"), "{}", err);
+    }
+
+    #[test]
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    fn test_prove_batch_parallel_proofs_verify() {
+        let requests: Vec<ProveRequest> = ["10", "3", "50", "7"].iter()
+            .map(|a| {
+                let program = crate::api::Program::from_zircon(&format!("1/A:{}/out:?/-/A>5", a)).unwrap();
+                crate::api::program_to_prove_request(&program, program.strategy)
+            })
+            .collect();
+
+        let results = prove_batch_parallel(requests, 4);
+        assert_eq!(results.len(), 4);
+
+        // Results stay in request order
+        let outputs: Vec<String> = results.iter()
+            .map(|r| r.as_ref().unwrap().public_signals["out"].value.clone())
+            .collect();
+        assert_eq!(outputs, vec!["1", "0", "1", "1"]);
+
+        for result in results {
+            let response = result.unwrap();
+            let verified = verify(VerifyRequest {
+                version: response.version,
+                proof: response.proof,
+                verify_context: response.verify_context,
+                public_signals: response.public_signals,
+                expected_circuit_hash: None,
+            }).unwrap();
+            assert!(verified.valid);
+        }
+    }
+
+    #[test]
+    fn test_decode_verify_context_round_trip() {
+        let program = crate::api::Program::from_zircon("1/A:10,B:20/out:?/-/sum<==A+B;sum>25").unwrap();
+        let response = prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap();
+
+        let context = decode_verify_context(&response.verify_context).unwrap();
+        assert_eq!(context.circuit, vec!["sum<==A+B", "sum>25"]);
+        assert_eq!(context.secret_signals, vec!["A", "B"]);
+        assert_eq!(context.output_signal, "out");
+        assert_eq!(context.k, response.debug.unwrap().k);
+
+        assert!(decode_verify_context("not base85").is_err());
+    }
+
+    #[test]
+    fn test_verify_malformed_proof_encoding() {
+        let program = crate::api::Program::from_zircon("1/A:10/out:?/-/A>5").unwrap();
+        let response = prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap();
+        let verify_with = |proof: &str| verify(VerifyRequest {
+            version: response.version,
+            proof: proof.to_string(),
+            verify_context: response.verify_context.clone(),
+            public_signals: response.public_signals.clone(),
+            expected_circuit_hash: None,
+        }).unwrap();
+
+        let malformed = |proof: &str| {
+            let result = verify_with(proof);
+            assert!(!result.valid);
+            result.error.unwrap()
+        };
+
+        assert_eq!(malformed(""), "malformed proof encoding: proof is empty");
+        assert_eq!(malformed("<~~>"), "malformed proof encoding: proof is empty");
+
+        let truncated = &response.proof[..response.proof.len() / 2];
+        assert_eq!(malformed(truncated), "malformed proof encoding: proof is truncated (missing the closing '~>')");
+
+        assert_eq!(
+            malformed("<~9jqo\u{263a}~>"),
+            "malformed proof encoding: character '\u{263a}' at index 6 is not in the Base85 alphabet"
+        );
+
+        // A decodable proof that does not verify is reported differently
+        let garbage = ascii85::encode(&[7u8; 64]);
+        let result = verify_with(&garbage);
+        assert!(!result.valid);
+        assert!(!result.error.unwrap().starts_with("malformed proof encoding"));
+
+        assert!(verify_with(&response.proof).valid);
+    }
+
+    #[test]
+    fn test_verify_error_kinds() {
+        let program = crate::api::Program::from_zircon("1/A:25/min:18,out:?/-/A>=min").unwrap();
+        let response = prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap();
+        let verify_with = |proof: &str, public: (&str, &str), expected_circuit_hash: Option<&str>| {
+            let mut public_signals = response.public_signals.clone();
+            public_signals.get_mut(public.0).unwrap().value = public.1.to_string();
+            verify(VerifyRequest {
+                version: response.version,
+                proof: proof.to_string(),
+                verify_context: response.verify_context.clone(),
+                public_signals,
+                expected_circuit_hash: expected_circuit_hash.map(str::to_string),
+            }).unwrap()
+        };
+        let kind = |result: VerifyResponse| {
+            assert!(!result.valid);
+            result.error_kind.unwrap()
+        };
+
+        let valid = verify_with(&response.proof, ("min", "18"), None);
+        assert!(valid.valid);
+        assert_eq!(valid.error_kind, None);
+
+        let mut tampered = decode_proof(&response.proof).unwrap();
+        let middle = tampered.len() / 2;
+        tampered[middle] ^= 1;
+        assert_eq!(kind(verify_with(&ascii85::encode(&tampered), ("min", "18"), None)), VerifyErrorKind::ProofInvalid);
+
+        assert_eq!(kind(verify_with(&response.proof, ("min", "30"), None)), VerifyErrorKind::PublicInputMismatch);
+        assert_eq!(kind(verify_with(&response.proof, ("out", "0"), None)), VerifyErrorKind::PublicInputMismatch);
+        assert_eq!(kind(verify_with("", ("min", "18"), None)), VerifyErrorKind::DecodeError);
+        assert_eq!(
+            kind(verify_with(&response.proof, ("min", "18"), Some("00"))),
+            VerifyErrorKind::CircuitReconstructionFailed
+        );
+        assert_eq!(kind(verify_with(&response.proof, ("out", "yes"), None)), VerifyErrorKind::PublicInputMismatch);
+
+        // A missing output signal is reported, not returned as an error
+        let mut public_signals = response.public_signals.clone();
+        public_signals.shift_remove("out");
+        let result = verify(VerifyRequest {
+            version: response.version,
+            proof: response.proof.clone(),
+            verify_context: response.verify_context.clone(),
+            public_signals,
+            expected_circuit_hash: None,
+        }).unwrap();
+        assert_eq!(kind(result), VerifyErrorKind::PublicInputMismatch);
+
+        // So is a consistent context whose circuit cannot be rebuilt
+        let mut context = decode_verify_context(&response.verify_context).unwrap();
+        context.circuit = vec!["A >= min AND".to_string()];
+        context.circuit_hash = context.compute_circuit_hash();
+        let result = verify(VerifyRequest {
+            version: response.version,
+            proof: response.proof.clone(),
+            verify_context: ascii85::encode(serde_json::to_string(&context).unwrap().as_bytes()),
+            public_signals: response.public_signals.clone(),
+            expected_circuit_hash: None,
+        }).unwrap();
+        assert!(result.error.as_deref().unwrap_or_default().starts_with("Failed to build circuit"), "{:?}", result.error);
+        assert_eq!(kind(result), VerifyErrorKind::CircuitReconstructionFailed);
+    }
+
+    #[test]
+    fn test_verify_returns_bound_output() {
+        let program = crate::api::Program::from_zircon("1/A:10,B:32/out:?/-/A+B").unwrap();
+        let response = prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap();
+        assert_eq!(response.public_signals["out"].value, "42");
+
+        let verify_claiming = |out: &str| {
+            let mut public_signals = response.public_signals.clone();
+            public_signals.get_mut("out").unwrap().value = out.to_string();
+            verify(VerifyRequest {
+                version: response.version,
+                proof: response.proof.clone(),
+                verify_context: response.verify_context.clone(),
+                public_signals,
+                expected_circuit_hash: None,
+            }).unwrap()
+        };
+
+        let result = verify_claiming("42");
+        assert!(result.valid);
+        assert_eq!(result.output.unwrap().value, response.public_signals["out"].value);
+
+        // A claimed output the proof does not bind yields no output
+        let result = verify_claiming("43");
+        assert!(!result.valid);
+        assert!(result.output.is_none());
+    }
+
+    #[test]
+    fn test_verify_expected_circuit_hash() {
+        let program = crate::api::Program::from_zircon("1/age:25/out:?/-/age >= 18").unwrap();
+        let response = prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap();
+        assert_eq!(response.circuit_hash.len(), 64);
+        assert_eq!(decode_verify_context(&response.verify_context).unwrap().circuit_hash, response.circuit_hash);
+
+        let verify_expecting = |expected: Option<String>| verify(VerifyRequest {
+            version: response.version,
+            proof: response.proof.clone(),
+            verify_context: response.verify_context.clone(),
+            public_signals: response.public_signals.clone(),
+            expected_circuit_hash: expected,
+        }).unwrap();
+
+        assert!(verify_expecting(None).valid);
+        assert!(verify_expecting(Some(response.circuit_hash.to_uppercase())).valid);
+
+        // Same proof, but the verifier pinned a different circuit
+        let other = crate::api::Program::from_zircon("1/age:25/out:?/-/age >= 21").unwrap();
+        let other_hash = prove(crate::api::program_to_prove_request(&other, other.strategy)).unwrap().circuit_hash;
+        assert_ne!(other_hash, response.circuit_hash);
+        let result = verify_expecting(Some(other_hash));
+        assert!(!result.valid);
+        assert!(result.error.unwrap().contains("does not match the expected"));
+
+        // Formatting does not change the hash
+        let mut context = decode_verify_context(&response.verify_context).unwrap();
+        context.circuit = vec!["(age)>=  18".to_string()];
+        assert_eq!(context.compute_circuit_hash(), response.circuit_hash);
+
+        // Every field the verifier rebuilds the circuit from is covered
+        let edits: [fn(&mut VerifyContext); 4] = [
+            |c| c.cached_max_bits = Some(c.cached_max_bits.unwrap_or(0) + 8),
+            |c| { c.signal_bits.insert("age".to_string(), 8); },
+            |c| { c.wide_signals.insert("age".to_string(), 2); },
+            |c| { c.array_lengths.insert("age".to_string(), 3); },
+        ];
+        for edit in edits {
+            let mut context = decode_verify_context(&response.verify_context).unwrap();
+            edit(&mut context);
+            assert_ne!(context.compute_circuit_hash(), response.circuit_hash, "{:?}", context);
+        }
+    }
+
+    #[test]
+    fn test_constant_signal() {
+        let request_for = |threshold: u32, constant: bool| -> ProveRequest {
+            serde_json::from_str(&format!(r#"{{
+                "circuit": ["A >= T"],
+                "signals": {{
+                    "A": {{"value": "25"}},
+                    "T": {{"value": "{}", "public": true, "constant": {}}},
+                    "out": {{"public": true}}
+                }}
+            }}"#, threshold, constant)).unwrap()
+        };
+        let circuit_for = |request: &ProveRequest| Circuit::from_program(&request.to_program()).unwrap();
+        let fingerprint_for = |request: &ProveRequest| {
+            crate::circuit::program_fingerprint(&request.to_program(), request.strategy).unwrap()
+        };
+
+        // The threshold is folded into the statement instead of being an instance input
+        let public = circuit_for(&request_for(18, false));
+        let constant = circuit_for(&request_for(18, true));
+        assert_eq!(public.public_signal_names, vec!["T"]);
+        assert!(constant.public_signal_names.is_empty());
+        assert!(!constant.signals.contains_key("T"));
+        let Statement::Expression(expr) = &constant.statements[0] else { panic!("expected an expression") };
+        assert_eq!(expr.variables(), vec!["A"]);
+        let k = |circuit: &Circuit| estimate_circuit_requirements_with_strategy(circuit, None).k;
+        assert!(k(&constant) <= k(&public));
+
+        // A public threshold shares the verifying key across values, a constant does not
+        assert_eq!(fingerprint_for(&request_for(18, false)), fingerprint_for(&request_for(21, false)));
+        assert_ne!(fingerprint_for(&request_for(18, true)), fingerprint_for(&request_for(21, true)));
+
+        let response = prove(request_for(18, true)).unwrap();
+        assert!(!response.public_signals.contains_key("T"));
+        let warnings = response.debug.as_ref().and_then(|debug| debug.warnings.clone()).unwrap_or_default();
+        assert!(warnings.iter().any(|w| w.contains("requires a new verifying key")), "{:?}", warnings);
+        assert_ne!(response.circuit_hash, prove(request_for(21, true)).unwrap().circuit_hash);
+
+        let result = verify(VerifyRequest {
+            version: response.version,
+            proof: response.proof.clone(),
+            verify_context: response.verify_context.clone(),
+            public_signals: response.public_signals.clone(),
+            expected_circuit_hash: None,
+        }).unwrap();
+        assert!(result.valid, "{:?}", result.error);
+
+        // A constant's value ships with the proof, so a secret one is rejected
+        let mut secret = request_for(18, true);
+        secret.signals["A"].constant = true;
+        let err = prove(secret.clone()).unwrap_err();
+        assert!(err.to_string().contains("Secret signal 'A' cannot be constant"), "{}", err);
+        assert!(secret.validate().unwrap_err().contains("Secret signal 'A' cannot be constant"));
+    }
+
+    #[test]
+    fn test_prove_with_registry_reuses_keys() {
+        let request_for = |a: &str, b: &str| -> ProveRequest {
+            serde_json::from_str(&format!(r#"{{
+                "circuit": ["A + B > 25"],
+                "signals": {{
+                    "A": {{ "value": "{}" }},
+                    "B": {{ "value": "{}" }},
+                    "out": {{ "value": "?", "public": true }}
+                }}
+            }}"#, a, b)).unwrap()
+        };
+
+        let mut registry = CircuitRegistry::new();
+        let first = prove_with_registry(&mut registry, request_for("10", "20")).unwrap();
+        let second = prove_with_registry(&mut registry, request_for("3", "4")).unwrap();
+
+        assert_eq!(registry.keygen_count(), 1);
+        assert_eq!(registry.len(), 1);
+        assert_eq!(first.public_signals["out"].value, "1");
+        assert_eq!(second.public_signals["out"].value, "0");
+
+        // Proof made with the cached key still verifies
+        let verify_request = VerifyRequest {
+            version: second.version,
+            proof: second.proof,
+            verify_context: second.verify_context,
+            public_signals: second.public_signals,
+            expected_circuit_hash: None,
+        };
+        assert!(verify(verify_request).unwrap().valid);
+    }
+
+    #[test]
+    fn test_prove_caches_keys_across_calls() {
+        // A shape no other test proves, so the first call is a cache miss
+        let request_for = |a: &str| -> ProveRequest {
+            serde_json::from_str(&format!(r#"{{
+                "circuit": ["A * 3 + 7 != 9172"],
+                "signals": {{
+                    "A": {{ "value": "{}" }},
+                    "out": {{ "value": "?", "public": true }}
+                }}
+            }}"#, a)).unwrap()
+        };
+
+        let (first, first_timings) = prove_timed(request_for("5")).unwrap();
+        let (second, second_timings) = prove_timed(request_for("3055")).unwrap();
+
+        assert!(first_timings.pk_generation.is_some());
+        assert!(second_timings.vk_generation.is_none());
+        assert!(second_timings.pk_generation.is_none());
+        assert!(second_timings.proof_creation.is_some());
+
+        // Witnesses are not cached: each proof carries its own result
+        assert_eq!(first.public_signals["out"].value, "1");
+        assert_eq!(second.public_signals["out"].value, "0");
+        assert_ne!(first.proof, second.proof);
+        assert!(verify(VerifyRequest {
+            version: second.version,
+            proof: second.proof,
+            verify_context: second.verify_context,
+            public_signals: second.public_signals,
+            expected_circuit_hash: None,
+        }).unwrap().valid);
+    }
+
+    #[test]
+    fn test_balance_chains_proof_verifies() {
+        let request = |balance_chains: bool| -> ProveRequest {
+            let mut request: ProveRequest = serde_json::from_str(r#"{
+                "circuit": ["s <== A + B + C + D", "s * 2 + A + B > 10"],
+                "signals": {
+                    "A": { "value": "1" },
+                    "B": { "value": "2" },
+                    "C": { "value": "3" },
+                    "D": { "value": "4" },
+                    "out": { "value": "?", "public": true }
+                }
+            }"#).unwrap();
+            request.balance_chains = balance_chains;
+            request
+        };
+
+        let plain = prove(request(false)).unwrap();
+        let balanced = prove(request(true)).unwrap();
+        assert_eq!(balanced.public_signals["out"].value, plain.public_signals["out"].value);
+
+        // The verify context carries the balanced statements
+        let context = decode_verify_context(&balanced.verify_context).unwrap();
+        assert_eq!(context.circuit[0], "s <== A + B + (C + D)");
+        assert_ne!(balanced.circuit_hash, plain.circuit_hash);
+
+        assert!(verify(VerifyRequest {
+            version: balanced.version,
+            proof: balanced.proof,
+            verify_context: balanced.verify_context,
+            public_signals: balanced.public_signals,
+            expected_circuit_hash: None,
+        }).unwrap().valid);
+    }
+
+    #[test]
+    fn test_balance_chains_with_inline_hash() {
+        let mut request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["crc32_int(A) + B + C + D > 10"],
+            "signals": {
+                "A": { "value": "1" },
+                "B": { "value": "2" },
+                "C": { "value": "3" },
+                "D": { "value": "4" },
+                "out": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+        request.balance_chains = true;
+        let response = prove(request).unwrap();
+
+        let context = decode_verify_context(&response.verify_context).unwrap();
+        assert_eq!(context.preprocess, vec!["__crc32_int_0<==crc32_int(A)".to_string()]);
+        assert!(context.circuit[0].contains("__crc32_int_0 + B + (C + D)"), "{}", context.circuit[0]);
+
+        assert!(verify(VerifyRequest {
+            version: response.version,
+            proof: response.proof,
+            verify_context: response.verify_context,
+            public_signals: response.public_signals,
+            expected_circuit_hash: None,
+        }).unwrap().valid);
+    }
+
+    #[test]
+    fn test_prove_rejects_circuit_over_max_k() {
+        let request_with = |max_k: u32| -> ProveRequest {
+            serde_json::from_str(&format!(r#"{{
+                "circuit": ["A > B"],
+                "signals": {{
+                    "A": {{ "value": "300" }},
+                    "B": {{ "value": "20" }},
+                    "out": {{ "value": "?", "public": true }}
+                }},
+                "max_k": {}
+            }}"#, max_k)).unwrap()
+        };
+
+        // 300 needs the 16-bit lookup table (k=17)
+        let err = prove(request_with(16)).unwrap_err();
+        assert_eq!(err, ZkplexError::CircuitTooLarge { k: 17, max_k: 16 });
+        assert_eq!(err.to_string(), "circuit requires k=17 which exceeds max_k=16");
+
+        // Within budget: an 8-bit circuit still proves
+        let mut request = request_with(16);
+        request.signals.get_mut("A").unwrap().value = Some("30".to_string());
+        let response = prove(request).unwrap();
+        assert_eq!(response.public_signals["out"].value, "1");
+    }
+
+    #[test]
+    fn test_forced_max_bits_shares_verify_context() {
+        let request_for = |a: &str, max_bits: Option<usize>| -> ProveRequest {
+            let mut request: ProveRequest = serde_json::from_str(&format!(r#"{{
+                "circuit": ["A > B"],
+                "signals": {{
+                    "A": {{ "value": "{}" }},
+                    "B": {{ "value": "20" }},
+                    "out": {{ "value": "?", "public": true }}
+                }},
+                "strategy": "bitd"
+            }}"#, a)).unwrap();
+            request.max_bits = max_bits;
+            request
+        };
+
+        // Sized from the witness, 30 and 300 need different range tables
+        let small = prove(request_for("30", None)).unwrap();
+        let large = prove(request_for("300", None)).unwrap();
+        assert_ne!(small.verify_context, large.verify_context);
+
+        // A forced size gives both witnesses the same circuit
+        let small = prove(request_for("30", Some(16))).unwrap();
+        let large = prove(request_for("300", Some(16))).unwrap();
+        assert_eq!(small.verify_context, large.verify_context);
+        assert_eq!(decode_verify_context(&small.verify_context).unwrap().cached_max_bits, Some(16));
+
+        let verify_request = VerifyRequest {
+            version: small.version,
+            proof: small.proof,
+            verify_context: large.verify_context,
+            public_signals: small.public_signals,
+            expected_circuit_hash: None,
+        };
+        assert!(verify(verify_request).unwrap().valid);
+
+        let err = prove(request_for("300", Some(8))).unwrap_err();
+        assert_eq!(err.to_string(), "comparison operands need 16-bit range checks, which exceeds max_bits=8");
+        let err = prove(request_for("30", Some(12))).unwrap_err();
+        assert_eq!(err.to_string(), "max_bits must be one of 8, 16, 32 or 64, got 12");
+    }
+
+    #[test]
+    fn test_prove_and_verify_with_each_transcript() {
+        let request_with = |transcript: Option<&str>| -> ProveRequest {
+            let mut request: ProveRequest = serde_json::from_str(r#"{
+                "circuit": ["A + B > 25"],
+                "signals": {
+                    "A": { "value": "10" },
+                    "B": { "value": "20" },
+                    "out": { "value": "?", "public": true }
+                }
+            }"#).unwrap();
+            request.transcript = transcript.map(str::to_string);
+            request
+        };
+        // Verify the proof of `response`, claiming it was made with `transcript`
+        let verify_as = |response: &ProveResponse, transcript: Option<&str>| {
+            let mut context = decode_verify_context(&response.verify_context).unwrap();
+            context.transcript = transcript.map(str::to_string);
+            verify(VerifyRequest {
+                version: response.version,
+                proof: response.proof.clone(),
+                verify_context: ascii85::encode(serde_json::to_string(&context).unwrap().as_bytes()),
+                public_signals: response.public_signals.clone(),
+                expected_circuit_hash: None,
+            }).unwrap().valid
+        };
+
+        let blake2b = prove(request_with(None)).unwrap();
+        assert_eq!(decode_verify_context(&blake2b.verify_context).unwrap().transcript, None);
+        assert!(verify_as(&blake2b, None));
+        assert!(verify_as(&blake2b, Some("blake2b")));
+        assert!(!verify_as(&blake2b, Some("keccak256")));
+
+        let keccak = prove(request_with(Some("keccak256"))).unwrap();
+        assert_eq!(decode_verify_context(&keccak.verify_context).unwrap().transcript.as_deref(), Some("keccak256"));
+        assert!(verify_as(&keccak, Some("keccak256")));
+        assert!(!verify_as(&keccak, None));
+
+        let err = prove(request_with(Some("poseidon"))).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid transcript 'poseidon'. Valid transcripts: blake2b, keccak256");
+    }
+
+    #[test]
+    fn test_intermediate_used_in_comparison_and_exposed() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["score<==A+B+C", "public_score<==score", "score > 100"],
+            "signals": {
+                "A": { "value": "40" },
+                "B": { "value": "50" },
+                "C": { "value": "40" },
+                "score": { "value": "?", "public": true },
+                "public_score": { "value": "?", "public": true },
+                "result": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let response = prove(request).unwrap();
+        assert_eq!(response.public_signals["score"].value, "130");
+        assert_eq!(response.public_signals["public_score"].value, "130");
+        assert_eq!(response.public_signals["result"].value, "1");
+
+        let verify_request = |score: &str| {
+            let mut public_signals = response.public_signals.clone();
+            public_signals.get_mut("public_score").unwrap().value = score.to_string();
+            VerifyRequest {
+                version: response.version,
+                proof: response.proof.clone(),
+                verify_context: response.verify_context.clone(),
+                public_signals,
+                expected_circuit_hash: None,
+            }
+        };
+
+        // The exposed value is bound to the same cell the comparison used
+        assert!(verify(verify_request("130")).unwrap().valid);
+        assert!(!verify(verify_request("131")).unwrap().valid);
+    }
+
+    #[test]
+    fn test_prove_with_saved_params() {
+        let program = crate::api::Program::from_zircon("1/A:10,B:20/out:?/-/A+B>25").unwrap();
+        let request = || crate::api::program_to_prove_request(&program, program.strategy);
+        let k = estimate(request()).unwrap().k;
+
+        let path = std::env::temp_dir().join(format!("zkplex-params-{}-k{}.bin", std::process::id(), k));
+        std::fs::write(&path, crate::api::generate_params(k)).unwrap();
+        let params = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let response = prove_with_params(request(), &params).unwrap();
+        assert!(verify(VerifyRequest {
+            version: response.version,
+            proof: response.proof,
+            verify_context: response.verify_context,
+            public_signals: response.public_signals,
+            expected_circuit_hash: None,
+        }).unwrap().valid);
+
+        let err = prove_with_params(request(), &crate::api::generate_params(k + 1)).unwrap_err();
+        assert!(err.to_string().contains(&format!("circuit requires k={}", k)), "{}", err);
+    }
+
+    #[test]
+    fn test_comparison_bounds_constant_vs_public() {
+        let bounds = |zircon: &str| {
+            let program = crate::api::Program::from_zircon(zircon).unwrap();
+            let response = prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap();
+            response.debug.unwrap().comparison_bounds
+        };
+        let bound = |comparison: &str, bound: &str, kind| ComparisonBound {
+            comparison: comparison.to_string(),
+            bound: bound.to_string(),
+            kind,
+        };
+
+        assert_eq!(bounds("1/age:30/out:?/-/age >= 18"), [bound("age >= 18", "18", BoundKind::Constant)]);
+        assert_eq!(bounds("1/age:30/threshold:18,out:?/-/age >= threshold"), [bound("age >= threshold", "threshold", BoundKind::Public)]);
+        assert!(bounds("1/age:30,limit:18/out:?/-/age >= limit").is_empty());
+
+        // A new public threshold keeps the verifying key, a new constant does not
+        let vk = |zircon: &str| {
+            let program = crate::api::Program::from_zircon(zircon).unwrap();
+            let circuit = Circuit::from_program(&program).unwrap();
+            let k = estimate_circuit_requirements_with_strategy(&circuit, Some(program.strategy)).k;
+            let vk = generate_vk_for_strategy(&circuit, program.strategy, &Params::new(k)).unwrap();
+            format!("{:?}", vk.pinned())
+        };
+        assert_eq!(
+            vk("1/age:30/threshold:18,out:?/-/age >= threshold"),
+            vk("1/age:30/threshold:21,out:?/-/age >= threshold"),
+        );
+        assert_ne!(vk("1/age:30/out:?/-/age >= 18"), vk("1/age:30/out:?/-/age >= 21"));
+    }
+
+    #[test]
+    fn test_prove_error_variants() {
+        let prove_zircon = |zircon: &str| {
+            let program = crate::api::Program::from_zircon(zircon).unwrap();
+            prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap_err()
+        };
+
+        assert!(matches!(prove_zircon("1/A:10/out:?/-/A +* 2"), ZkplexError::Parse(_)));
+        assert!(matches!(prove_zircon("1/A:xyz:hex/out:?/-/A > 2"), ZkplexError::Encoding(_)));
+        assert!(matches!(prove_zircon("1/flag:2/out:?/-/is_bool(flag)"), ZkplexError::Synthesis(_)));
+
+        let program = crate::api::Program::from_zircon("1/A:10/out:?/-/A > 2").unwrap();
+        let err = prove(crate::api::program_to_prove_request(&program, crate::circuit::Strategy::Boolean)).unwrap_err();
+        assert!(matches!(err, ZkplexError::UnsupportedOperation(_)), "{:?}", err);
+        assert!(err.to_string().starts_with("Strategy 'boolean' does not support"), "{}", err);
+    }
+
+    #[test]
+    fn test_is_bool_rejects_non_boolean_witness() {
+        let prove_flag = |flag: u64| {
+            let program = crate::api::Program::from_zircon(&format!("1/flag:{}/out:?/-/is_bool(flag)", flag)).unwrap();
+            prove(crate::api::program_to_prove_request(&program, program.strategy))
+        };
+
+        assert!(prove_flag(2).is_err());
+        let response = prove_flag(1).unwrap();
+        assert!(verify(VerifyRequest {
+            version: response.version,
+            proof: response.proof,
+            verify_context: response.verify_context,
+            public_signals: response.public_signals,
+            expected_circuit_hash: None,
+        }).unwrap().valid);
+    }
+
+    #[test]
+    fn test_public_copy_of_secret_warns() {
+        let leaks = |circuit: &str| {
+            let program = crate::api::Program::from_zircon(&format!("1/secret:42/result:?,out:?/-/{}", circuit)).unwrap();
+            let response = prove(crate::api::program_to_prove_request(&program, program.strategy)).unwrap();
+            response.debug.unwrap().warnings.unwrap_or_default().into_iter()
+                .filter(|w| w.contains("reveals the secret"))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            leaks("result<==secret;secret > 10"),
+            ["Public signal 'result' equals secret signal 'secret', so the proof reveals the secret value."]
+        );
+        assert_eq!(leaks("t<==secret;pub shown<==t;result<==t+1;t > 1").len(), 1);
+        assert_eq!(leaks("result<==secret+1;secret").len(), 1, "circuit output copies the secret");
+        assert!(leaks("result<==secret*2;secret > 10").is_empty());
+    }
+
+    #[test]
+    fn test_pub_assignment_is_public_output() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["pub total<==A+B", "total > 25"],
+            "signals": {
+                "A": { "value": "10" },
+                "B": { "value": "20" },
+                "result": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let response = prove(request).unwrap();
+        assert_eq!(response.public_signals["total"].value, "30");
+        assert_eq!(response.public_signals["result"].value, "1");
+        assert_eq!(response.public_signals.keys().collect::<Vec<_>>(), ["result", "total"]);
+
+        let verify_request = |total: &str| {
+            let mut public_signals = response.public_signals.clone();
+            public_signals.get_mut("total").unwrap().value = total.to_string();
+            VerifyRequest {
+                version: response.version,
+                proof: response.proof.clone(),
+                verify_context: response.verify_context.clone(),
+                public_signals,
+                expected_circuit_hash: None,
+            }
+        };
+
+        assert!(verify(verify_request("30")).unwrap().valid);
+        assert!(!verify(verify_request("31")).unwrap().valid);
+    }
+
+    #[test]
+    fn test_secret_array_sum_verifies() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["sum(votes) >= 3"],
+            "signals": {
+                "votes": { "value": "[1,0,1,1]", "bits": 1 },
+                "result": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let response = prove(request).unwrap();
+        assert_eq!(response.public_signals["result"].value, "1");
+
+        // The verifier expands sum(votes) from the array length in the verify context
+        let context = decode_verify_context(&response.verify_context).unwrap();
+        assert_eq!(context.array_lengths.get("votes"), Some(&4));
+        assert_eq!(context.signal_bits.get("votes_3"), Some(&1));
+
+        let verify_request = VerifyRequest {
+            version: response.version,
+            proof: response.proof,
+            verify_context: response.verify_context,
+            public_signals: response.public_signals,
+            expected_circuit_hash: None,
+        };
+        assert!(verify(verify_request).unwrap().valid);
+    }
+
+    #[test]
+    fn test_preprocessed_hashes_compared_in_circuit() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "preprocess": ["h1<==sha256(A)", "h2<==sha256(B)"],
+            "circuit": ["h1 == h2"],
+            "signals": {
+                "A": { "value": "42" },
+                "B": { "value": "42" },
+                "result": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+
+        let response = prove(request).unwrap();
+        assert_eq!(response.public_signals["result"].value, "1");
+
+        // The verifier rebuilds the limb-wise equality of the two digests
+        let context = decode_verify_context(&response.verify_context).unwrap();
+        assert_eq!(context.wide_signals.get("h1"), Some(&2));
+        assert_eq!(context.wide_signals.get("h2"), Some(&2));
+
+        let verify_request = VerifyRequest {
+            version: response.version,
+            proof: response.proof,
+            verify_context: response.verify_context,
+            public_signals: response.public_signals,
+            expected_circuit_hash: None,
+        };
+        assert!(verify(verify_request).unwrap().valid);
+    }
+
+    #[test]
+    fn test_metadata_is_bound_to_proof() {
+        let mut request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["A + B > 25"],
+            "signals": {
+                "A": { "value": "10" },
+                "B": { "value": "20" },
+                "out": { "value": "?", "public": true }
+            }
+        }"#).unwrap();
+        request.metadata = Some("request-42".to_string());
+        let response = prove(request).unwrap();
+
+        // Verify the proof of `response` with the metadata in its context replaced
+        let verify_with = |metadata: Option<&str>| {
+            let mut context = decode_verify_context(&response.verify_context).unwrap();
+            context.metadata = metadata.map(str::to_string);
+            verify(VerifyRequest {
+                version: response.version,
+                proof: response.proof.clone(),
+                verify_context: ascii85::encode(serde_json::to_string(&context).unwrap().as_bytes()),
+                public_signals: response.public_signals.clone(),
+                expected_circuit_hash: Some(response.circuit_hash.clone()),
+            }).unwrap()
+        };
+
+        let verified = verify_with(Some("request-42"));
+        assert!(verified.valid);
+        assert_eq!(verified.metadata.as_deref(), Some("request-42"));
+
+        // The circuit hash still matches, but the transcript does not
+        let altered = verify_with(Some("request-43"));
+        assert!(!altered.valid);
+        assert_eq!(altered.metadata, None);
+        assert!(!verify_with(None).valid);
+    }
+
+    #[test]
+    fn test_evaluate_without_proof() {
+        let program = crate::api::Program::from_zircon("1/A:2,B:3,C:7/result:/-/total<==A+B;(A+B)*C").unwrap();
+        let response = evaluate(&program).unwrap();
+        assert_eq!(response.output.as_deref(), Some("35"));
+        assert!(response.encoded_output.is_none());
+        assert_eq!(response.signals.get("total").map(String::as_str), Some("5"));
+
+        let program = crate::api::Program::from_zircon("1/A:200,B:55/result::hex/-/A+B").unwrap();
+        let response = evaluate(&program).unwrap();
+        assert_eq!(response.output.as_deref(), Some("255"));
+        let encoded = response.encoded_output.unwrap();
+        assert_eq!(encoded.value, "0xff");
+        assert_eq!(encoded.encoding, Some(ValueEncoding::Hex));
+    }
+}
@@ -7,17 +7,30 @@ pub mod program;
 pub mod core;
 pub mod prove_helpers;
 pub mod layout;
+pub mod schema;
+pub mod transcript;
+pub mod params;
 
 // Re-export types from types module (for JSON API)
 pub use types::{
     Signal, ProveRequest, ProveResponse,
-    VerifyRequest, VerifyResponse, ErrorResponse,
-    EstimateResponse, DebugInfo, PublicSignal, VerifyContext,
+    VerifyRequest, VerifyResponse, CheckResponse, EvaluateResponse, ErrorResponse,
+    EstimateResponse, OutputFormat, DebugInfo, ComparisonBound, BoundKind, PublicSignal, VerifyContext, VerifyErrorKind, Timings,
     PROOF_VERSION, // Re-export proof version constant
+    DEFAULT_MAX_K,
 };
 
 // Re-export Program type (Signal within program is kept internal)
-pub use program::Program;
+pub use program::{Program, SignalChange, SignalChangeKind, PROGRAM_VERSION};
+
+// Re-export JSON schema export
+pub use schema::json_schema;
+
+// Re-export transcript selection
+pub use transcript::TranscriptHash;
+
+// Re-export params (de)serialization
+pub use params::{generate_params, params_from_bytes, params_k, params_to_bytes};
 
 // Re-export prove helpers
 pub use prove_helpers::{apply_signal_overrides, program_to_prove_request};
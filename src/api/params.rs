@@ -0,0 +1,72 @@
+//! Commitment params (de)serialization
+//!
+//! Proving needs the IPA commitment params for the circuit size `k`. They need
+//! no trusted setup, and `Params::new(k)` always derives the same ones, but
+//! generating them takes time that grows with 2^k. Params saved once with
+//! [`params_to_bytes`] can be loaded by `prove_with_params()` instead.
+//!
+//! The format is Halo2's: `k` as 4 little-endian bytes, then the compressed
+//! `g` and `g_lagrange` generators (2^k each), `w` and `u`.
+
+use halo2_proofs::pasta::EqAffine;
+use halo2_proofs::poly::commitment::Params;
+
+/// Bytes of one compressed curve point
+const POINT_SIZE: u64 = 32;
+
+/// Largest `k` a params file may declare (matches the Pasta two-adicity)
+const MAX_PARAMS_K: u32 = 32;
+
+/// Generate the params for circuit size `k`, serialized
+pub fn generate_params(k: u32) -> Vec<u8> {
+    params_to_bytes(&Params::new(k))
+}
+
+/// Serialize params
+pub fn params_to_bytes(params: &Params<EqAffine>) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    params.write(&mut bytes).expect("writing to a Vec cannot fail");
+    bytes
+}
+
+/// Read the circuit size `k` from the header of serialized params
+pub fn params_k(bytes: &[u8]) -> Result<u32, String> {
+    let header: [u8; 4] = bytes.get(..4)
+        .and_then(|header| header.try_into().ok())
+        .ok_or_else(|| "Params are truncated: missing the k header".to_string())?;
+    Ok(u32::from_le_bytes(header))
+}
+
+/// Deserialize params
+///
+/// The length is checked against the `k` header before anything is read, so a
+/// corrupt header cannot make this allocate 2^k points.
+pub fn params_from_bytes(bytes: &[u8]) -> Result<Params<EqAffine>, String> {
+    let k = params_k(bytes)?;
+    if k > MAX_PARAMS_K {
+        return Err(format!("Params declare k={}, above the maximum of {}", k, MAX_PARAMS_K));
+    }
+
+    let expected = 4 + (2 * (1u64 << k) + 2) * POINT_SIZE;
+    if bytes.len() as u64 != expected {
+        return Err(format!("Params for k={} must be {} bytes, got {}", k, expected, bytes.len()));
+    }
+
+    Params::read(&mut &bytes[..]).map_err(|e| format!("Invalid params: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_roundtrip() {
+        let bytes = generate_params(4);
+        assert_eq!(params_k(&bytes).unwrap(), 4);
+        assert_eq!(params_to_bytes(&params_from_bytes(&bytes).unwrap()), bytes);
+
+        assert!(params_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(params_from_bytes(&[40, 0, 0, 0]).is_err());
+        assert!(params_k(&[4, 0]).is_err());
+    }
+}
@@ -20,6 +20,10 @@
 //! - **preprocess**: `statement[;statement]*` or `-` if empty (hash/encoding operations)
 //! - **circuit**: `statement[;statement]*` where last statement is the output
 //!
+//! A `#` in a preprocess or circuit statement starts a comment that runs to
+//! the end of the statement (the next `;` or `/`): `age>=18 # adult check`.
+//! Signal values are not affected, so `#` can appear in encoded values.
+//!
 //! # Examples
 //!
 //! ```text
@@ -62,11 +66,21 @@
 //! - NOT / !
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use indexmap::IndexMap;
 use crate::encoding::ValueEncoding;
+use crate::circuit::Strategy;
+use std::collections::BTreeSet;
+
+/// Current Program format version, the one [`Program::migrate`] upgrades to
+///
+/// - 1: original format; signals without an encoding are auto-detected
+/// - 2: every input signal value carries an explicit encoding
+pub const PROGRAM_VERSION: u32 = 2;
 
 /// Signal with value and optional encoding
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[schemars(rename = "ProgramSignal")]
 pub struct Signal {
     /// Signal value (None for output signals that will be computed)
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -75,6 +89,15 @@ pub struct Signal {
     /// Optional encoding (hex, base58, base64)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub encoding: Option<ValueEncoding>,
+
+    /// Optional declared bit width, range-checked in the circuit (JSON only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1, max = 64))]
+    pub bits: Option<usize>,
+
+    /// Fold the value into the circuit as a constant instead of an input (JSON only)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub constant: bool,
 }
 
 impl Signal {
@@ -83,6 +106,8 @@ impl Signal {
         Self {
             value: Some(value.into()),
             encoding: None,
+            bits: None,
+            constant: false,
         }
     }
 
@@ -91,6 +116,8 @@ impl Signal {
         Self {
             value: Some(value.into()),
             encoding: Some(encoding),
+            bits: None,
+            constant: false,
         }
     }
 
@@ -99,10 +126,43 @@ impl Signal {
         Self {
             value: None,
             encoding: None,
+            bits: None,
+            constant: false,
         }
     }
 }
 
+/// Kind of change reported by [`Program::diff`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalChangeKind {
+    /// Signal exists only in the new program
+    Added,
+    /// Signal exists only in the old program
+    Removed,
+    /// Signal exists in both programs with a different value or encoding
+    Changed,
+}
+
+/// A single signal difference between two programs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SignalChange {
+    /// Signal name
+    pub name: String,
+
+    /// Whether the signal is in the public section (secret otherwise)
+    pub public: bool,
+
+    /// Kind of change
+    pub kind: SignalChangeKind,
+
+    /// Signal in the old program (None if added)
+    pub old: Option<Signal>,
+
+    /// Signal in the new program (None if removed)
+    pub new: Option<Signal>,
+}
+
 /// ZKPlex Program
 ///
 /// Represents a ZKP circuit with inputs and computation logic.
@@ -114,7 +174,8 @@ impl Signal {
 /// - `public`: Public input signals
 /// - `preprocess`: Preprocessing operations (hashes, encodings, etc.)
 /// - `circuit`: Circuit statements (last one is output)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// - `strategy`: Intended proof strategy (defaults to auto)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Program {
     /// Program version
     pub version: u32,
@@ -133,9 +194,11 @@ pub struct Program {
     /// Format: `name<==operation(args)`
     ///
     /// Supported operations:
-    /// - Hash functions: `sha1()`, `sha256()`, `sha512()`, `md5()`, `crc32()`, `blake2b()`, `keccak256()`, `keccak()`
+    /// - Hash functions: `sha1()`, `sha256()`, `sha512()`, `md5()`, `crc32()`, `crc32_int()`, `blake2b()`, `blake3()`, `keccak256()`, `keccak()`
+    /// - Ethereum: `eth_address()` - last 20 bytes of `keccak256()`
     /// - Encoding functions: `hex_encode()`, `base64()`, `base58()`, `base64_encode()`, `base58_encode()`
     /// - Utility: `concat()` - concatenates arguments (alternative to `|`)
+    /// - Byte operations: `reverse()`, `xor_bytes()`, `pad_left()`, `pad_right()`
     ///
     /// Format specifiers (printf-style):
     /// - `{%x}` / `{%X}` - hex lowercase/uppercase
@@ -162,6 +225,10 @@ pub struct Program {
 
     /// Circuit statements (last one is output)
     pub circuit: Vec<String>,
+
+    /// Intended proof strategy (omitted when auto)
+    #[serde(default, skip_serializing_if = "Strategy::is_auto")]
+    pub strategy: Strategy,
 }
 
 impl Program {
@@ -173,10 +240,14 @@ impl Program {
             public: IndexMap::new(),
             preprocess: Vec::new(),
             circuit: Vec::new(),
+            strategy: Strategy::Auto,
         }
     }
 
-    /// Parse from zircon format: `version/secret/public/preprocess/circuit` or `version/secret/public/circuit`
+    /// Parse from zircon format: `version/secret/public/preprocess/circuit[/strategy]`
+    /// or the basic `version/secret/public/circuit`
+    ///
+    /// The optional 6th segment carries the proof strategy (`auto`, `boolean`, `lookup`, `bitd`).
     ///
     /// # Examples
     ///
@@ -186,23 +257,39 @@ impl Program {
     ///
     /// // With preprocessing
     /// let p = Program::from_zircon("1/A:10/-/h<==sha256(A{%x})/h>100")?;
+    ///
+    /// // With strategy
+    /// let p = Program::from_zircon("1/A:10/-/-/A>5/bitd")?;
     /// ```
     pub fn from_zircon(input: &str) -> Result<Self, String> {
         let parts: Vec<&str> = input.split('/').collect();
 
-        let (version, secret, public, preprocess, circuit) = match parts.len() {
-            5 => {
+        let (version, secret, public, preprocess, circuit, strategy) = match parts.len() {
+            4 => {
+                // Basic format without the preprocess segment
+                let version = parts[0].parse::<u32>()
+                    .map_err(|_| format!("Invalid version: {}", parts[0]))?;
+                let secret = Self::parse_signals(parts[1])?;
+                let public = Self::parse_signals(parts[2])?;
+                let circuit = Self::parse_statements(parts[3])?;
+                (version, secret, public, Vec::new(), circuit, Strategy::Auto)
+            }
+            5 | 6 => {
                 let version = parts[0].parse::<u32>()
                     .map_err(|_| format!("Invalid version: {}", parts[0]))?;
                 let secret = Self::parse_signals(parts[1])?;
                 let public = Self::parse_signals(parts[2])?;
                 let preprocess = Self::parse_statements(parts[3])?;
                 let circuit = Self::parse_statements(parts[4])?;
-                (version, secret, public, preprocess, circuit)
+                let strategy = match parts.get(5) {
+                    Some(s) => s.trim().parse::<Strategy>()?,
+                    None => Strategy::Auto,
+                };
+                (version, secret, public, preprocess, circuit, strategy)
             }
             _ => {
                 return Err(format!(
-                    "Invalid format: expected 'version/secret/public/preprocess/circuit', got {} parts",
+                    "Invalid format: expected 'version/secret/public/[preprocess/]circuit[/strategy]', got {} parts",
                     parts.len()
                 ));
             }
@@ -218,10 +305,144 @@ impl Program {
             public,
             preprocess,
             circuit,
+            strategy,
         })
     }
 
+    /// Report signals that differ between this program and `other`
+    ///
+    /// Secret and public sections are compared separately, so a signal that moved
+    /// from secret to public is reported as removed from one and added to the other.
+    /// Unchanged signals are not reported.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let template = Program::from_zircon("1/age:?/-/-/age>=18")?;
+    /// let mut filled = template.clone();
+    /// apply_signal_overrides(&mut filled, &overrides)?;
+    /// for change in template.diff(&filled) {
+    ///     println!("{}: {:?} -> {:?}", change.name, change.old, change.new);
+    /// }
+    /// ```
+    pub fn diff(&self, other: &Program) -> Vec<SignalChange> {
+        let mut changes = Self::diff_signals(&self.secret, &other.secret, false);
+        changes.extend(Self::diff_signals(&self.public, &other.public, true));
+        changes
+    }
+
+    /// Combine a base program with an overlay
+    ///
+    /// Secret and public signals are unioned; a signal present in both programs must
+    /// have the same value and encoding, and stay in the same section. The overlay's
+    /// preprocess statements run after the base's, and its circuit statements are
+    /// appended, so the overlay's last statement becomes the output. The overlay
+    /// strategy wins unless it is auto.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let base = Program::from_zircon("1/secret:?,salt:42/-/h<==sha256(salt|secret)/h>0")?;
+    /// let user = Program::from_zircon("1/-/threshold:100/-/h>threshold")?;
+    /// let merged = Program::merge(&base, &user)?;
+    /// ```
+    pub fn merge(base: &Program, overlay: &Program) -> Result<Program, String> {
+        if base.version != overlay.version {
+            return Err(format!(
+                "Cannot merge programs with different versions: {} and {}",
+                base.version, overlay.version
+            ));
+        }
+
+        let mut merged = base.clone();
+        Self::merge_signals(&mut merged.secret, &base.public, &overlay.secret, "secret")?;
+        Self::merge_signals(&mut merged.public, &base.secret, &overlay.public, "public")?;
+        merged.preprocess.extend(overlay.preprocess.iter().cloned());
+        merged.circuit.extend(overlay.circuit.iter().cloned());
+        if !overlay.strategy.is_auto() {
+            merged.strategy = overlay.strategy;
+        }
+
+        Ok(merged)
+    }
+
+    /// Merge one overlay signal section into `target`, rejecting conflicts
+    fn merge_signals(
+        target: &mut IndexMap<String, Signal>,
+        other_section: &IndexMap<String, Signal>,
+        overlay: &IndexMap<String, Signal>,
+        section: &str,
+    ) -> Result<(), String> {
+        for (name, signal) in overlay {
+            if other_section.contains_key(name) {
+                return Err(format!(
+                    "Signal '{}' is {} in the overlay but in the other section of the base program",
+                    name, section
+                ));
+            }
+            match target.get(name) {
+                Some(existing) if existing != signal => {
+                    return Err(format!(
+                        "Conflicting values for {} signal '{}': {:?} and {:?}",
+                        section, name, existing.value, signal.value
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    target.insert(name.clone(), signal.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Diff a single signal section
+    fn diff_signals(
+        old: &IndexMap<String, Signal>,
+        new: &IndexMap<String, Signal>,
+        public: bool,
+    ) -> Vec<SignalChange> {
+        let mut changes = Vec::new();
+
+        for (name, old_signal) in old {
+            match new.get(name) {
+                Some(new_signal) if new_signal == old_signal => {}
+                Some(new_signal) => changes.push(SignalChange {
+                    name: name.clone(),
+                    public,
+                    kind: SignalChangeKind::Changed,
+                    old: Some(old_signal.clone()),
+                    new: Some(new_signal.clone()),
+                }),
+                None => changes.push(SignalChange {
+                    name: name.clone(),
+                    public,
+                    kind: SignalChangeKind::Removed,
+                    old: Some(old_signal.clone()),
+                    new: None,
+                }),
+            }
+        }
+
+        for (name, new_signal) in new {
+            if !old.contains_key(name) {
+                changes.push(SignalChange {
+                    name: name.clone(),
+                    public,
+                    kind: SignalChangeKind::Added,
+                    old: None,
+                    new: Some(new_signal.clone()),
+                });
+            }
+        }
+
+        changes
+    }
+
     /// Parse statements from semicolon-separated string
+    ///
+    /// `#` comments are stripped from each statement; a statement that is only
+    /// a comment is dropped.
     pub fn parse_statements(input: &str) -> Result<Vec<String>, String> {
         if input.trim() == "-" || input.is_empty() {
             return Ok(Vec::new());
@@ -229,7 +450,7 @@ impl Program {
 
         Ok(input
             .split(';')
-            .map(|s| s.trim().to_string())
+            .map(|s| strip_comment(s).trim().to_string())
             .filter(|s| !s.is_empty())
             .collect())
     }
@@ -260,10 +481,41 @@ impl Program {
 
         let circuit_str = self.circuit.join(";");
         let preprocess_str = self.preprocess.join(";");
-        format!("{}/{}/{}/{}/{}", self.version, secret_str, public_str, preprocess_str, circuit_str)
+        let zircon = format!("{}/{}/{}/{}/{}", self.version, secret_str, public_str, preprocess_str, circuit_str);
+
+        // Strategy segment is only emitted when it differs from the default
+        if self.strategy.is_auto() {
+            zircon
+        } else {
+            format!("{}/{}", zircon, self.strategy)
+        }
+    }
+
+    /// Split a signal list on commas, keeping array values `[a,b,c]` whole
+    fn split_signal_list(input: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in input.char_indices() {
+            match c {
+                '[' => depth += 1,
+                ']' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    parts.push(&input[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(&input[start..]);
+        parts
     }
 
     /// Parse signals from format: `name:value[:encoding][,...]` or `-`
+    ///
+    /// An empty value marks an output signal, optionally with the encoding its
+    /// computed value should be rendered in: `result:` or `result::hex`.
+    /// Values are percent-unescaped (see [`escape_value`]).
     fn parse_signals(input: &str) -> Result<IndexMap<String, Signal>, String> {
         if input.trim() == "-" || input.is_empty() {
             return Ok(IndexMap::new());
@@ -271,42 +523,34 @@ impl Program {
 
         let mut signals = IndexMap::new();
 
-        for part in input.split(',') {
+        for part in Self::split_signal_list(input) {
             let components: Vec<&str> = part.trim().split(':').collect();
 
             match components.len() {
                 2 => {
                     // name:value
                     let name = components[0].trim().to_string();
-                    let value = components[1].trim().to_string();
+                    let value = unescape_value(components[1].trim());
 
                     if name.is_empty() {
                         return Err("Signal name cannot be empty".to_string());
                     }
 
-                    signals.insert(name, Signal::new(value));
+                    signals.insert(name, Self::signal_from_parts(value, None));
                 }
                 3 => {
                     // name:value:encoding
                     let name = components[0].trim().to_string();
-                    let value = components[1].trim().to_string();
+                    let value = unescape_value(components[1].trim());
                     let encoding_str = components[2].trim();
 
                     if name.is_empty() {
                         return Err("Signal name cannot be empty".to_string());
                     }
 
-                    let encoding = match encoding_str {
-                        "hex" => ValueEncoding::Hex,
-                        "base58" => ValueEncoding::Base58,
-                        "base64" => ValueEncoding::Base64,
-                        "base85" => ValueEncoding::Base85,
-                        "decimal" => ValueEncoding::Decimal,
-                        "text" => ValueEncoding::Text,
-                        _ => return Err(format!("Unknown encoding: {}", encoding_str)),
-                    };
-
-                    signals.insert(name, Signal::with_encoding(value, encoding));
+                    let encoding: ValueEncoding = encoding_str.parse()?;
+
+                    signals.insert(name, Self::signal_from_parts(value, Some(encoding)));
                 }
                 _ => {
                     return Err(format!("Invalid signal format '{}': expected 'name:value' or 'name:value:encoding'", part));
@@ -317,22 +561,24 @@ impl Program {
         Ok(signals)
     }
 
+    /// Build a signal from parsed components; an empty value becomes an output signal
+    fn signal_from_parts(value: String, encoding: Option<ValueEncoding>) -> Signal {
+        Signal {
+            value: if value.is_empty() { None } else { Some(value) },
+            encoding,
+            bits: None,
+            constant: false,
+        }
+    }
+
     /// Convert signals IndexMap to string format
     fn signals_to_string(signals: &IndexMap<String, Signal>) -> String {
         let mut items: Vec<String> = signals
             .iter()
             .map(|(name, signal)| {
-                let value_str = signal.value.as_deref().unwrap_or("");
+                let value_str = escape_value(signal.value.as_deref().unwrap_or(""));
                 if let Some(encoding) = &signal.encoding {
-                    let enc_str = match encoding {
-                        ValueEncoding::Hex => "hex",
-                        ValueEncoding::Base58 => "base58",
-                        ValueEncoding::Base64 => "base64",
-                        ValueEncoding::Base85 => "base85",
-                        ValueEncoding::Decimal => "decimal",
-                        ValueEncoding::Text => "text",
-                    };
-                    format!("{}:{}:{}", name, value_str, enc_str)
+                    format!("{}:{}:{}", name, value_str, encoding.as_str())
                 } else {
                     format!("{}:{}", name, value_str)
                 }
@@ -368,11 +614,66 @@ impl Program {
             .map_err(|e| format!("Failed to serialize to JSON: {}", e))
     }
 
+    /// Upgrade the program to [`PROGRAM_VERSION`], one version at a time
+    ///
+    /// Migrations keep the program's meaning: the migrated program builds the
+    /// same circuit from the same signal values. A program already at the
+    /// current version is returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// let program = Program::from_zircon("1/A:0x0a/-/-/A>5")?.migrate()?;
+    /// assert_eq!(program.version, PROGRAM_VERSION);
+    /// assert_eq!(program.secret["A"].encoding, Some(ValueEncoding::Hex));
+    /// ```
+    pub fn migrate(&self) -> Result<Program, String> {
+        if let Some(error) = Self::version_error(self.version) {
+            return Err(error);
+        }
+
+        let mut program = self.clone();
+        while program.version < PROGRAM_VERSION {
+            match program.version {
+                1 => program.migrate_v1_to_v2(),
+                version => return Err(format!("No migration from program version {}", version)),
+            }
+            program.version += 1;
+        }
+        Ok(program)
+    }
+
+    /// v1 → v2: record the encoding auto-detection picks for each input value
+    ///
+    /// Array values are left alone, as their elements are detected one by one.
+    fn migrate_v1_to_v2(&mut self) {
+        for signal in self.secret.values_mut().chain(self.public.values_mut()) {
+            if signal.encoding.is_some() {
+                continue;
+            }
+            if let Some(value) = signal.value.as_deref() {
+                if !value.is_empty() && value != "?" && crate::encoding::split_array_value(value).is_none() {
+                    signal.encoding = Some(crate::encoding::auto_encoding(value));
+                }
+            }
+        }
+    }
+
+    /// Error for a version this crate can neither read nor migrate
+    fn version_error(version: u32) -> Option<String> {
+        (!(1..=PROGRAM_VERSION).contains(&version)).then(|| format!(
+            "Unsupported program version {}: supported versions are 1 to {}",
+            version, PROGRAM_VERSION
+        ))
+    }
+
     /// Validate program
+    ///
+    /// Any version [`migrate`](Self::migrate) can upgrade is accepted.
     pub fn validate(&self) -> Result<(), String> {
         // Check version
-        if self.version != 1 {
-            return Err("Version must be >= 1".to_string());
+        if let Some(error) = Self::version_error(self.version) {
+            return Err(error);
         }
 
         // Check that we have at least one circuit statement
@@ -382,42 +683,166 @@ impl Program {
 
         // Validate signal values can be parsed
         for (name, signal) in self.secret.iter().chain(self.public.iter()) {
-            // Skip output signals (value is None)
-            let value_str = match &signal.value {
-                Some(v) => v,
-                None => continue, // Output signal, skip validation
-            };
+            if let Some(error) = Self::signal_error(name, signal) {
+                return Err(error);
+            }
+        }
 
-            // Skip validation for placeholder values
-            if value_str == "?" {
-                continue;
+        Ok(())
+    }
+
+    /// Collect every problem in the program instead of stopping at the first
+    ///
+    /// Runs the [`validate`](Self::validate) checks on each signal and also parses
+    /// every circuit statement and checks the syntax of every preprocess statement,
+    /// without evaluating anything. An empty result means the program is well-formed.
+    ///
+    /// # Examples
+    ///
+    /// ```ignore
+    /// for error in program.validation_errors() {
+    ///     eprintln!("{}", error);
+    /// }
+    /// ```
+    pub fn validation_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        errors.extend(Self::version_error(self.version));
+        if self.circuit.is_empty() {
+            errors.push("Circuit cannot be empty".to_string());
+        }
+
+        errors.extend(
+            self.secret.iter().chain(self.public.iter())
+                .filter_map(|(name, signal)| Self::signal_error(name, signal))
+        );
+        errors.extend(
+            self.secret.iter()
+                .filter(|(_, signal)| signal.constant)
+                .map(|(name, _)| format!("Secret signal '{}' cannot be constant: constant values are stored in the proof", name))
+        );
+
+        for (i, statement) in self.preprocess.iter().enumerate() {
+            if let Err(e) = crate::preprocess::check_statement(statement) {
+                errors.push(format!("Preprocess statement {} '{}': {}", i + 1, statement, e));
             }
+        }
 
-            // Signal values cannot be empty
-            if value_str.is_empty() {
-                return Err(format!(
-                    "Signal '{}' has empty value",
-                    name
-                ));
+        // Inline hash calls are moved to preprocessing before the circuit is parsed
+        let (_, circuit) = crate::preprocess::hoist_inline_hashes(&[], &self.circuit);
+        for (i, (statement, hoisted)) in self.circuit.iter().zip(&circuit).enumerate() {
+            let expression = match hoisted.find("<==") {
+                Some(pos) => &hoisted[pos + 3..],
+                None => hoisted.as_str(),
+            };
+            if let Err(e) = crate::parser::parse_circuit(expression) {
+                errors.push(format!("Circuit statement {} '{}': {}", i + 1, statement, e));
             }
+        }
 
-            use crate::encoding::{parse_value, parse_value_auto};
+        errors
+    }
 
-            let output = if let Some(encoding) = signal.encoding {
-                parse_value(value_str, encoding)
-            } else {
-                parse_value_auto(value_str)
+    /// Cross-reference declared signals against the signals the statements use
+    ///
+    /// Returns `Ok(warnings)` naming declared input signals no statement uses, or
+    /// `Err(errors)` naming signals that are referenced but neither declared nor
+    /// produced by a preprocess or circuit assignment. Output placeholders
+    /// (no value or `?`) are not inputs, and statements that fail to parse are left
+    /// to [`validation_errors`](Self::validation_errors).
+    pub fn check_signal_usage(&self) -> Result<Vec<String>, Vec<String>> {
+        let mut intermediates = BTreeSet::new();
+        let mut used = BTreeSet::new();
+
+        let (preprocess, circuit) = crate::preprocess::hoist_inline_hashes(&self.preprocess, &self.circuit);
+        for statement in &preprocess {
+            if let Ok((name, inputs)) = crate::preprocess::statement_signals(statement) {
+                used.extend(inputs);
+                intermediates.insert(name);
+            }
+        }
+        for statement in &circuit {
+            let statement = crate::circuit::split_public_assignment(statement).1;
+            let expression = match statement.find("<==") {
+                Some(pos) => {
+                    intermediates.insert(statement[..pos].trim().to_string());
+                    &statement[pos + 3..]
+                }
+                None => statement,
             };
+            if let Ok(expr) = crate::parser::parse_circuit(expression) {
+                used.extend(expr.variable_set());
+            }
+        }
 
-            if let Err(e) = output {
-                return Err(format!(
-                    "Signal '{}' has invalid value '{}': {}",
-                    name, value_str, e
-                ));
+        let is_input = |signal: &Signal| signal.value.as_ref().is_some_and(|v| v != "?");
+        let mut declared: BTreeSet<String> = self.secret.keys().chain(self.public.keys()).cloned().collect();
+
+        // Elements of an array signal can be used directly as `name_0`, `name_1`, ...
+        for (name, signal) in self.secret.iter().chain(self.public.iter()) {
+            let elements = signal.value.as_deref().and_then(crate::encoding::split_array_value);
+            if let Some(elements) = elements {
+                declared.extend((0..elements.len()).map(|i| crate::circuit::array_element_name(name, i)));
             }
         }
 
-        Ok(())
+        let errors: Vec<String> = used.iter()
+            .filter(|name| !declared.contains(*name) && !intermediates.contains(*name))
+            .map(|name| format!("Signal '{}' is used but not declared", name))
+            .collect();
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(self.secret.iter().chain(self.public.iter())
+            .filter(|(name, signal)| is_input(signal) && !used.contains(*name))
+            .map(|(name, _)| format!("Signal '{}' is declared but never used", name))
+            .collect())
+    }
+
+    /// Check that a signal value can be parsed, returning the error message if not
+    fn signal_error(name: &str, signal: &Signal) -> Option<String> {
+        // Skip output signals (value is None)
+        let value_str = signal.value.as_ref()?;
+
+        // Skip validation for placeholder values
+        if value_str == "?" {
+            return None;
+        }
+
+        // Signal values cannot be empty
+        if value_str.is_empty() {
+            return Some(format!(
+                "Signal '{}' has empty value",
+                name
+            ));
+        }
+
+        use crate::encoding::{parse_value, parse_value_auto, split_array_value};
+
+        let parse = |value: &str| if let Some(encoding) = signal.encoding {
+            parse_value(value, encoding)
+        } else {
+            parse_value_auto(value)
+        };
+
+        // Array values are checked element by element
+        if let Some(elements) = split_array_value(value_str) {
+            return elements.iter().enumerate().find_map(|(i, element)| {
+                if element.is_empty() {
+                    return Some(format!("Signal '{}' has empty array element {}", name, i));
+                }
+                parse(element).err().map(|e| format!(
+                    "Signal '{}' has invalid array element {} '{}': {}",
+                    name, i, element, e
+                ))
+            });
+        }
+
+        parse(value_str).err().map(|e| format!(
+            "Signal '{}' has invalid value '{}': {}",
+            name, value_str, e
+        ))
     }
 
     /// Get all input signal names (secret + public)
@@ -441,6 +866,63 @@ impl Program {
     }
 }
 
+/// Drop a `#` comment from a preprocess or circuit statement
+fn strip_comment(statement: &str) -> &str {
+    statement.split_once('#').map_or(statement, |(code, _)| code)
+}
+
+/// Characters that delimit signals in Zircon, with their percent escapes
+const ESCAPES: [(char, &str); 6] = [
+    ('%', "%25"),
+    ('/', "%2F"),
+    (',', "%2C"),
+    (':', "%3A"),
+    ('[', "%5B"),
+    (']', "%5D"),
+];
+
+/// Percent-encode the Zircon delimiters in a signal value
+///
+/// Values without delimiters are returned unchanged. Array values keep their
+/// brackets and commas so they still split into elements.
+fn escape_value(value: &str) -> String {
+    let is_array = crate::encoding::split_array_value(value).is_some();
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match ESCAPES.iter().find(|(delimiter, _)| *delimiter == c) {
+            Some((',' | '[' | ']', _)) if is_array => escaped.push(c),
+            Some((_, escape)) => escaped.push_str(escape),
+            None => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Decode the escapes written by [`escape_value`]
+///
+/// Only the escapes of Zircon delimiters are decoded (case-insensitively), so
+/// a `%` that does not start one, as in `50%`, is kept as is.
+fn unescape_value(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(pos) = rest.find('%') {
+        unescaped.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        match ESCAPES.iter().find(|(_, escape)| rest.get(..3).is_some_and(|s| s.eq_ignore_ascii_case(escape))) {
+            Some((delimiter, _)) => {
+                unescaped.push(*delimiter);
+                rest = &rest[3..];
+            }
+            None => {
+                unescaped.push('%');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -494,6 +976,15 @@ mod tests {
         assert_eq!(p.public.get("expected").unwrap().encoding, Some(ValueEncoding::Base58));
     }
 
+    #[test]
+    fn test_parse_base64url_encoding() {
+        let p = Program::from_zircon("1/sig:SflKxwRJSMeKKF2QT4fwpMeJf36POk6yJV_adQssw5c:b64url/-/-/sig>0").unwrap();
+        assert_eq!(p.secret["sig"].encoding, Some(ValueEncoding::Base64Url));
+
+        // Written back with the canonical name
+        assert!(p.to_zircon().starts_with("1/sig:SflKxwRJSMeKKF2QT4fwpMeJf36POk6yJV_adQssw5c:base64url/"));
+    }
+
     #[test]
     fn test_to_zircon() {
         let mut p = Program::new(1);
@@ -520,6 +1011,217 @@ mod tests {
         assert_eq!(p.secret.len(), p2.secret.len());
         assert_eq!(p.public.len(), p2.public.len());
         assert_eq!(p.circuit.len(), p2.circuit.len());
+        assert_eq!(p.strategy, p2.strategy);
+    }
+
+    #[test]
+    fn test_roundtrip_output_signal_with_encoding() {
+        let mut p = Program::new(1);
+        p.secret.insert("A".to_string(), Signal::new("10"));
+        p.public.insert("result".to_string(), Signal {
+            value: None,
+            encoding: Some(ValueEncoding::Base58),
+            bits: None,
+            constant: false,
+        });
+        p.circuit.push("A+1".to_string());
+
+        let zircon = p.to_zircon();
+        assert_eq!(zircon, "1/A:10/result::base58//A+1");
+
+        let p2 = Program::from_zircon(&zircon).unwrap();
+        assert_eq!(p2.public["result"], p.public["result"]);
+        assert_eq!(p2.to_zircon(), zircon);
+    }
+
+    #[test]
+    fn test_roundtrip_escaped_values() {
+        let mut p = Program::new(1);
+        p.secret.insert("note".to_string(), Signal::with_encoding("a:b/c,d", ValueEncoding::Text));
+        p.secret.insert("sig".to_string(), Signal::with_encoding("ab/cd+e=", ValueEncoding::Base64));
+        p.public.insert("rate".to_string(), Signal::with_encoding("50%", ValueEncoding::Text));
+        p.circuit.push("note > 0".to_string());
+
+        let zircon = p.to_zircon();
+        assert_eq!(zircon, "1/note:a%3Ab%2Fc%2Cd:text,sig:ab%2Fcd+e=:base64/rate:50%25:text//note > 0");
+        let p2 = Program::from_zircon(&zircon).unwrap();
+        assert_eq!(p2.secret, p.secret);
+        assert_eq!(p2.public, p.public);
+
+        // Simple values and arrays stay readable
+        let p = Program::from_zircon("1/A:10,votes:[1,0,1]/-/-/sum(votes) > A").unwrap();
+        assert_eq!(p.to_zircon(), "1/A:10,votes:[1,0,1]/-//sum(votes) > A");
+
+        // A '%' that is not an escape is kept
+        let p = Program::from_zircon("1/rate:50%:text,x:%2f/-/-/rate > 0").unwrap();
+        assert_eq!(p.secret["rate"].value.as_deref(), Some("50%"));
+        assert_eq!(p.secret["x"].value.as_deref(), Some("/"));
+    }
+
+    #[test]
+    fn test_parse_empty_value_is_output() {
+        let p = Program::from_zircon("1/A:10/result:/-/A+1").unwrap();
+        assert_eq!(p.public["result"], Signal::output());
+
+        let p = Program::from_zircon("1/A:10/result::hex/-/A+1").unwrap();
+        assert_eq!(p.public["result"].value, None);
+        assert_eq!(p.public["result"].encoding, Some(ValueEncoding::Hex));
+    }
+
+    #[test]
+    fn test_diff_value_override() {
+        let template = Program::from_zircon("1/age:?/-/-/age>=18").unwrap();
+        let mut filled = template.clone();
+        filled.secret.insert("age".to_string(), Signal::new("25"));
+
+        let changes = template.diff(&filled);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].name, "age");
+        assert_eq!(changes[0].kind, SignalChangeKind::Changed);
+        assert!(!changes[0].public);
+        assert_eq!(changes[0].old.as_ref().unwrap().value.as_deref(), Some("?"));
+        assert_eq!(changes[0].new.as_ref().unwrap().value.as_deref(), Some("25"));
+    }
+
+    #[test]
+    fn test_diff_encoding_change() {
+        let a = Program::from_zircon("1/key:ff/-/-/key>0").unwrap();
+        let b = Program::from_zircon("1/key:ff:hex/-/-/key>0").unwrap();
+
+        let changes = a.diff(&b);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, SignalChangeKind::Changed);
+        assert_eq!(changes[0].new.as_ref().unwrap().encoding, Some(ValueEncoding::Hex));
+    }
+
+    #[test]
+    fn test_diff_unchanged_added_removed() {
+        let a = Program::from_zircon("1/A:10,B:20/-/-/A+B").unwrap();
+        let b = Program::from_zircon("1/A:10/C:30/-/A+C").unwrap();
+
+        // A is unchanged and must not be reported
+        assert!(a.diff(&a).is_empty());
+
+        let changes = a.diff(&b);
+        assert_eq!(changes.len(), 2);
+        assert!(changes.iter().all(|c| c.name != "A"));
+        assert!(changes.iter().any(|c| c.name == "B" && c.kind == SignalChangeKind::Removed));
+        assert!(changes.iter().any(|c| c.name == "C" && c.kind == SignalChangeKind::Added && c.public));
+    }
+
+    #[test]
+    fn test_merge_programs() {
+        let base = Program::from_zircon("1/secret:7,salt:42/-/h<==sha256(salt{%x}|secret{%x})/h>0").unwrap();
+        let overlay = Program::from_zircon("1/salt:42/threshold:100/s<==sha256(h)/h>threshold").unwrap();
+
+        let merged = Program::merge(&base, &overlay).unwrap();
+        assert_eq!(merged.secret.keys().collect::<Vec<_>>(), vec!["secret", "salt"]);
+        assert_eq!(merged.public.get("threshold").unwrap().value.as_deref(), Some("100"));
+        assert_eq!(merged.circuit, vec!["h>0", "h>threshold"]);
+        assert_eq!(merged.output_expression().map(String::as_str), Some("h>threshold"));
+    }
+
+    #[test]
+    fn test_merge_preprocess_order() {
+        let base = Program::from_zircon("1/A:1/-/a<==sha256(A);b<==md5(a)/A>0").unwrap();
+        let overlay = Program::from_zircon("1/-/-/c<==sha1(b)/A>0").unwrap();
+
+        let merged = Program::merge(&base, &overlay).unwrap();
+        assert_eq!(merged.preprocess, vec!["a<==sha256(A)", "b<==md5(a)", "c<==sha1(b)"]);
+    }
+
+    #[test]
+    fn test_merge_conflicting_signals() {
+        let base = Program::from_zircon("1/A:1/B:2/-/A>0").unwrap();
+
+        let err = Program::merge(&base, &Program::from_zircon("1/A:5/-/-/A>0").unwrap()).unwrap_err();
+        assert!(err.contains("Conflicting values for secret signal 'A'"), "{}", err);
+
+        // Same value but different section
+        let err = Program::merge(&base, &Program::from_zircon("1/B:2/-/-/B>0").unwrap()).unwrap_err();
+        assert!(err.contains("'B'"), "{}", err);
+
+        assert!(Program::merge(&base, &Program::from_zircon("2/-/-/-/A>0").unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_validation_errors_valid_program() {
+        let p = Program::from_zircon("1/A:10,B:0xff:hex/-/h<==sha256(A{%x}):16/sum<==A+B;sum>sha1(h)").unwrap();
+        assert_eq!(p.validation_errors(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_validation_errors_reports_all() {
+        let p = Program::from_zircon("1/A:zz:hex,B:20/-/h<==nohash(A)/sum<==A+B;sum>>25").unwrap();
+
+        let errors = p.validation_errors();
+        assert_eq!(errors.len(), 3, "{:?}", errors);
+        assert!(errors[0].starts_with("Signal 'A' has invalid value 'zz'"), "{}", errors[0]);
+        assert_eq!(errors[1], "Preprocess statement 1 'h<==nohash(A)': Unknown function: nohash");
+        assert!(errors[2].starts_with("Circuit statement 2 'sum>>25':"), "{}", errors[2]);
+
+        // validate() still stops at the first signal error
+        assert!(p.validate().unwrap_err().starts_with("Signal 'A'"));
+    }
+
+    #[test]
+    fn test_check_signal_usage_unused() {
+        // B is declared but never used; h and sum are intermediates
+        let p = Program::from_zircon("1/A:10,B:20/out:?/h<==sha256(A{%d})/sum<==A+h;sum>25").unwrap();
+        assert_eq!(p.check_signal_usage(), Ok(vec!["Signal 'B' is declared but never used".to_string()]));
+
+        let p = Program::from_zircon("1/A:10,B:20/out:?/-/A+B>25").unwrap();
+        assert_eq!(p.check_signal_usage(), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_check_signal_usage_missing() {
+        let p = Program::from_zircon("1/A:10/out:?/h<==sha256(D{%x})/A+C>h").unwrap();
+        assert_eq!(p.check_signal_usage(), Err(vec![
+            "Signal 'C' is used but not declared".to_string(),
+            "Signal 'D' is used but not declared".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn test_parse_with_strategy() {
+        let p = Program::from_zircon("1/A:10/-/-/A>5/bitd").unwrap();
+        assert_eq!(p.strategy, Strategy::BitD);
+        assert_eq!(p.circuit[0], "A>5");
+
+        // 5-part format defaults to auto
+        let p = Program::from_zircon("1/A:10/-/-/A>5").unwrap();
+        assert_eq!(p.strategy, Strategy::Auto);
+
+        assert!(Program::from_zircon("1/A:10/-/-/A>5/fastest").is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_zircon_with_strategy() {
+        let p = Program::from_zircon("1/A:10/-/-/A>5/lookup").unwrap();
+        let zircon = p.to_zircon();
+        assert_eq!(zircon, "1/A:10/-//A>5/lookup");
+
+        let p2 = Program::from_zircon(&zircon).unwrap();
+        assert_eq!(p2.strategy, Strategy::Lookup);
+
+        // Auto strategy is not emitted
+        let p = Program::from_zircon("1/A:10/-/-/A>5/auto").unwrap();
+        assert_eq!(p.to_zircon(), "1/A:10/-//A>5");
+    }
+
+    #[test]
+    fn test_json_strategy() {
+        let p = Program::from_zircon("1/A:10/-/-/A>5/boolean").unwrap();
+        let json = p.to_json().unwrap();
+        assert!(json.contains("\"strategy\": \"boolean\""));
+        assert_eq!(Program::from_json(&json).unwrap().strategy, Strategy::Boolean);
+
+        // Auto strategy is omitted from JSON and restored as default
+        let p = Program::from_zircon("1/A:10/-/-/A>5").unwrap();
+        let json = p.to_json().unwrap();
+        assert!(!json.contains("strategy"));
+        assert_eq!(Program::from_json(&json).unwrap().strategy, Strategy::Auto);
     }
 
     #[test]
@@ -572,6 +1274,45 @@ mod tests {
         assert!(p.validate().is_err());
     }
 
+    #[test]
+    fn test_validate_migratable_versions() {
+        for version in 1..=PROGRAM_VERSION {
+            let mut p = Program::new(version);
+            p.circuit.push("5+5".to_string());
+            assert!(p.validate().is_ok(), "version {}", version);
+        }
+
+        let mut p = Program::new(PROGRAM_VERSION + 1);
+        p.circuit.push("5+5".to_string());
+        assert!(p.validate().unwrap_err().contains("Unsupported program version"));
+        assert!(p.migrate().is_err());
+    }
+
+    #[test]
+    fn test_migrate_v1_to_current() {
+        let v1 = Program::from_zircon("1/A:10,key:0x0a0b,msg:hello world,b:3J98t1WpEZ73CNmYviecrnyiWrnqRhWNLy/min:05:hex,out:?/h<==sha256(msg)/A>min;h!=0;key>b").unwrap();
+        let migrated = v1.migrate().unwrap();
+
+        assert_eq!(migrated.version, PROGRAM_VERSION);
+        assert_eq!(migrated.secret["A"].encoding, Some(ValueEncoding::Decimal));
+        assert_eq!(migrated.secret["key"].encoding, Some(ValueEncoding::Hex));
+        assert_eq!(migrated.secret["msg"].encoding, Some(ValueEncoding::Text));
+        assert_eq!(migrated.secret["b"].encoding, Some(ValueEncoding::Base58));
+        assert_eq!(migrated.public["min"].encoding, Some(ValueEncoding::Hex));
+        assert_eq!(migrated.public["out"].encoding, None);
+
+        // Same circuit from the same values
+        let before = crate::circuit::Circuit::from_program(&v1).unwrap();
+        let after = crate::circuit::Circuit::from_program(&migrated).unwrap();
+        assert_eq!(before.signals, after.signals);
+        assert_eq!(before.circuit_output, after.circuit_output);
+        assert_eq!(migrated.circuit, v1.circuit);
+        assert_eq!(migrated.preprocess, v1.preprocess);
+
+        // Migrating the current version is a no-op
+        assert_eq!(migrated.migrate().unwrap().to_zircon(), migrated.to_zircon());
+    }
+
     #[test]
     fn test_validate_empty_circuit() {
         let p = Program::new(1);
@@ -779,4 +1520,19 @@ mod tests {
         assert!(zircon.contains("B:20"));
     }
 
+    #[test]
+    fn test_comments() {
+        let commented = Program::from_zircon(
+            "1/A:10,B:20/-/-/sum<==A+B # total;# bound check;sum>25 # output"
+        ).unwrap();
+        let plain = Program::from_zircon("1/A:10,B:20/-/-/sum<==A+B;sum>25").unwrap();
+        assert_eq!(commented.circuit, plain.circuit);
+
+        let p = Program::from_zircon("1/A:10/-/h<==sha256(A{%x}) # digest/h!=0").unwrap();
+        assert_eq!(p.preprocess, vec!["h<==sha256(A{%x})"]);
+
+        // `#` in signal values is not a comment
+        let p = Program::from_zircon("1/A:a#b:text/-/-/A!=0").unwrap();
+        assert_eq!(p.secret.get("A").unwrap().value.as_deref(), Some("a#b"));
+    }
 }
@@ -36,11 +36,17 @@ pub fn apply_signal_overrides(
                 if let Some(encoding) = override_signal.encoding {
                     secret_sig.encoding = Some(encoding);
                 }
+                if let Some(bits) = override_signal.bits {
+                    secret_sig.bits = Some(bits);
+                }
+                secret_sig.constant |= override_signal.constant;
             } else {
                 // Add new secret signal
                 program.secret.insert(name.clone(), crate::api::program::Signal {
                     value: override_signal.value.clone(),
                     encoding: override_signal.encoding,
+                    bits: override_signal.bits,
+                    constant: override_signal.constant,
                 });
             }
         } else {
@@ -53,11 +59,17 @@ pub fn apply_signal_overrides(
                 if let Some(encoding) = override_signal.encoding {
                     public_sig.encoding = Some(encoding);
                 }
+                if let Some(bits) = override_signal.bits {
+                    public_sig.bits = Some(bits);
+                }
+                public_sig.constant |= override_signal.constant;
             } else {
                 // Add new public signal
                 program.public.insert(name.clone(), crate::api::program::Signal {
                     value: override_signal.value.clone(),
                     encoding: override_signal.encoding,
+                    bits: override_signal.bits,
+                    constant: override_signal.constant,
                 });
             }
         }
@@ -100,6 +112,8 @@ pub fn program_to_prove_request(
             value: sig.value.clone(),
             encoding: sig.encoding,
             public: false,
+            bits: sig.bits,
+            constant: sig.constant,
         });
     }
 
@@ -109,6 +123,8 @@ pub fn program_to_prove_request(
             value: sig.value.clone(),
             encoding: sig.encoding,
             public: true,
+            bits: sig.bits,
+            constant: sig.constant,
         });
     }
 
@@ -117,5 +133,11 @@ pub fn program_to_prove_request(
         circuit: program.circuit.clone(),
         signals,
         strategy,
+        max_k: None,
+        max_bits: None,
+        transcript: None,
+        seed: None,
+        balance_chains: false,
+        metadata: None,
     }
 }
\ No newline at end of file
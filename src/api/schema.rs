@@ -0,0 +1,103 @@
+//! JSON Schema for the API types
+//!
+//! Lets clients in other languages generate their request/response types
+//! instead of hand-writing them. The schema is derived from the types in
+//! `types.rs` and `program.rs`, so descriptions come from their doc comments.
+
+use schemars::SchemaGenerator;
+use serde_json::{json, Value};
+use crate::api::{Program, ProveRequest, ProveResponse, VerifyRequest, VerifyResponse};
+
+/// JSON Schema (draft 2020-12) for the API types
+///
+/// Returns a single document whose `$defs` hold `ProveRequest`, `ProveResponse`,
+/// `VerifyRequest`, `VerifyResponse` and `Program`, plus the shared `Signal`,
+/// `ProgramSignal`, `PublicSignal`, `DebugInfo`, `ValueEncoding` and `Strategy`
+/// definitions they reference.
+///
+/// # Examples
+///
+/// ```ignore
+/// let schema = json_schema();
+/// let prove_request = &schema["$defs"]["ProveRequest"];
+/// assert!(prove_request["properties"]["circuit"].is_object());
+/// ```
+pub fn json_schema() -> Value {
+    let mut generator = SchemaGenerator::default();
+    generator.subschema_for::<ProveRequest>();
+    generator.subschema_for::<ProveResponse>();
+    generator.subschema_for::<VerifyRequest>();
+    generator.subschema_for::<VerifyResponse>();
+    generator.subschema_for::<Program>();
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "zkplex-core API",
+        "$defs": generator.take_definitions(true)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Assert that every key of `value` is a property of the `$defs` entry `name`
+    fn assert_described(value: &Value, name: &str) {
+        let schema = json_schema();
+        let properties = schema["$defs"][name]["properties"].as_object().unwrap();
+        for key in value.as_object().unwrap().keys() {
+            assert!(properties.contains_key(key), "{} is missing property '{}'", name, key);
+        }
+    }
+
+    #[test]
+    fn test_schema_describes_prove_request() {
+        let schema = json_schema();
+        let prove_request = &schema["$defs"]["ProveRequest"]["properties"];
+        assert!(prove_request["circuit"].is_object());
+        assert!(prove_request["signals"].is_object());
+
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "preprocess": ["h<==sha256(A)"],
+            "circuit": ["h > 0"],
+            "signals": {"A": {"value": "1", "encoding": "decimal", "public": true, "bits": 8, "constant": true}},
+            "strategy": "lookup",
+            "max_k": 16,
+            "max_bits": 32,
+            "transcript": "keccak256",
+            "seed": "01",
+            "balance_chains": true,
+            "metadata": "request-42"
+        }"#).unwrap();
+        let value = serde_json::to_value(&request).unwrap();
+        assert_described(&value, "ProveRequest");
+        assert_described(&value["signals"]["A"], "Signal");
+    }
+
+    #[test]
+    fn test_schema_describes_program_and_responses() {
+        let program = Program::from_json(
+            r#"{"version":1,"secret":{"A":{"value":"1","bits":8}},"public":{"T":{"value":"1","constant":true},"out":{}},"preprocess":["h<==sha256(A)"],"circuit":["A>0"],"strategy":"bitd"}"#
+        ).unwrap();
+        let value = serde_json::to_value(&program).unwrap();
+        assert_described(&value, "Program");
+        assert_described(&value["secret"]["A"], "ProgramSignal");
+        assert_described(&value["public"]["T"], "ProgramSignal");
+
+        let output = Some(crate::api::PublicSignal { value: "1".to_string(), encoding: None });
+        let response = VerifyResponse {
+            valid: false,
+            error: Some("bad proof".to_string()),
+            error_kind: Some(crate::api::VerifyErrorKind::ProofInvalid),
+            output,
+            metadata: Some("request-42".to_string()),
+        };
+        assert_described(&serde_json::to_value(&response).unwrap(), "VerifyResponse");
+
+        // Public signals of a verify request may be bare strings, as deserialization accepts
+        let schema = json_schema();
+        let inputs = schema["$defs"]["PublicSignalInput"]["anyOf"].as_array().unwrap();
+        assert!(inputs.iter().any(|input| input["type"] == "string"));
+        assert_eq!(schema["$defs"]["ProgramSignal"]["properties"]["bits"]["maximum"], 64);
+    }
+}
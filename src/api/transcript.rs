@@ -0,0 +1,247 @@
+//! Fiat–Shamir transcript selection
+//!
+//! Proofs are made non-interactive with a hash transcript. Halo2 ships a
+//! BLAKE2b transcript, the default. A Keccak-256 transcript is provided for
+//! verifiers that only have Keccak available (e.g. EVM contracts).
+//!
+//! The transcript is stored in the verification context, so `verify()` reads
+//! a proof with the same hash it was created with.
+
+use ff::PrimeField;
+use halo2_proofs::arithmetic::{Coordinates, CurveAffine};
+use halo2_proofs::transcript::{
+    Challenge255, EncodedChallenge, Transcript, TranscriptRead, TranscriptWrite,
+};
+use sha3::{Digest, Keccak256};
+use std::io::{self, Read, Write};
+use std::marker::PhantomData;
+
+/// Hash used for the Fiat–Shamir transcript of a proof
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TranscriptHash {
+    /// BLAKE2b, Halo2's native transcript (default)
+    #[default]
+    Blake2b,
+    /// Keccak-256
+    Keccak256,
+}
+
+impl TranscriptHash {
+    /// Returns the string representation of the transcript hash
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranscriptHash::Blake2b => "blake2b",
+            TranscriptHash::Keccak256 => "keccak256",
+        }
+    }
+
+    /// Parse an optional transcript name, `None` meaning the default
+    pub fn from_option(name: Option<&str>) -> Result<Self, String> {
+        name.map_or(Ok(TranscriptHash::default()), str::parse)
+    }
+}
+
+impl std::fmt::Display for TranscriptHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for TranscriptHash {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "blake2b" => Ok(TranscriptHash::Blake2b),
+            "keccak256" | "keccak" => Ok(TranscriptHash::Keccak256),
+            _ => Err(format!(
+                "Invalid transcript '{}'. Valid transcripts: blake2b, keccak256",
+                s
+            )),
+        }
+    }
+}
+
+/// Prefix to a prover's message soliciting a challenge
+const KECCAK256_PREFIX_CHALLENGE: u8 = 0;
+
+/// Prefix to a prover's message containing a curve point
+const KECCAK256_PREFIX_POINT: u8 = 1;
+
+/// Prefix to a prover's message containing a scalar
+const KECCAK256_PREFIX_SCALAR: u8 = 2;
+
+/// Domain separator, the Keccak counterpart of BLAKE2b's personalization
+const KECCAK256_DOMAIN: &[u8] = b"Halo2-Transcript";
+
+/// Keccak-256 sponge shared by the read and write transcripts
+#[derive(Debug, Clone)]
+struct Keccak256State(Keccak256);
+
+impl Keccak256State {
+    fn new() -> Self {
+        let mut state = Keccak256::new();
+        state.update(KECCAK256_DOMAIN);
+        Keccak256State(state)
+    }
+
+    /// Two 32-byte digests of the state, as the 64 bytes a `Challenge255` is reduced from
+    fn squeeze<C: CurveAffine>(&mut self) -> Challenge255<C> {
+        self.0.update([KECCAK256_PREFIX_CHALLENGE]);
+        let mut input = [0u8; 64];
+        for (half, counter) in input.chunks_mut(32).zip(0u8..) {
+            let mut hasher = self.0.clone();
+            hasher.update([counter]);
+            half.copy_from_slice(&hasher.finalize());
+        }
+        Challenge255::<C>::new(&input)
+    }
+
+    fn absorb_point<C: CurveAffine>(&mut self, point: C) -> io::Result<()> {
+        self.0.update([KECCAK256_PREFIX_POINT]);
+        let coords: Coordinates<C> = Option::from(point.coordinates()).ok_or_else(|| {
+            io::Error::other("cannot write points at infinity to the transcript")
+        })?;
+        self.0.update(coords.x().to_repr().as_ref());
+        self.0.update(coords.y().to_repr().as_ref());
+        Ok(())
+    }
+
+    fn absorb_scalar<F: PrimeField>(&mut self, scalar: F) {
+        self.0.update([KECCAK256_PREFIX_SCALAR]);
+        self.0.update(scalar.to_repr().as_ref());
+    }
+}
+
+/// Keccak-256 transcript read by the verifier
+#[derive(Debug, Clone)]
+pub struct Keccak256Read<R: Read, C: CurveAffine, E: EncodedChallenge<C>> {
+    state: Keccak256State,
+    reader: R,
+    _marker: PhantomData<(C, E)>,
+}
+
+impl<R: Read, C: CurveAffine, E: EncodedChallenge<C>> Keccak256Read<R, C, E> {
+    /// Initialize a transcript given an input buffer
+    pub fn init(reader: R) -> Self {
+        Keccak256Read { state: Keccak256State::new(), reader, _marker: PhantomData }
+    }
+}
+
+impl<R: Read, C: CurveAffine> Transcript<C, Challenge255<C>> for Keccak256Read<R, C, Challenge255<C>> {
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        self.state.squeeze()
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        self.state.absorb_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.state.absorb_scalar(scalar);
+        Ok(())
+    }
+}
+
+impl<R: Read, C: CurveAffine> TranscriptRead<C, Challenge255<C>> for Keccak256Read<R, C, Challenge255<C>> {
+    fn read_point(&mut self) -> io::Result<C> {
+        let mut compressed = C::Repr::default();
+        self.reader.read_exact(compressed.as_mut())?;
+        let point: C = Option::from(C::from_bytes(&compressed)).ok_or_else(|| {
+            io::Error::other("invalid point encoding in proof")
+        })?;
+        self.common_point(point)?;
+        Ok(point)
+    }
+
+    fn read_scalar(&mut self) -> io::Result<C::Scalar> {
+        let mut data = <C::Scalar as PrimeField>::Repr::default();
+        self.reader.read_exact(data.as_mut())?;
+        let scalar: C::Scalar = Option::from(C::Scalar::from_repr(data)).ok_or_else(|| {
+            io::Error::other("invalid field element encoding in proof")
+        })?;
+        self.common_scalar(scalar)?;
+        Ok(scalar)
+    }
+}
+
+/// Keccak-256 transcript written by the prover
+#[derive(Debug, Clone)]
+pub struct Keccak256Write<W: Write, C: CurveAffine, E: EncodedChallenge<C>> {
+    state: Keccak256State,
+    writer: W,
+    _marker: PhantomData<(C, E)>,
+}
+
+impl<W: Write, C: CurveAffine, E: EncodedChallenge<C>> Keccak256Write<W, C, E> {
+    /// Initialize a transcript given an output buffer
+    pub fn init(writer: W) -> Self {
+        Keccak256Write { state: Keccak256State::new(), writer, _marker: PhantomData }
+    }
+
+    /// Conclude the interaction and return the output buffer (writer)
+    pub fn finalize(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write, C: CurveAffine> Transcript<C, Challenge255<C>> for Keccak256Write<W, C, Challenge255<C>> {
+    fn squeeze_challenge(&mut self) -> Challenge255<C> {
+        self.state.squeeze()
+    }
+
+    fn common_point(&mut self, point: C) -> io::Result<()> {
+        self.state.absorb_point(point)
+    }
+
+    fn common_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.state.absorb_scalar(scalar);
+        Ok(())
+    }
+}
+
+impl<W: Write, C: CurveAffine> TranscriptWrite<C, Challenge255<C>> for Keccak256Write<W, C, Challenge255<C>> {
+    fn write_point(&mut self, point: C) -> io::Result<()> {
+        self.common_point(point)?;
+        self.writer.write_all(point.to_bytes().as_ref())
+    }
+
+    fn write_scalar(&mut self, scalar: C::Scalar) -> io::Result<()> {
+        self.common_scalar(scalar)?;
+        self.writer.write_all(scalar.to_repr().as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::pasta::{EqAffine, Fp};
+    use halo2_proofs::transcript::{Blake2bWrite, TranscriptWrite};
+
+    #[test]
+    fn test_transcript_hash_parse() {
+        assert_eq!(TranscriptHash::from_option(None).unwrap(), TranscriptHash::Blake2b);
+        assert_eq!(TranscriptHash::from_option(Some("Keccak256")).unwrap(), TranscriptHash::Keccak256);
+        assert_eq!("blake2b".parse::<TranscriptHash>().unwrap().to_string(), "blake2b");
+        assert!(TranscriptHash::from_option(Some("poseidon")).is_err());
+    }
+
+    #[test]
+    fn test_keccak_transcript_read_matches_write() {
+        let scalar = Fp::from(42);
+
+        let mut writer = Keccak256Write::<_, EqAffine, Challenge255<_>>::init(vec![]);
+        writer.write_scalar(scalar).unwrap();
+        let written = *writer.squeeze_challenge();
+        let proof = writer.finalize();
+
+        let mut reader = Keccak256Read::<_, EqAffine, Challenge255<_>>::init(&proof[..]);
+        assert_eq!(reader.read_scalar().unwrap(), scalar);
+        assert_eq!(*reader.squeeze_challenge(), written);
+
+        // Same messages, different challenge than BLAKE2b
+        let mut blake = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+        blake.write_scalar(scalar).unwrap();
+        assert_ne!(*blake.squeeze_challenge(), written);
+    }
+}
@@ -3,13 +3,21 @@
 //! This module defines the JSON request/response formats for the WASM API.
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use indexmap::IndexMap;
 use crate::encoding::{ValueEncoding, parse_value, parse_value_auto};
 use crate::circuit::Strategy;
+use std::collections::BTreeMap;
+use std::time::Duration;
 
 /// Current API version for proof format
 pub const PROOF_VERSION: u32 = 1;
 
+/// Largest circuit size parameter `prove` accepts when a request sets no `max_k`
+///
+/// k=24 means 16M rows; params and keys at that size already need several GB.
+pub const DEFAULT_MAX_K: u32 = 24;
+
 /// Signal definition with value and visibility
 ///
 /// # Value Formats
@@ -49,7 +57,7 @@ pub const PROOF_VERSION: u32 = 1;
 /// - All digits → decimal
 /// - Contains base64 chars (+/=) → base64
 /// - Otherwise → base58 or decimal
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Signal {
     /// Value in the specified encoding format
     /// Optional for output signals (will be computed during proof generation)
@@ -63,10 +71,22 @@ pub struct Signal {
     /// Whether this signal is public (default: false = secret/witness)
     #[serde(default)]
     pub public: bool,
+
+    /// Declared maximum bit width; the value is range-checked against it in the circuit
+    /// and it fixes the range table size regardless of the witness magnitude
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(range(min = 1, max = 64))]
+    pub bits: Option<usize>,
+
+    /// Substitute the value into the circuit as a constant (default: false)
+    /// A constant is not a public input: it is part of the verifying key, so
+    /// changing it needs new keys, and its value is stored in the proof
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub constant: bool,
 }
 
 /// Request to create a ZKP proof
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProveRequest {
     /// Preprocessing statements (executed before circuit)
     /// Format: `name <== operation(args)`
@@ -91,10 +111,49 @@ pub struct ProveRequest {
     /// - "boolean": Base strategy (no range comparisons)
     #[serde(default)]
     pub strategy: Strategy,
+
+    /// Maximum circuit size parameter the prover will accept (optional)
+    /// Circuits whose estimated k exceeds this are rejected before any
+    /// params are allocated. Defaults to `DEFAULT_MAX_K` when not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_k: Option<u32>,
+
+    /// Force the range table size in bits: 8, 16, 32 or 64 (optional)
+    /// By default it is sized from the witness, so different inputs can give
+    /// different `k` and verifying keys. A fixed size keeps the circuit shape
+    /// stable across witnesses; proving fails if an operand doesn't fit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bits: Option<usize>,
+
+    /// Fiat–Shamir transcript hash: "blake2b" (default) or "keccak256" (optional)
+    /// Stored in the verification context, so verification uses the same hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
+
+    /// Hex seed for the proof randomness (optional)
+    /// Makes proofs reproducible, e.g. for tests asserting exact proof bytes.
+    /// Never reuse a seed with a different witness: that can leak the secrets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+
+    /// Re-associate `+`, `*`, `AND` and `OR` chains into balanced trees (optional)
+    /// Lowers the critical path depth without changing results. The circuit
+    /// layout changes, so proofs get a different verifying key; the balanced
+    /// statements are stored in the verification context.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub balance_chains: bool,
+
+    /// Caller-supplied opaque string bound to the proof (optional)
+    /// For the application's own bookkeeping (a request ID, a chain, a
+    /// timestamp). It is not secret: it is stored in the verification context
+    /// and absorbed into the proof transcript, so a proof only verifies with
+    /// the metadata it was created with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
 }
 
 /// Public signal value with optional encoding information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PublicSignal {
     /// Signal value as string
     pub value: String,
@@ -107,7 +166,7 @@ pub struct PublicSignal {
 
 /// Debug information for proof generation
 /// Contains human-readable version of verification context plus warnings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DebugInfo {
     /// Preprocessing statements (if any)
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
@@ -135,10 +194,37 @@ pub struct DebugInfo {
     /// Optional warnings about privacy or security
     #[serde(skip_serializing_if = "Option::is_none")]
     pub warnings: Option<Vec<String>>,
+
+    /// Comparison operands that are circuit constants or public inputs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comparison_bounds: Vec<ComparisonBound>,
+}
+
+/// A comparison operand that is not a secret, e.g. the `18` of `age >= 18`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ComparisonBound {
+    /// The comparison, as parsed (e.g. "age >= 18")
+    pub comparison: String,
+
+    /// The operand: a constant or a public signal name
+    pub bound: String,
+
+    /// Whether the operand is fixed in the circuit or a public input
+    pub kind: BoundKind,
+}
+
+/// How a comparison bound enters the circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum BoundKind {
+    /// Fixed in the circuit: changing it needs a new verifying key
+    Constant,
+    /// Public instance input: changing it keeps the verifying key
+    Public,
 }
 
 /// Response from proof generation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProveResponse {
     /// Proof format version (current: 1)
     #[serde(default = "default_version")]
@@ -154,6 +240,11 @@ pub struct ProveResponse {
     /// Public signal values with encoding information
     pub public_signals: IndexMap<String, PublicSignal>,
 
+    /// Hash of the circuit the proof commits to (see `VerifyContext::compute_circuit_hash`)
+    /// Verifiers can pin it with `VerifyRequest::expected_circuit_hash`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub circuit_hash: String,
+
     /// Debug information (human-readable verification context + warnings)
     /// Optional - only included for debugging/logging purposes
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -194,10 +285,106 @@ pub struct VerifyContext {
     /// This is needed to reconstruct the same circuit constraints during verification
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cached_max_bits: Option<usize>,
+
+    /// Declared bit widths of input signals (range-checked in the circuit)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub signal_bits: BTreeMap<String, usize>,
+
+    /// Signals wider than 248 bits and their limb counts (compared limb by limb)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub wide_signals: BTreeMap<String, usize>,
+
+    /// Array signals and their element counts (needed to expand `sum`/`product`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub array_lengths: BTreeMap<String, usize>,
+
+    /// Constant signals and their decimal values (substituted into the circuit)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub constants: BTreeMap<String, String>,
+
+    /// Fiat–Shamir transcript hash the proof was created with (None = blake2b)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
+
+    /// Caller-supplied metadata, absorbed into the proof transcript
+    /// Not part of the circuit hash: proofs with different metadata share a circuit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+
+    /// Hash of every field that shapes the circuit (empty in older proofs):
+    /// circuit, preprocess, strategy, k, constants, secret signal names, output
+    /// signal, cached_max_bits, signal_bits, wide_signals and array_lengths.
+    /// The transcript and metadata are left out; they do not change the circuit.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub circuit_hash: String,
+
+    /// SHA-256 (hex) of the public inputs the proof was created with (empty in older proofs)
+    /// Only used to tell a wrong public signal from a bad proof when verification fails
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub public_inputs_hash: String,
+}
+
+impl VerifyContext {
+    /// SHA-256 (hex) over every field the rebuilt circuit depends on
+    ///
+    /// Covers the normalized circuit and preprocess statements, strategy, `k`,
+    /// signal names, range table size, declared widths, wide signals, array
+    /// lengths and constants. Circuit statements are parsed and printed in
+    /// canonical form, so whitespace and redundant parentheses do not change the
+    /// hash. Signal values are not included, except those of constant signals
+    /// (which shape the circuit): every proof of the same circuit shares it.
+    pub fn compute_circuit_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let normalize = |stmt: &str| -> String {
+            let (public, stmt) = crate::circuit::split_public_assignment(stmt);
+            let visibility = if public { "pub " } else { "" };
+            let (target, expr) = match stmt.find("<==") {
+                Some(pos) => (format!("{}{}<==", visibility, stmt[..pos].trim()), &stmt[pos + 3..]),
+                None => (String::new(), stmt),
+            };
+            // Statements the parser rejects (e.g. inline hash calls) only lose their whitespace
+            let expr = crate::parser::parse_circuit(expr.trim())
+                .map(|e| e.to_string())
+                .unwrap_or_else(|_| expr.split_whitespace().collect());
+            target + &expr
+        };
+
+        let mut canonical = format!("k={};strategy={}", self.k, self.strategy);
+        for stmt in &self.circuit {
+            canonical.push_str(";circuit=");
+            canonical.push_str(&normalize(stmt));
+        }
+        for stmt in &self.preprocess {
+            canonical.push_str(";preprocess=");
+            canonical.push_str(stmt.trim());
+        }
+        for (name, value) in &self.constants {
+            canonical.push_str(&format!(";constant={}={}", name, value));
+        }
+        for name in &self.secret_signals {
+            canonical.push_str(&format!(";secret={}", name));
+        }
+        canonical.push_str(&format!(";output={}", self.output_signal));
+        if let Some(bits) = self.cached_max_bits {
+            canonical.push_str(&format!(";max_bits={}", bits));
+        }
+        for (name, bits) in &self.signal_bits {
+            canonical.push_str(&format!(";bits={}={}", name, bits));
+        }
+        for (name, limbs) in &self.wide_signals {
+            canonical.push_str(&format!(";wide={}={}", name, limbs));
+        }
+        for (name, length) in &self.array_lengths {
+            canonical.push_str(&format!(";array={}={}", name, length));
+        }
+
+        hex::encode(Sha256::digest(canonical.as_bytes()))
+    }
 }
 
 /// Request to verify a ZKP proof
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VerifyRequest {
     /// Proof format version (current: 1)
     #[serde(default = "default_version")]
@@ -212,11 +399,44 @@ pub struct VerifyRequest {
 
     /// Public signal values with optional encoding information
     /// Can be simple strings (for backward compatibility) or PublicSignal objects
+    #[serde(deserialize_with = "deserialize_public_signals")]
+    #[schemars(with = "IndexMap<String, PublicSignalInput>")]
     pub public_signals: IndexMap<String, PublicSignal>,
+
+    /// Circuit hash the verifier trusts; a proof of any other circuit is invalid
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_circuit_hash: Option<String>,
+}
+
+/// Public signal as sent by a client: a bare value or a `PublicSignal` object
+#[derive(Deserialize, JsonSchema)]
+#[serde(untagged)]
+enum PublicSignalInput {
+    Value(String),
+    Signal(PublicSignal),
+}
+
+/// Deserialize public signals given as `"name": "value"` or `"name": {"value": ..., "encoding": ...}`
+///
+/// Bare strings come from older clients and have no encoding (auto-detected).
+fn deserialize_public_signals<'de, D>(deserializer: D) -> Result<IndexMap<String, PublicSignal>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let inputs = IndexMap::<String, PublicSignalInput>::deserialize(deserializer)?;
+    Ok(inputs.into_iter()
+        .map(|(name, input)| {
+            let signal = match input {
+                PublicSignalInput::Value(value) => PublicSignal { value, encoding: None },
+                PublicSignalInput::Signal(signal) => signal,
+            };
+            (name, signal)
+        })
+        .collect())
 }
 
 /// Response from proof verification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VerifyResponse {
     /// Whether the proof is valid
     pub valid: bool,
@@ -224,6 +444,105 @@ pub struct VerifyResponse {
     /// Optional error message if verification failed
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+
+    /// Why verification failed (only for invalid proofs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<VerifyErrorKind>,
+
+    /// Circuit output the proof is bound to (only for valid proofs)
+    ///
+    /// Read back from the verified public inputs rather than echoed from the
+    /// request, so it is the value the proof actually asserts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<PublicSignal>,
+
+    /// Metadata the proof was created with (only for valid proofs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<String>,
+}
+
+/// Class of a verification failure, see `VerifyResponse::error_kind`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VerifyErrorKind {
+    /// The proof is not valid Base85
+    DecodeError,
+    /// The context does not describe the circuit it claims (its own circuit
+    /// hash, or the expected one), or the circuit or its VK cannot be rebuilt
+    CircuitReconstructionFailed,
+    /// The public signals differ from those the proof was created with, or
+    /// the output signal is missing or not a number
+    PublicInputMismatch,
+    /// The proof does not verify for the public signals it was created with,
+    /// e.g. tampered proof bytes
+    ProofInvalid,
+}
+
+impl VerifyErrorKind {
+    /// Name as serialized in `VerifyResponse`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VerifyErrorKind::DecodeError => "decode_error",
+            VerifyErrorKind::CircuitReconstructionFailed => "circuit_reconstruction_failed",
+            VerifyErrorKind::PublicInputMismatch => "public_input_mismatch",
+            VerifyErrorKind::ProofInvalid => "proof_invalid",
+        }
+    }
+}
+
+/// Wall-clock durations of the proving and verification phases
+///
+/// Filled in by `prove_timed()` and `verify_timed()`. A phase that did not run
+/// is `None`, e.g. key generation when the proving key came from a registry cache.
+#[derive(Debug, Clone, Default)]
+pub struct Timings {
+    /// Verifying key generation
+    pub vk_generation: Option<Duration>,
+
+    /// Proving key generation
+    pub pk_generation: Option<Duration>,
+
+    /// Proof creation
+    pub proof_creation: Option<Duration>,
+
+    /// Proof verification
+    pub verification: Option<Duration>,
+}
+
+/// Response from a constraint check (dry run without proof generation)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResponse {
+    /// Whether the witness satisfies all constraints
+    pub satisfied: bool,
+
+    /// Circuit size parameter the check was run at
+    pub k: u32,
+
+    /// Computed circuit output (None if the circuit produced no output)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// Constraint failures reported by the mock prover (empty if satisfied)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<String>,
+}
+
+/// Response from evaluating a circuit (no constraints checked, no proof)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluateResponse {
+    /// Circuit output as a decimal string (None if the circuit produced no output)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// Circuit output in the encoding declared by the output signal
+    /// Only present when that encoding is not decimal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoded_output: Option<PublicSignal>,
+
+    /// Intermediate signals (preprocess outputs and circuit assignments) and their
+    /// decimal values, in evaluation order
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub signals: IndexMap<String, String>,
 }
 
 /// Error response
@@ -273,10 +592,78 @@ pub struct EstimateResponse {
     /// Depends on circuit complexity, roughly: 1KB + (columns * 32 bytes)
     pub vk_size_bytes: u64,
 
+    /// Longest chain of operations that depend on each other
+    /// `((A+B)+C)+D` is 3 deep, `(A+B)+(C+D)` is 2: a lower depth for the same
+    /// operation count means a less serial circuit
+    #[serde(default)]
+    pub critical_path_depth: u32,
+
     /// Circuit complexity description
     pub complexity: String,
 }
 
+impl EstimateResponse {
+    /// Column names for [`EstimateResponse::to_csv_row`], in order
+    pub const CSV_HEADER: &'static str = "k,total_rows,estimated_rows,operation_count,comparison_count,\
+preprocess_count,params_size_bytes,proof_size_bytes,vk_size_bytes,complexity";
+
+    /// Render as a single CSV row (columns as in [`EstimateResponse::CSV_HEADER`])
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            self.k,
+            self.total_rows,
+            self.estimated_rows,
+            self.operation_count,
+            self.comparison_count,
+            self.preprocess_count,
+            self.params_size_bytes,
+            self.proof_size_bytes,
+            self.vk_size_bytes,
+            self.complexity,
+        )
+    }
+}
+
+/// Output format for estimate/layout reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human-readable text (default)
+    #[default]
+    Text,
+    /// JSON (stable schema, matches the response struct)
+    Json,
+    /// Single CSV row
+    Csv,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Text => write!(f, "text"),
+            OutputFormat::Json => write!(f, "json"),
+            OutputFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "Invalid format '{}'. Valid formats: text, json, csv",
+                s
+            )),
+        }
+    }
+}
+
 impl ProveRequest {
     /// Validate the prove request
     ///
@@ -296,6 +683,13 @@ impl ProveRequest {
         // Validate that all signal values can be parsed according to their encoding
         // Skip output signals (no value)
         for (name, signal) in &self.signals {
+            if signal.constant && !signal.public {
+                return Err(format!(
+                    "Secret signal '{}' cannot be constant: constant values are stored in the proof",
+                    name
+                ));
+            }
+
             // Skip validation for output signals (value is None)
             let value = match &signal.value {
                 Some(v) => v,
@@ -372,6 +766,8 @@ impl ProveRequest {
             let prog_signal = ProgramSignal {
                 value: signal.value.clone(),
                 encoding: signal.encoding,
+                bits: signal.bits,
+                constant: signal.constant,
             };
 
             if signal.public {
@@ -387,6 +783,7 @@ impl ProveRequest {
             public,
             preprocess: self.preprocess.clone(),
             circuit: self.circuit.clone(),
+            strategy: self.strategy,
         }
     }
 }
@@ -442,6 +839,8 @@ mod tests {
                 value: Some("10".to_string()),
                 encoding: None,
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
         signals.insert(
@@ -450,6 +849,8 @@ mod tests {
                 value: Some("20".to_string()),
                 encoding: None,
                 public: true,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -458,6 +859,12 @@ mod tests {
             circuit: vec!["(A + B) * C > D".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -473,6 +880,8 @@ mod tests {
             value: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb".to_string()),
             encoding: Some(ValueEncoding::Hex),
             public: true,
+            bits: None,
+            constant: false,
         };
 
         let json = serde_json::to_string(&signal).unwrap();
@@ -488,6 +897,8 @@ mod tests {
             value: Some("9aE476sH92Vc7DMCzKNgWUiQ6UdC2DXf9v".to_string()),
             encoding: Some(ValueEncoding::Base58),
             public: false,
+            bits: None,
+            constant: false,
         };
 
         let json = serde_json::to_string(&signal).unwrap();
@@ -502,6 +913,8 @@ mod tests {
             value: Some("SGVsbG8gV29ybGQ=".to_string()),
             encoding: Some(ValueEncoding::Base64),
             public: true,
+            bits: None,
+            constant: false,
         };
 
         let json = serde_json::to_string(&signal).unwrap();
@@ -517,6 +930,8 @@ mod tests {
                 value: Some("10".to_string()),
                 encoding: None,
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -525,6 +940,12 @@ mod tests {
             circuit: vec!["(A + B) > C".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         assert!(request.validate().is_ok());
@@ -539,6 +960,8 @@ mod tests {
                 value: Some("not_a_number".to_string()),
                 encoding: None,
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -547,6 +970,12 @@ mod tests {
             circuit: vec!["A > B".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         assert!(request.validate().is_err());
@@ -561,6 +990,8 @@ mod tests {
                 value: Some("10".to_string()),
                 encoding: None,
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
         signals.insert(
@@ -569,6 +1000,8 @@ mod tests {
                 value: Some("20".to_string()),
                 encoding: None,
                 public: true,
+                bits: None,
+                constant: false,
             },
         );
         signals.insert(
@@ -577,6 +1010,8 @@ mod tests {
                 value: Some("30".to_string()),
                 encoding: None,
                 public: true,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -585,6 +1020,12 @@ mod tests {
             circuit: vec!["(A + B) > C".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         let public_names = request.public_signal_names();
@@ -602,11 +1043,15 @@ mod tests {
         let response = VerifyResponse {
             valid: true,
             error: None,
+            error_kind: None,
+            output: None,
+            metadata: None,
         };
 
         let json = serde_json::to_string(&response).unwrap();
         assert!(json.contains("\"valid\":true"));
         assert!(!json.contains("error")); // Should be omitted when None
+        assert!(!json.contains("output"));
     }
 
     #[test]
@@ -633,6 +1078,8 @@ mod tests {
                 value: Some("0x742d35Cc6634C0532925a3b844Bc9e7595f0bE".to_string()),
                 encoding: Some(ValueEncoding::Hex),
                 public: true,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -641,6 +1088,12 @@ mod tests {
             circuit: vec!["addr > 0".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         // Should pass validation (hex with explicit encoding)
@@ -656,6 +1109,8 @@ mod tests {
                 value: Some("9aE476sH92Vc7DMC8bZNpe1xNNNy1fNjFpCGvfMuZMwM".to_string()),
                 encoding: Some(ValueEncoding::Base58),
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -664,6 +1119,12 @@ mod tests {
             circuit: vec!["solana_addr == solana_addr".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         // Should pass validation (base58 with explicit encoding)
@@ -679,6 +1140,8 @@ mod tests {
                 value: Some("SGVsbG8gV29ybGQ=".to_string()),
                 encoding: Some(ValueEncoding::Base64),
                 public: true,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -687,6 +1150,12 @@ mod tests {
             circuit: vec!["data > 0".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         // Should pass validation (base64 with explicit encoding)
@@ -702,6 +1171,8 @@ mod tests {
                 value: Some("0x1a2b".to_string()),
                 encoding: None,  // Auto-detect
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -710,6 +1181,12 @@ mod tests {
             circuit: vec!["addr > 0".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         // Should pass validation (hex auto-detected)
@@ -725,6 +1202,8 @@ mod tests {
                 value: Some("0xZZZZ".to_string()),  // Invalid hex
                 encoding: Some(ValueEncoding::Hex),
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -733,12 +1212,20 @@ mod tests {
             circuit: vec!["addr > 0".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
-        // Should fail validation (invalid hex)
+        // Should fail validation (invalid hex), naming the first bad character
         let output = request.validate();
         assert!(output.is_err());
-        assert!(output.unwrap_err().contains("addr"));
+        let err = output.unwrap_err();
+        assert!(err.contains("addr"));
+        assert!(err.contains("character 'Z' at index 2 is not a hex digit"), "{}", err);
     }
 
     #[test]
@@ -750,6 +1237,8 @@ mod tests {
                 value: Some("0OIl".to_string()),  // Invalid base58 (contains 0, O, I, l)
                 encoding: Some(ValueEncoding::Base58),
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -758,12 +1247,20 @@ mod tests {
             circuit: vec!["addr > 0".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
-        // Should fail validation (invalid base58)
+        // Should fail validation (invalid base58), naming the first bad character
         let output = request.validate();
         assert!(output.is_err());
-        assert!(output.unwrap_err().contains("addr"));
+        let err = output.unwrap_err();
+        assert!(err.contains("addr"));
+        assert!(err.contains("character '0' at index 0 is not in the base58 alphabet"), "{}", err);
     }
 
     #[test]
@@ -775,6 +1272,8 @@ mod tests {
                 value: Some("999999999999999999999999999999".to_string()),  // Very large decimal
                 encoding: None,
                 public: false,
+                bits: None,
+                constant: false,
             },
         );
 
@@ -783,9 +1282,79 @@ mod tests {
             circuit: vec!["large > 0".to_string()],
             signals,
             strategy: Strategy::Auto,
+            max_k: None,
+            max_bits: None,
+            transcript: None,
+            seed: None,
+            balance_chains: false,
+            metadata: None,
         };
 
         // Should pass validation (large decimal is valid)
         assert!(request.validate().is_ok());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_verify_request_accepts_string_and_object_public_signals() {
+        let request: VerifyRequest = serde_json::from_str(r#"{
+            "proof": "<~~>",
+            "verify_context": "<~~>",
+            "public_signals": {
+                "threshold": "100",
+                "hash": { "value": "0xabcd", "encoding": "hex" },
+                "result": { "value": "1" }
+            }
+        }"#).unwrap();
+
+        assert_eq!(request.public_signals.keys().collect::<Vec<_>>(), ["threshold", "hash", "result"]);
+        assert_eq!(request.public_signals["threshold"].value, "100");
+        assert_eq!(request.public_signals["threshold"].encoding, None);
+        assert_eq!(request.public_signals["hash"].value, "0xabcd");
+        assert_eq!(request.public_signals["hash"].encoding, Some(ValueEncoding::Hex));
+        assert_eq!(request.public_signals["result"].encoding, None);
+
+        // Serialized back in the object form
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(json["public_signals"]["threshold"], serde_json::json!({ "value": "100" }));
+
+        let invalid = serde_json::from_str::<VerifyRequest>(
+            r#"{"proof":"","verify_context":"","public_signals":{"A":42}}"#
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn test_output_format_parse() {
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!("CSV".parse::<OutputFormat>().unwrap(), OutputFormat::Csv);
+        assert_eq!(OutputFormat::default(), OutputFormat::Text);
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_estimate_json_and_csv_output() {
+        let request: ProveRequest = serde_json::from_str(r#"{
+            "circuit": ["A + B > C"],
+            "signals": {
+                "A": { "value": "10" },
+                "B": { "value": "20" },
+                "C": { "value": "25", "public": true }
+            }
+        }"#).unwrap();
+
+        let estimate = crate::api::core::estimate(request).unwrap();
+
+        let json: serde_json::Value = serde_json::to_value(&estimate).unwrap();
+        assert_eq!(json["k"], estimate.k);
+        assert_eq!(json["estimated_rows"], estimate.estimated_rows);
+        assert_eq!(json["proof_size_bytes"], estimate.proof_size_bytes);
+
+        let row = estimate.to_csv_row();
+        assert!(!row.contains('\n'));
+        assert_eq!(
+            row.split(',').count(),
+            EstimateResponse::CSV_HEADER.split(',').count()
+        );
+        assert!(row.starts_with(&format!("{},{},", estimate.k, estimate.total_rows)));
+    }
+}
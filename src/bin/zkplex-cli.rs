@@ -24,7 +24,7 @@ use std::process;
 use std::fs;
 use std::path::Path;
 use indexmap::IndexMap;
-use zkplex_core::api::{Program, Signal, ProveResponse, VerifyRequest};
+use zkplex_core::api::{Program, Signal, ProveResponse, PublicSignal, VerifyRequest, OutputFormat, Timings};
 use zkplex_core::api::program::Signal as ProgramSignal;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -35,9 +35,10 @@ fn get_build_id() -> Option<&'static str> {
     option_env!("BUILD_ID")
 }
 
-use zkplex_core::circuit::{Circuit, estimate_circuit_requirements_with_strategy, validate_strategy_compatibility, Strategy};
+use zkplex_core::circuit::{Circuit, CircuitRegistry, estimate_circuit_requirements_with_strategy, validate_strategy_compatibility, Strategy};
 use zkplex_core::encoding::ValueEncoding;
 use zkplex_core::layout;
+use zkplex_core::ZkplexError;
 
 fn main() {
     let args: Vec<String> = std::env::args().collect();
@@ -55,14 +56,32 @@ fn main() {
     let mut secret_signals: Vec<String> = Vec::new();
     let mut public_signals: Vec<String> = Vec::new();
     let mut proof_file: Option<String> = None;
+    let mut proof_out_dir: Option<String> = None;
     let mut into_json = false;
     let mut into_zircon = false;
     let mut show_info = false;
     let mut show_estimate = false;
     let mut show_layout = false;
+    let mut show_witness = false;
+    let mut show_explain = false;
+    let mut do_validate = false;
     let mut do_prove = false;
     let mut do_verify = false;
     let mut proof_strategy: Option<Strategy> = None;
+    let mut output_format: Option<OutputFormat> = None;
+    let mut color_choice = layout::ColorChoice::default();
+    let mut batch_file: Option<String> = None;
+    let mut show_time = false;
+    let mut seed: Option<String> = None;
+    let mut params_file: Option<String> = None;
+    let mut jobs: Option<usize> = None;
+    let mut repeat: usize = 1;
+    let mut json_compact = false;
+    let mut balance_chains = false;
+    let mut max_bits: Option<usize> = None;
+    let mut from_stdin = false;
+    let mut expected_circuit_hash: Option<String> = None;
+    let mut verbosity = Verbosity::Normal;
 
     let mut i = 1;
     while i < args.len() {
@@ -85,6 +104,10 @@ fn main() {
                     process::exit(1);
                 }
             }
+            "--from-stdin" => {
+                from_stdin = true;
+                i += 1;
+            }
             "--circuit" => {
                 if i + 1 < args.len() {
                     circuit_input = Some(args[i + 1].clone());
@@ -121,6 +144,15 @@ fn main() {
                     process::exit(1);
                 }
             }
+            "--batch" => {
+                if i + 1 < args.len() {
+                    batch_file = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --batch requires a value");
+                    process::exit(1);
+                }
+            }
             "--prove" => {
                 do_prove = true;
                 i += 1;
@@ -129,6 +161,94 @@ fn main() {
                 do_verify = true;
                 i += 1;
             }
+            "--expected-circuit-hash" => {
+                if i + 1 < args.len() {
+                    expected_circuit_hash = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --expected-circuit-hash requires a value");
+                    process::exit(1);
+                }
+            }
+            "--jobs" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<usize>() {
+                        Ok(n) if n > 0 => jobs = Some(n),
+                        _ => {
+                            eprintln!("Error: --jobs requires a positive number");
+                            process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --jobs requires a value");
+                    process::exit(1);
+                }
+            }
+            "--repeat" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<usize>() {
+                        Ok(n) if n > 0 => repeat = n,
+                        _ => {
+                            eprintln!("Error: --repeat requires a positive number");
+                            process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --repeat requires a value");
+                    process::exit(1);
+                }
+            }
+            "--max-bits" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<usize>() {
+                        Ok(n) if [8, 16, 32, 64].contains(&n) => max_bits = Some(n),
+                        _ => {
+                            eprintln!("Error: --max-bits must be one of 8, 16, 32 or 64");
+                            process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --max-bits requires a value");
+                    process::exit(1);
+                }
+            }
+            "--balance-chains" => {
+                balance_chains = true;
+                i += 1;
+            }
+            "--json-compact" => {
+                json_compact = true;
+                i += 1;
+            }
+            "--time" => {
+                show_time = true;
+                i += 1;
+            }
+            "--quiet" | "-q" => {
+                verbosity = Verbosity::Quiet;
+                i += 1;
+            }
+            "--seed" => {
+                if i + 1 < args.len() {
+                    seed = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --seed requires a value");
+                    process::exit(1);
+                }
+            }
+            "--params" => {
+                if i + 1 < args.len() {
+                    params_file = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --params requires a value");
+                    process::exit(1);
+                }
+            }
             "--proof" => {
                 if i + 1 < args.len() {
                     proof_file = Some(args[i + 1].clone());
@@ -138,6 +258,15 @@ fn main() {
                     process::exit(1);
                 }
             }
+            "--proof-out-dir" => {
+                if i + 1 < args.len() {
+                    proof_out_dir = Some(args[i + 1].clone());
+                    i += 2;
+                } else {
+                    eprintln!("Error: --proof-out-dir requires a value");
+                    process::exit(1);
+                }
+            }
             "--into-json" => {
                 into_json = true;
                 i += 1;
@@ -158,6 +287,18 @@ fn main() {
                 show_layout = true;
                 i += 1;
             }
+            "--witness" => {
+                show_witness = true;
+                i += 1;
+            }
+            "--explain" => {
+                show_explain = true;
+                i += 1;
+            }
+            "--validate" => {
+                do_validate = true;
+                i += 1;
+            }
             "--proof-strategy" => {
                 if i + 1 < args.len() {
                     match args[i + 1].parse::<Strategy>() {
@@ -173,6 +314,40 @@ fn main() {
                     process::exit(1);
                 }
             }
+            "--format" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<OutputFormat>() {
+                        Ok(format) => output_format = Some(format),
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --format requires a value");
+                    process::exit(1);
+                }
+            }
+            "--color" => {
+                if i + 1 < args.len() {
+                    match args[i + 1].parse::<layout::ColorChoice>() {
+                        Ok(choice) => color_choice = choice,
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                    i += 2;
+                } else {
+                    eprintln!("Error: --color requires a value");
+                    process::exit(1);
+                }
+            }
+            "--schema" => {
+                println!("{}", serde_json::to_string_pretty(&zkplex_core::api::json_schema()).unwrap());
+                process::exit(0);
+            }
             "--help" | "-h" => {
                 print_usage();
                 process::exit(0);
@@ -196,6 +371,39 @@ fn main() {
         }
     }
 
+    let color = color_choice.enabled(std::io::IsTerminal::is_terminal(&std::io::stdout()));
+
+    // --from-stdin is `--json -` for a JSON object on stdin, `--zircon -` otherwise
+    if from_stdin {
+        if zircon_input.is_some() || json_input.is_some() {
+            eprintln!("Error: --from-stdin cannot be combined with --zircon or --json (use '-' as their value instead)");
+            process::exit(1);
+        }
+        if read_stdin().trim_start().starts_with('{') {
+            json_input = Some("-".to_string());
+        } else {
+            zircon_input = Some("-".to_string());
+        }
+    }
+
+    // Handle batch prove command
+    if let Some(file) = batch_file {
+        let options = BatchOptions { strategy: proof_strategy, max_bits, jobs, compact: json_compact };
+        prove_batch(&file, proof_file.as_deref(), proof_out_dir.as_deref(), &options, verbosity);
+        return;
+    }
+
+    // Program sources shared by the commands below
+    let inputs = ProgramInputs {
+        circuit: circuit_input.clone(),
+        zircon: zircon_input.clone(),
+        json: json_input.clone(),
+        preprocess: preprocess_inputs.clone(),
+        secret_signals: secret_signals.clone(),
+        public_signals: public_signals.clone(),
+        strategy: proof_strategy,
+    };
+
     // Handle prove command
     if do_prove {
         // Support --circuit, --zircon, or --json for proof generation
@@ -205,70 +413,24 @@ fn main() {
         }
 
         // Create Program from input format
-        let program = if let Some(circuit) = circuit_input {
-            // Direct circuit mode - convert to Program
-            let signals_map = parse_signals_from_cli(&secret_signals, &public_signals);
-
-            // Convert signals to Program format
-            let mut secret_sigs = IndexMap::new();
-            let mut public_sigs = IndexMap::new();
-
-            for (name, sig) in &signals_map {
-                let prog_sig = ProgramSignal {
-                    value: sig.value.clone(),
-                    encoding: sig.encoding,
-                };
-                if sig.public {
-                    public_sigs.insert(name.clone(), prog_sig);
-                } else {
-                    secret_sigs.insert(name.clone(), prog_sig);
-                }
-            }
-
-            // Parse circuit and preprocess statements (split on semicolons)
-            let circuit_statements = match Program::parse_statements(&circuit) {
-                Ok(statements) => statements,
-                Err(e) => {
-                    eprintln!("Error parsing circuit statements: {}", e);
-                    process::exit(1);
-                }
-            };
+        let program = program_from_inputs(&inputs, verbosity);
 
-            // Join multiple --preprocess arguments
-            let preprocess_combined = preprocess_inputs.join(";");
-            let preprocess_statements = if !preprocess_combined.is_empty() {
-                match Program::parse_statements(&preprocess_combined) {
-                    Ok(statements) => statements,
-                    Err(e) => {
-                        eprintln!("Error parsing preprocess statements: {}", e);
-                        process::exit(1);
-                    }
-                }
-            } else {
-                Vec::new()
-            };
-
-            Program {
-                version: zkplex_core::api::PROOF_VERSION,
-                secret: secret_sigs,
-                public: public_sigs,
-                preprocess: preprocess_statements,
-                circuit: circuit_statements,
-            }
-        } else {
-            // File format mode (zircon or json)
-            let (input, format) = if let Some(zircon) = zircon_input.as_ref() {
-                (zircon, "zircon")
-            } else if let Some(json) = json_input.as_ref() {
-                (json, "json")
-            } else {
-                unreachable!()
-            };
+        // Name the proof file by program fingerprint when writing to a directory
+        if let Some(dir) = &proof_out_dir {
+            let path = fingerprint_proof_path(dir, &program, proof_strategy.unwrap_or(program.strategy), None);
+            proof_file = Some(path.display().to_string());
+        }
 
-            load_program_from_format(input, format, &secret_signals, &public_signals)
+        let options = ProveOptions {
+            max_bits,
+            seed: seed.as_deref(),
+            params_file: params_file.as_deref(),
+            show_time,
+            repeat,
+            json_compact,
+            balance_chains,
         };
-
-        generate_proof(&program, proof_file.as_deref(), proof_strategy);
+        generate_proof(&program, proof_file.as_deref(), proof_strategy, &options, verbosity);
         return;
     }
 
@@ -279,7 +441,46 @@ fn main() {
             process::exit(1);
         }
 
-        verify_proof(&proof_file.unwrap(), into_json);
+        verify_proof(&proof_file.unwrap(), &public_signals, expected_circuit_hash, into_json, show_time, verbosity);
+        return;
+    }
+
+    // Handle validate command
+    if do_validate {
+        if circuit_input.is_none() && zircon_input.is_none() && json_input.is_none() {
+            eprintln!("Error: --circuit, --zircon, or --json is required for --validate");
+            process::exit(1);
+        }
+
+        let program = program_from_inputs(&inputs, verbosity);
+
+        validate_program(&program);
+        return;
+    }
+
+    // Handle explain command
+    if show_explain {
+        if circuit_input.is_none() && zircon_input.is_none() && json_input.is_none() {
+            eprintln!("Error: --circuit, --zircon, or --json is required for --explain");
+            process::exit(1);
+        }
+
+        let program = program_from_inputs(&inputs, verbosity);
+
+        print_explain(&program);
+        return;
+    }
+
+    // Handle witness dump command
+    if show_witness {
+        if circuit_input.is_none() && zircon_input.is_none() && json_input.is_none() {
+            eprintln!("Error: --circuit, --zircon, or --json is required for --witness");
+            process::exit(1);
+        }
+
+        let program = program_from_inputs(&inputs, verbosity);
+
+        print_witness(&program);
         return;
     }
 
@@ -292,71 +493,13 @@ fn main() {
         }
 
         // Create Program from input format (same logic as prove)
-        let program = if let Some(circuit) = circuit_input {
-            // Direct circuit mode - convert to Program
-            let signals_map = parse_signals_from_cli(&secret_signals, &public_signals);
-
-            // Convert signals to Program format
-            let mut secret_sigs = IndexMap::new();
-            let mut public_sigs = IndexMap::new();
-
-            for (name, sig) in &signals_map {
-                let prog_sig = ProgramSignal {
-                    value: sig.value.clone(),
-                    encoding: sig.encoding,
-                };
-                if sig.public {
-                    public_sigs.insert(name.clone(), prog_sig);
-                } else {
-                    secret_sigs.insert(name.clone(), prog_sig);
-                }
-            }
+        let program = program_from_inputs(&inputs, verbosity);
 
-            // Parse circuit and preprocess statements (split on semicolons)
-            let circuit_statements = match Program::parse_statements(&circuit) {
-                Ok(statements) => statements,
-                Err(e) => {
-                    eprintln!("Error parsing circuit statements: {}", e);
-                    process::exit(1);
-                }
-            };
-
-            // Join multiple --preprocess arguments
-            let preprocess_combined = preprocess_inputs.join(";");
-            let preprocess_statements = if !preprocess_combined.is_empty() {
-                match Program::parse_statements(&preprocess_combined) {
-                    Ok(statements) => statements,
-                    Err(e) => {
-                        eprintln!("Error parsing preprocess statements: {}", e);
-                        process::exit(1);
-                    }
-                }
-            } else {
-                Vec::new()
-            };
-
-            Program {
-                version: zkplex_core::api::PROOF_VERSION,
-                secret: secret_sigs,
-                public: public_sigs,
-                preprocess: preprocess_statements,
-                circuit: circuit_statements,
-            }
-        } else {
-            // File format mode (zircon or json)
-            let (input, format) = if let Some(zircon) = zircon_input.as_ref() {
-                (zircon, "zircon")
-            } else if let Some(json) = json_input.as_ref() {
-                (json, "json")
-            } else {
-                unreachable!()
-            };
-
-            load_program_from_format(input, format, &secret_signals, &public_signals)
-        };
+        // Fall back to the strategy carried by the program
+        let proof_strategy = proof_strategy.or(Some(program.strategy));
 
         // Build circuit for estimation using from_program
-        let circuit_obj = match Circuit::from_program(&program) {
+        let mut circuit_obj = match Circuit::from_program(&program) {
             Ok(c) => c,
             Err(e) => {
                 eprintln!("Error building circuit: {}", e);
@@ -364,6 +507,13 @@ fn main() {
             }
         };
 
+        if let Some(bits) = max_bits {
+            if let Err(e) = circuit_obj.force_max_bits(bits) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+
         // Validate strategy compatibility with circuit operations
         if let Some(strategy_val) = proof_strategy {
             if let Err(e) = validate_strategy_compatibility(&circuit_obj, strategy_val) {
@@ -379,21 +529,15 @@ fn main() {
         // Get estimation (use auto strategy for --estimate without --proof-strategy)
         let estimate = estimate_circuit_requirements_with_strategy(&circuit_obj, proof_strategy);
 
-        if into_json {
-            // Output in JSON format
-            let json_output = serde_json::json!({
-                "complexity": estimate.complexity,
-                "k": estimate.k,
-                "total_rows": estimate.total_rows,
-                "estimated_rows": estimate.estimated_rows,
-                "operation_count": estimate.operation_count,
-                "comparison_count": estimate.comparison_count,
-                "preprocess_count": estimate.preprocess_count,
-                "params_size_bytes": estimate.params_size_bytes,
-                "proof_size_bytes": estimate.proof_size_bytes,
-                "vk_size_bytes": estimate.vk_size_bytes
-            });
-            println!("{}", serde_json::to_string_pretty(&json_output).unwrap());
+        // --into-json is kept as a shorthand for --format json
+        let format = output_format.unwrap_or(if into_json { OutputFormat::Json } else { OutputFormat::Text });
+
+        if format == OutputFormat::Json {
+            // Output in JSON format (same schema as EstimateResponse)
+            println!("{}", serde_json::to_string_pretty(&estimate).unwrap());
+        } else if format == OutputFormat::Csv {
+            // Single CSV row, columns as in EstimateResponse::CSV_HEADER
+            println!("{}", estimate.to_csv_row());
         } else {
             // Output in text format
             let circuit_str = program.circuit.join("; ");
@@ -405,7 +549,9 @@ fn main() {
                 println!("Preprocess: {}", program.preprocess.join("; "));
             }
             println!();
-            println!("Complexity: {}", estimate.complexity);
+            // Complex circuits and above (k > 18) are slow to prove
+            let complexity_style = if estimate.k > 18 { layout::TermStyle::Warning } else { layout::TermStyle::CircuitGates };
+            println!("Complexity: {}", layout::paint(&estimate.complexity, complexity_style, color));
             println!();
             println!("Circuit Parameters:");
             println!("  Required k:        {}", estimate.k);
@@ -418,6 +564,7 @@ fn main() {
             println!("  Arithmetic ops:    {}", estimate.operation_count);
             println!("  Comparisons:       {}", estimate.comparison_count);
             println!("  Preprocessing:     {}", estimate.preprocess_count);
+            println!("  Critical path:     {}", estimate.critical_path_depth);
             println!();
             println!("Resource Requirements (Hardware-Independent):");
             println!("  Params size:       {} bytes ({} KB)",
@@ -476,7 +623,21 @@ fn main() {
         } else if show_estimate {
             print_estimate(&prog);
         } else if show_layout {
-            layout::print_circuit_layout(&prog, proof_strategy);
+            let strategy = proof_strategy.or(Some(prog.strategy));
+            match output_format.unwrap_or_default() {
+                OutputFormat::Text => layout::print_circuit_layout(&prog, strategy, color),
+                OutputFormat::Json => match layout::build_circuit_layout(&prog, strategy) {
+                    Ok(circuit_layout) => println!("{}", serde_json::to_string_pretty(&circuit_layout).unwrap()),
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        process::exit(1);
+                    }
+                },
+                OutputFormat::Csv => {
+                    eprintln!("Error: --format csv is only supported for --estimate");
+                    process::exit(1);
+                }
+            }
         } else {
             // No conversion requested, just validate
             println!("✓ Valid program format");
@@ -490,9 +651,14 @@ fn main() {
 
 /// Read input from file or return the string itself
 ///
+/// If the input is `-`, read the program from stdin.
 /// If the input looks like a file path and the file exists, read its contents.
 /// Otherwise, return the input string as-is.
 fn read_input_or_file(input: &str) -> String {
+    if input == "-" {
+        return read_stdin().to_string();
+    }
+
     let path = Path::new(input);
 
     // If path exists as a file, read it
@@ -510,6 +676,19 @@ fn read_input_or_file(input: &str) -> String {
     }
 }
 
+/// Read all of stdin once; later calls return the same content
+fn read_stdin() -> &'static str {
+    static STDIN: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+    STDIN.get_or_init(|| {
+        let mut content = String::new();
+        if let Err(e) = std::io::Read::read_to_string(&mut std::io::stdin(), &mut content) {
+            eprintln!("Error reading program from stdin: {}", e);
+            process::exit(1);
+        }
+        content
+    })
+}
+
 fn print_usage() {
     println!("zkplex-cli - ZKPlex Program Format Converter and Prover");
     println!();
@@ -517,13 +696,25 @@ fn print_usage() {
     println!("    zkplex-cli [OPTIONS]");
     println!();
     println!("FORMAT CONVERSION OPTIONS:");
-    println!("    -z, --zircon <TEXT|FILE> Input in Zircon format (text or file path)");
-    println!("    -j, --json <TEXT|FILE>   Input in JSON format (text or file path)");
+    println!("    -z, --zircon <TEXT|FILE> Input in Zircon format (text or file path, '-' for stdin)");
+    println!("    -j, --json <TEXT|FILE>   Input in JSON format (text or file path, '-' for stdin)");
+    println!("    --from-stdin            Read the program from stdin (JSON if it starts with '{{', else Zircon)");
     println!("    --into-json             Convert to JSON format");
     println!("    --into-zircon           Convert to Zircon format");
     println!("    -i, --info              Show program information");
     println!("    -e, --estimate          Estimate circuit requirements");
     println!("    -l, --layout            Show circuit layout visualization (ASCII)");
+    println!("    --witness               Print evaluated signal values, including preprocessing outputs");
+    println!("                            and intermediate assignments (reveals secrets!)");
+    println!("    --explain               Print each circuit statement as a parsed expression tree");
+    println!("    --validate              Check the program parses and builds a circuit, without proving;");
+    println!("                            exits non-zero and lists every error otherwise");
+    println!("    --format <FORMAT>       Output format for --estimate/--layout (text|json|csv, default: text)");
+    println!("                            json: stable schema (EstimateResponse / CircuitLayout)");
+    println!("                            csv:  single row for --estimate, columns:");
+    println!("                                  {}", zkplex_core::api::EstimateResponse::CSV_HEADER);
+    println!("    --color <WHEN>          Color text output of --estimate/--layout (auto|always|never, default: auto)");
+    println!("                            auto: only when stdout is a terminal");
     println!();
     println!("PROOF GENERATION/VERIFICATION OPTIONS:");
     println!("    --circuit <TEXT>              Circuit expression (e.g., \"A + B > 100\")");
@@ -533,10 +724,36 @@ fn print_usage() {
     println!("    -p, --public <name:value[:enc]>   Public signal (can be used multiple times)");
     println!("                                  At least one public signal is REQUIRED for proofs");
     println!("                                  Use '?' as value for output signal (computed from circuit)");
-    println!("                                  Encodings: base58/b58, base64/b64, base85/b85, hex, decimal");
+    println!("                                  With --verify, replaces that public value of the proof");
+    println!("                                  Encodings: base58/b58, base64/b64, base64url/b64url, base85/b85, hex, decimal");
     println!("    --prove                       Generate a proof");
     println!("    --verify                      Verify a proof");
     println!("    --proof <FILE>                Proof file (for output or input)");
+    println!("    --expected-circuit-hash <HEX> With --verify, reject proofs whose circuit_hash differs");
+    println!("    --proof-out-dir <DIR>         Write proofs to DIR/<fingerprint>.json, named by circuit fingerprint");
+    println!("                                  (also with --batch)");
+    println!("    --balance-chains              With --prove, rewrite A+B+C+D as (A+B)+(C+D) (also *, AND, OR)");
+    println!("                                  to shorten the critical path; changes the verifying key");
+    println!("    --json-compact                With --prove and --batch, write proofs as single-line JSON");
+    println!("                                  instead of pretty-printed");
+    println!("    --time                        Print VK/PK generation, proof creation and verification");
+    println!("                                  durations to stderr");
+    println!("    --repeat <N>                  With --prove, prove N times and print avg/min/max durations to");
+    println!("                                  stderr; only the last proof is output (keys are reused after");
+    println!("                                  the first run)");
+    println!("    -q, --quiet                   Print only errors to stderr: no progress messages or warnings");
+    println!("                                  (with --prove, --verify and --batch)");
+    println!("    --params <FILE>               Load commitment params for the circuit size from FILE instead of");
+    println!("                                  generating them; FILE is created on first use");
+    println!("    --seed <HEX>                  Seed the proof randomness for reproducible proofs (tests only:");
+    println!("                                  never reuse a seed with a different witness)");
+    println!("    --batch <FILE>                Prove many programs: one Zircon program per line, or a JSON array");
+    println!("                                  of programs. Prints a JSON array of results, or writes one file");
+    println!("                                  per program when --proof names a directory");
+    println!("    --jobs <N>                    With --batch, prove up to N programs in parallel (default: 1)");
+    println!("    --max-bits <BITS>             Force the range table size (8|16|32|64) instead of sizing it from");
+    println!("                                  the witness, so every input gives the same k and verifying key");
+    println!("                                  (also with --estimate and --batch)");
     println!("    --proof-strategy <STRATEGY>   Circuit strategy (auto|boolean|lookup|bitd)");
     println!("                                  auto:    {} - Ops: {}", Strategy::Auto.description(), Strategy::Auto.operations());
     println!("                                  boolean: {} - Ops: {}", Strategy::Boolean.description(), Strategy::Boolean.operations());
@@ -553,6 +770,7 @@ fn print_usage() {
     println!("GENERAL OPTIONS:");
     println!("    -h, --help                    Print help information");
     println!("    -v, --version                 Print version information");
+    println!("    --schema                      Print the JSON Schema of the request, response and program formats");
     println!();
     println!("EXAMPLES:");
     println!();
@@ -587,6 +805,9 @@ fn print_usage() {
     println!("    # With output signal in Zircon format");
     println!("    zkplex-cli --zircon \"1/A:10,B:20/result:?/-A+B\" --prove");
     println!();
+    println!("    # Pipe the program in through stdin");
+    println!("    cat proof.zrc | zkplex-cli --zircon - --prove");
+    println!();
     println!("  4. Base58 Encoding (Solana/Bitcoin addresses):");
     println!("    zkplex-cli --circuit \"myAddress == targetAddress\" \\");
     println!("               --secret myAddress:9aE476sH92Vc7DMC8bZNpe1xNNNy1fNjFpCGvfMuZMwM:base58 \\");
@@ -665,11 +886,126 @@ fn print_estimate(program: &Program) {
     println!("      minimum requirements for proof generation and verification.");
 }
 
+/// Print the parsed expression tree of every circuit statement
+///
+/// Shows how operator precedence grouped each statement, e.g. that `*` binds tighter than `+`.
+fn print_explain(program: &Program) {
+    println!("ZKPlex Circuit Tree");
+    println!("===================");
+
+    for (idx, stmt) in program.circuit.iter().enumerate() {
+        // Assignments are explained by their right-hand side
+        let expr_str = match stmt.split_once("<==") {
+            Some((_, rhs)) => rhs.trim(),
+            None => stmt.trim(),
+        };
+
+        let expr = match zkplex_core::parse_circuit(expr_str) {
+            Ok(expr) => expr,
+            Err(e) => {
+                eprintln!("Error parsing statement '{}': {}", stmt, e);
+                process::exit(1);
+            }
+        };
+
+        println!();
+        println!("[{}] {}", idx + 1, stmt);
+        print!("{}", expr.to_tree_string());
+    }
+}
+
+/// Check a program without proving it, exiting non-zero on any error
+///
+/// Reports every syntax and signal error from `Program::validation_errors`; only a
+/// program without those is built with `Circuit::from_program`, which evaluates it.
+fn validate_program(program: &Program) {
+    let mut errors = program.validation_errors();
+    if errors.is_empty() {
+        // Undeclared signals are only caught here: the circuit builder leaves them unknown
+        if let Err(usage_errors) = program.check_signal_usage() {
+            errors.extend(usage_errors);
+        }
+    }
+    if errors.is_empty() {
+        if let Err(e) = Circuit::from_program(program) {
+            errors.push(format!("Error building circuit: {}", e));
+        }
+    }
+
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("✗ {}", error);
+        }
+        eprintln!("{} error(s) found", errors.len());
+        process::exit(1);
+    }
+
+    println!("✓ Program is valid");
+}
+
+/// Print every evaluated signal value of the circuit
+///
+/// Values are read from `Circuit::signals` after `Circuit::from_program`, so this shows
+/// inputs, preprocessing outputs, and intermediate assignments exactly as the prover sees them.
+fn print_witness(program: &Program) {
+    use ff::PrimeField;
+    use num_bigint::BigUint;
+    use zkplex_core::circuit::Statement;
+
+    let circuit = match Circuit::from_program(program) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error building circuit: {}", e);
+            process::exit(1);
+        }
+    };
+
+    eprintln!("⚠ WARNING: Witness output contains SECRET signal values and values derived from them.");
+    eprintln!("  Do not share or log this output.");
+    eprintln!();
+
+    // Definition order: inputs, preprocessing outputs, then circuit assignments
+    let mut ordered: Vec<(String, &str)> = Vec::new();
+    ordered.extend(program.secret.keys().map(|name| (name.clone(), "secret")));
+    ordered.extend(program.public.keys().map(|name| (name.clone(), "public")));
+    ordered.extend(program.preprocess.iter()
+        .filter_map(|stmt| stmt.split_once("<=="))
+        .map(|(name, _)| (name.trim().to_string(), "preprocess")));
+    ordered.extend(circuit.statements.iter().filter_map(|stmt| match stmt {
+        Statement::Assignment { name, .. } => Some((name.clone(), "intermediate")),
+        Statement::Expression(_) => None,
+    }));
+
+    // Anything else computed (should not happen, but never hide a value)
+    let mut remaining: Vec<&String> = circuit.signals.keys()
+        .filter(|name| !ordered.iter().any(|(n, _)| n == *name))
+        .collect();
+    remaining.sort();
+    ordered.extend(remaining.into_iter().map(|name| (name.clone(), "other")));
+
+    println!("ZKPlex Witness");
+    println!("==============");
+    println!();
+    for (name, source) in &ordered {
+        if let Some(value) = circuit.signals.get(name) {
+            let n = BigUint::from_bytes_le(value.to_repr().as_ref());
+            println!("  {} ({}) = {} (0x{:x})", name, source, n, n);
+        }
+    }
+
+    if let Some(output) = circuit.circuit_output {
+        let n = BigUint::from_bytes_le(output.to_repr().as_ref());
+        println!();
+        println!("  Circuit output = {} (0x{:x})", n, n);
+    }
+}
+
 fn print_program_info(program: &Program) {
     println!("ZKPlex Program Information");
     println!("==========================");
     println!();
     println!("Version: {}", program.version);
+    println!("Strategy: {}", program.strategy);
     println!();
 
     println!("Secret Signals: {}", program.secret.len());
@@ -706,22 +1042,9 @@ fn print_program_info(program: &Program) {
     println!("Zircon format: {}", zircon);
 }
 
-/// Convert encoding string to ValueEncoding enum
-fn string_to_value_encoding(s: &str) -> Result<ValueEncoding, String> {
-    match s.to_lowercase().as_str() {
-        "decimal" => Ok(ValueEncoding::Decimal),
-        "hex" => Ok(ValueEncoding::Hex),
-        "base58" | "b58" => Ok(ValueEncoding::Base58),
-        "base64" | "b64" => Ok(ValueEncoding::Base64),
-        "base85" | "b85" => Ok(ValueEncoding::Base85),
-        "text" | "txt" | "string" | "str" => Ok(ValueEncoding::Text),
-        _ => Err(format!("Unknown encoding: {}. Supported: decimal, hex, base58/b58, base64/b64, base85/b85, text/txt/string/str", s)),
-    }
-}
-
 /// Parse signal in format "name", "name:value" or "name:value:encoding"
 ///
-/// Supported encodings: base58/b58, base64/b64, hex, decimal
+/// Supported encodings: see `ValueEncoding`'s `FromStr` (names and short aliases)
 ///
 /// Examples:
 /// - "output" - output signal with no value (empty string)
@@ -752,7 +1075,7 @@ fn parse_signal(signal_str: &str) -> Result<(String, Option<String>, Option<Valu
             let encoding_str = parts[2];
 
             // Validate and convert encoding
-            let encoding = string_to_value_encoding(encoding_str)?;
+            let encoding: ValueEncoding = encoding_str.parse()?;
 
             // Special case: "?" means output signal (empty value)
             let value = if parts[1] == "?" {
@@ -805,6 +1128,8 @@ fn parse_signals_from_cli(
                     value,
                     encoding,
                     public: false,
+                    bits: None,
+                    constant: false,
                 });
             }
             Err(e) => {
@@ -822,6 +1147,8 @@ fn parse_signals_from_cli(
                     value,
                     encoding,
                     public: true,
+                    bits: None,
+                    constant: false,
                 });
             }
             Err(e) => {
@@ -852,6 +1179,8 @@ fn apply_signal_overrides_cli(
                 value,
                 encoding,
                 public: false,
+                bits: None,
+                constant: false,
             });
         }
     }
@@ -862,6 +1191,8 @@ fn apply_signal_overrides_cli(
                 value,
                 encoding,
                 public: true,
+                bits: None,
+                constant: false,
             });
         }
     }
@@ -873,55 +1204,140 @@ fn apply_signal_overrides_cli(
     }
 }
 
+/// Proof generation settings from the command line
+struct ProveOptions<'a> {
+    max_bits: Option<usize>,
+    seed: Option<&'a str>,
+    /// Params file to load, or to create when it does not exist (`--params`)
+    params_file: Option<&'a str>,
+    show_time: bool,
+    /// Number of times to prove, for timing statistics (`--repeat`)
+    repeat: usize,
+    /// Write the proof as single-line JSON (`--json-compact`)
+    json_compact: bool,
+    /// Re-associate operator chains into balanced trees (`--balance-chains`)
+    balance_chains: bool,
+}
+
 /// Generate a proof from a Program
 fn generate_proof(
     program: &Program,
     output_file: Option<&str>,
     strategy: Option<Strategy>,
+    options: &ProveOptions,
+    verbosity: Verbosity,
 ) {
     use std::fs;
 
     // Validate and display strategy
-    let strategy_value = strategy.unwrap_or(Strategy::Auto);
-    eprintln!("Circuit strategy: {} - {}", strategy_value.as_str(), strategy_value.description());
-
-    // Join circuit statements for display
-    let circuit_str = program.circuit.join("; ");
-    eprintln!("Circuit: {}", circuit_str);
-
-    // Display signals
-    eprintln!("Signals:");
-    for (name, sig) in &program.secret {
-        let value_str = sig.value.as_ref().map(|v| v.as_str()).unwrap_or("");
-        eprintln!("  {} = {} (secret)", name, value_str);
-    }
-    for (name, sig) in &program.public {
-        let value_str = sig.value.as_ref().map(|v| v.as_str()).unwrap_or("");
-        eprintln!("  {} = {} (public)", name, value_str);
+    let strategy_value = strategy.unwrap_or(program.strategy);
+    let info = verbosity.info();
+    if info {
+        eprintln!("Circuit strategy: {} - {}", strategy_value.as_str(), strategy_value.description());
+
+        // Join circuit statements for display
+        let circuit_str = program.circuit.join("; ");
+        eprintln!("Circuit: {}", circuit_str);
+
+        // Display signals
+        eprintln!("Signals:");
+        for (name, sig) in &program.secret {
+            let value_str = sig.value.as_ref().map(|v| v.as_str()).unwrap_or("");
+            eprintln!("  {} = {} (secret)", name, value_str);
+        }
+        for (name, sig) in &program.public {
+            let value_str = sig.value.as_ref().map(|v| v.as_str()).unwrap_or("");
+            eprintln!("  {} = {} (public)", name, value_str);
+        }
     }
 
     // Convert Program to ProveRequest using shared helper
-    let prove_request = zkplex_core::api::program_to_prove_request(program, strategy_value);
+    let mut prove_request = zkplex_core::api::program_to_prove_request(program, strategy_value);
+    prove_request.max_bits = options.max_bits;
+    prove_request.balance_chains = options.balance_chains;
+    if let Some(seed) = options.seed {
+        if info {
+            eprintln!("⚠ WARNING: --seed makes the proof deterministic. Never reuse a seed with a different witness:");
+            eprintln!("  proofs of different secrets from the same randomness can leak them. Use it for tests only.");
+        }
+        prove_request.seed = Some(seed.to_string());
+    }
+
+    // Load saved params; a missing file is created after proving
+    let params = options.params_file
+        .filter(|file| std::path::Path::new(file).exists())
+        .map(|file| {
+            if info {
+                eprintln!("Loading params from {}...", file);
+            }
+            fs::read(file).unwrap_or_else(|e| {
+                eprintln!("Failed to read params file: {}", e);
+                process::exit(1);
+            })
+        });
 
     // Call core prove function
-    eprintln!("Generating proving key...");
-    eprintln!("Creating proof...");
-    let response = match zkplex_core::api::core::prove(prove_request) {
-        Ok(resp) => resp,
-        Err(e) => {
-            eprintln!("Error generating proof: {}", e);
+    if info {
+        eprintln!("Generating proving key...");
+        eprintln!("Creating proof...");
+    }
+    // With --repeat, every run proves the same request and only the last proof is kept.
+    // Runs share a registry, so keys (and loaded params) are only set up by the first one
+    let registry = CircuitRegistry::new();
+    let mut totals = Vec::with_capacity(options.repeat);
+    let mut proof_times = Vec::with_capacity(options.repeat);
+    let mut last = None;
+    for run in 1..=options.repeat {
+        let start = std::time::Instant::now();
+        let run_params = params.as_deref().filter(|_| run == 1);
+        let result = zkplex_core::api::core::prove_with_registry_timed(&registry, prove_request.clone(), run_params);
+        match result {
+            Ok((resp, timings)) => {
+                let total = start.elapsed();
+                if options.repeat > 1 {
+                    let keygen = if timings.pk_generation.is_some() { " (key generation)" } else { "" };
+                    eprintln!("Run {}/{}: {:.3} ms{}", run, options.repeat, total.as_secs_f64() * 1000.0, keygen);
+                }
+                totals.push(total);
+                proof_times.extend(timings.proof_creation);
+                last = Some((resp, timings));
+            }
+            Err(e) => {
+                eprintln!("Error generating proof: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+    let (response, timings) = last.expect("--repeat is at least 1");
+    if options.show_time {
+        print_timings(&timings);
+    }
+    if options.repeat > 1 {
+        eprintln!("Repeat: {} runs", options.repeat);
+        print_duration_stats("Total", &totals);
+        print_duration_stats("Proof creation", &proof_times);
+    }
+
+    // Save the params the proof was just made with
+    let used_params = response.debug.as_ref().and_then(|debug| Some((debug.k, registry.params(debug.k)?)));
+    if let (Some(file), None, Some((k, used))) = (options.params_file, &params, used_params) {
+        if let Err(e) = fs::write(file, zkplex_core::api::params_to_bytes(&used)) {
+            eprintln!("Failed to write params to file: {}", e);
             process::exit(1);
         }
-    };
+        if info {
+            eprintln!("✓ Params (k={}) saved to {}", k, file);
+        }
+    }
 
-    // Output some info about the proof
-    let proof_bytes_len = response.proof.len() * 3 / 4;  // Approximate size (Base85 overhead)
-    eprintln!("Proof size: ~{} bytes (~{:.1} KB)", proof_bytes_len, proof_bytes_len as f64 / 1024.0);
-    eprintln!("Proof encoding: Base85 {} bytes", response.proof.len());
+    if info {
+        // Output some info about the proof
+        let proof_bytes_len = response.proof.len() * 3 / 4;  // Approximate size (Base85 overhead)
+        eprintln!("Proof size: ~{} bytes (~{:.1} KB)", proof_bytes_len, proof_bytes_len as f64 / 1024.0);
+        eprintln!("Proof encoding: Base85 {} bytes", response.proof.len());
 
-    // Show warnings if any
-    if let Some(ref debug) = response.debug {
-        if let Some(ref warnings) = debug.warnings {
+        // Show warnings if any
+        if let Some(warnings) = response.debug.as_ref().and_then(|debug| debug.warnings.as_ref()) {
             for warning in warnings {
                 eprintln!("\n⚠ WARNING: {}", warning);
             }
@@ -929,7 +1345,7 @@ fn generate_proof(
     }
 
     // Serialize response to JSON
-    let json = match serde_json::to_string_pretty(&response) {
+    let json = match proof_json(&response, options.json_compact) {
         Ok(j) => j,
         Err(e) => {
             eprintln!("Failed to serialize response: {}", e);
@@ -943,11 +1359,15 @@ fn generate_proof(
             eprintln!("Failed to write proof to file: {}", e);
             process::exit(1);
         }
-        eprintln!("✓ Proof saved to {}", file);
+        if info {
+            eprintln!("✓ Proof saved to {}", file);
+        }
     } else {
         // Output JSON to stdout (no prefix message to keep it clean for piping)
         println!("{}", json);
-        eprintln!("\n✓ Proof generated successfully");
+        if info {
+            eprintln!("\n✓ Proof generated successfully");
+        }
     }
 }
 
@@ -987,11 +1407,74 @@ fn generate_proof(
 //     Ok(transcript.finalize())
 // }
 
+/// How much progress information the CLI prints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verbosity {
+    /// Only errors and the requested output (`--quiet`)
+    Quiet,
+    /// Progress messages and warnings on stderr (default)
+    Normal,
+}
+
+impl Verbosity {
+    /// Whether informational messages are printed
+    fn info(self) -> bool {
+        self != Verbosity::Quiet
+    }
+}
+
+/// Print phase durations to stderr, skipping phases that did not run
+fn print_timings(timings: &Timings) {
+    let phases = [
+        ("VK generation", timings.vk_generation),
+        ("PK generation", timings.pk_generation),
+        ("Proof creation", timings.proof_creation),
+        ("Verification", timings.verification),
+    ];
+
+    for (label, duration) in phases {
+        if let Some(duration) = duration {
+            eprintln!("Time: {:<15} {:>10.3} ms", label, duration.as_secs_f64() * 1000.0);
+        }
+    }
+}
+
+/// Serialize proof output, pretty-printed unless `compact`
+fn proof_json<T: serde::Serialize>(value: &T, compact: bool) -> serde_json::Result<String> {
+    if compact {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    }
+}
+
+/// Print average, minimum and maximum of repeated durations to stderr
+fn print_duration_stats(label: &str, durations: &[std::time::Duration]) {
+    let (Some(min), Some(max)) = (durations.iter().min(), durations.iter().max()) else {
+        return;
+    };
+    let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+    let avg = durations.iter().map(|d| ms(*d)).sum::<f64>() / durations.len() as f64;
+
+    eprintln!(
+        "Time: {:<15} avg {:>10.3} ms  min {:>10.3} ms  max {:>10.3} ms",
+        label, avg, ms(*min), ms(*max)
+    );
+}
+
 /// Verify a proof
-fn verify_proof(proof_file: &str, into_json: bool) {
+fn verify_proof(
+    proof_file: &str,
+    public_overrides: &[String],
+    expected_circuit_hash: Option<String>,
+    into_json: bool,
+    show_time: bool,
+    verbosity: Verbosity,
+) {
     use std::fs;
 
-    if !into_json {
+    let info = !into_json && verbosity.info();
+    if info {
         println!("Verifying proof from {}...", proof_file);
     }
 
@@ -1031,22 +1514,43 @@ fn verify_proof(proof_file: &str, into_json: bool) {
         }
     };
 
+    // Replace public signal values from --public before verifying
+    let mut public_signals = prove_response.public_signals;
+    if let Err(e) = apply_public_overrides(&mut public_signals, public_overrides, info) {
+        if into_json {
+            let error_json = serde_json::json!({
+                "valid": false,
+                "error": e.to_string()
+            });
+            println!("{}", serde_json::to_string_pretty(&error_json).unwrap());
+        } else {
+            eprintln!("Error: {}", e);
+        }
+        process::exit(1);
+    }
+
     // Create VerifyRequest
     let verify_request = VerifyRequest {
         version: prove_response.version,
         proof: prove_response.proof,
         verify_context: prove_response.verify_context,
-        public_signals: prove_response.public_signals,
+        public_signals,
+        expected_circuit_hash,
     };
 
     // Call core verify function
-    let verify_response = match zkplex_core::api::core::verify(verify_request) {
-        Ok(resp) => resp,
+    let verify_response = match zkplex_core::api::core::verify_timed(verify_request) {
+        Ok((resp, timings)) => {
+            if show_time {
+                print_timings(&timings);
+            }
+            resp
+        }
         Err(e) => {
             if into_json {
                 let error_json = serde_json::json!({
                     "valid": false,
-                    "error": e
+                    "error": e.to_string()
                 });
                 println!("{}", serde_json::to_string_pretty(&error_json).unwrap());
                 process::exit(1);
@@ -1066,20 +1570,336 @@ fn verify_proof(proof_file: &str, into_json: bool) {
     } else {
         if verify_response.valid {
             println!("✓ Proof is VALID");
+            if let Some(output) = &verify_response.output {
+                println!("  Output: {}", output.value);
+            }
         } else {
-            eprintln!("✗ Proof is INVALID{}",
+            let kind = verify_response.error_kind
+                .map(|kind| format!(" ({})", kind.as_str()))
+                .unwrap_or_default();
+            eprintln!("✗ Proof is INVALID{}{}", kind,
                 verify_response.error.map(|e| format!(": {}", e)).unwrap_or_default());
             process::exit(1);
         }
     }
 }
 
+/// Override public signal values of a proof with `--public name:value[:enc]` arguments
+///
+/// Only signals the proof already exposes can be overridden; the encoding is kept
+/// unless the argument gives one.
+fn apply_public_overrides(
+    public_signals: &mut IndexMap<String, PublicSignal>,
+    overrides: &[String],
+    verbose: bool,
+) -> Result<(), String> {
+    for sig_str in overrides {
+        let (name, value, encoding) = parse_signal(sig_str)?;
+        let signal = public_signals.get_mut(&name)
+            .ok_or_else(|| format!("Proof has no public signal '{}'", name))?;
+
+        let value = value.ok_or_else(|| format!("Public signal override '{}' needs a value", name))?;
+        if verbose {
+            println!("Overriding public signal {}: {} -> {}", name, signal.value, value);
+        }
+        signal.value = value;
+        if encoding.is_some() {
+            signal.encoding = encoding;
+        }
+    }
+
+    Ok(())
+}
+
+/// Batch proving settings from the command line
+struct BatchOptions {
+    strategy: Option<Strategy>,
+    max_bits: Option<usize>,
+    jobs: Option<usize>,
+    /// Write results as single-line JSON (`--json-compact`)
+    compact: bool,
+}
+
+/// Prove every program in a batch file
+///
+/// The file holds one Zircon program per line (blank lines and `#` comments are
+/// skipped) or a JSON array of programs. Consecutive programs with the same circuit
+/// share params and proving key. A failing entry is reported in place as an
+/// `ErrorResponse` and does not stop the batch.
+///
+/// Results go to stdout as a JSON array, or to `<dir>/proof_<n>.json` when
+/// `output_dir` is given (errors are then printed to stderr). Exits with status 1
+/// after writing all results if any entry failed.
+///
+/// With `jobs` > 1, entries are proven concurrently on that many threads.
+fn prove_batch(
+    batch_file: &str,
+    output_dir: Option<&str>,
+    fingerprint_dir: Option<&str>,
+    options: &BatchOptions,
+    verbosity: Verbosity,
+) {
+    use zkplex_core::api::ErrorResponse;
+
+    let &BatchOptions { strategy, max_bits, jobs, compact } = options;
+
+    let info = verbosity.info();
+
+    let content = match fs::read_to_string(batch_file) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("Error reading batch file '{}': {}", batch_file, e);
+            process::exit(1);
+        }
+    };
+
+    // Parse entries as (label, program or parse error)
+    let entries: Vec<(String, Result<Program, String>)> = if content.trim_start().starts_with('[') {
+        let items: Vec<serde_json::Value> = match serde_json::from_str(&content) {
+            Ok(items) => items,
+            Err(e) => {
+                eprintln!("Error parsing batch JSON array: {}", e);
+                process::exit(1);
+            }
+        };
+        items.into_iter().enumerate()
+            .map(|(idx, item)| {
+                let program = serde_json::from_value::<Program>(item)
+                    .map_err(|e| format!("Failed to parse program: {}", e));
+                (format!("item {}", idx + 1), program)
+            })
+            .collect()
+    } else {
+        content.lines().enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(idx, line)| (format!("line {}", idx + 1), Program::from_zircon(line.trim())))
+            .collect()
+    };
+
+    if entries.is_empty() {
+        eprintln!("Error: batch file '{}' contains no programs", batch_file);
+        process::exit(1);
+    }
+
+    // Prove all parsed programs in one call so keys are shared between them
+    let requests: Vec<_> = entries.iter()
+        .filter_map(|(_, program)| program.as_ref().ok())
+        .map(|program| {
+            let mut request = zkplex_core::api::program_to_prove_request(program, strategy.unwrap_or(program.strategy));
+            request.max_bits = max_bits;
+            request
+        })
+        .collect();
+    if info {
+        eprintln!("Proving {} of {} programs...", requests.len(), entries.len());
+    }
+    let mut proofs = match jobs {
+        Some(jobs) if jobs > 1 => prove_batch_jobs(requests, jobs),
+        _ => zkplex_core::api::core::prove_batch(requests),
+    }.into_iter();
+
+    let mut results = Vec::new();
+    let mut failed = 0;
+    for (label, program) in &entries {
+        let result = match program {
+            Ok(_) => proofs.next().expect("one result per request").map_err(String::from),
+            Err(e) => Err(e.clone()),
+        };
+        let json = match result {
+            Ok(response) => serde_json::to_value(&response),
+            Err(e) => {
+                failed += 1;
+                eprintln!("✗ {}: {}", label, e);
+                serde_json::to_value(ErrorResponse { error: e, details: Some(label.clone()) })
+            }
+        };
+        results.push(json.expect("responses serialize to JSON"));
+    }
+
+    if let Some(dir) = output_dir {
+        if let Err(e) = fs::create_dir_all(dir) {
+            eprintln!("Failed to create proof directory '{}': {}", dir, e);
+            process::exit(1);
+        }
+        for (idx, result) in results.iter().enumerate() {
+            if result.get("error").is_some() {
+                continue;
+            }
+            let path = Path::new(dir).join(format!("proof_{}.json", idx + 1));
+            if let Err(e) = fs::write(&path, proof_json(result, compact).unwrap()) {
+                eprintln!("Failed to write proof to '{}': {}", path.display(), e);
+                process::exit(1);
+            }
+        }
+        if info {
+            eprintln!("✓ Proofs saved to {}", dir);
+        }
+    } else if let Some(dir) = fingerprint_dir {
+        let mut written = std::collections::HashSet::new();
+        for (idx, ((_, program), result)) in entries.iter().zip(&results).enumerate() {
+            let Ok(program) = program else { continue };
+            if result.get("error").is_some() {
+                continue;
+            }
+            // Later proofs of an already written circuit get their batch position
+            // appended, so witnesses of the same circuit never overwrite each other
+            let mut path = fingerprint_proof_path(dir, program, strategy.unwrap_or(program.strategy), None);
+            if !written.insert(path.clone()) {
+                path = fingerprint_proof_path(dir, program, strategy.unwrap_or(program.strategy), Some(idx + 1));
+            }
+            if let Err(e) = fs::write(&path, proof_json(result, compact).unwrap()) {
+                eprintln!("Failed to write proof to '{}': {}", path.display(), e);
+                process::exit(1);
+            }
+        }
+        if info {
+            eprintln!("✓ Proofs saved to {}", dir);
+        }
+    } else {
+        println!("{}", proof_json(&results, compact).unwrap());
+    }
+
+    if info || failed > 0 {
+        eprintln!("{} succeeded, {} failed", entries.len() - failed, failed);
+    }
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Path `dir/<fingerprint>.json` for a program's proof, or `dir/<fingerprint>-<n>.json`
+/// with a batch position, creating `dir` if needed
+fn fingerprint_proof_path(dir: &str, program: &Program, strategy: Strategy, position: Option<usize>) -> std::path::PathBuf {
+    let fingerprint = match zkplex_core::circuit::program_fingerprint(program, strategy) {
+        Ok(fingerprint) => fingerprint,
+        Err(e) => {
+            eprintln!("Error computing circuit fingerprint: {}", e);
+            process::exit(1);
+        }
+    };
+    if let Err(e) = fs::create_dir_all(dir) {
+        eprintln!("Failed to create proof directory '{}': {}", dir, e);
+        process::exit(1);
+    }
+    match position {
+        Some(n) => Path::new(dir).join(format!("{}-{}.json", fingerprint, n)),
+        None => Path::new(dir).join(format!("{}.json", fingerprint)),
+    }
+}
+
+/// Prove a batch on `jobs` threads
+#[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+fn prove_batch_jobs(requests: Vec<zkplex_core::api::ProveRequest>, jobs: usize) -> Vec<Result<ProveResponse, ZkplexError>> {
+    zkplex_core::api::core::prove_batch_parallel(requests, jobs)
+}
+
+/// Prove a batch serially; parallel proving was not compiled in (or targets wasm32)
+#[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+fn prove_batch_jobs(requests: Vec<zkplex_core::api::ProveRequest>, _jobs: usize) -> Vec<Result<ProveResponse, ZkplexError>> {
+    eprintln!("Warning: built without parallel proving (the 'parallel' feature, native targets only), --jobs is ignored");
+    zkplex_core::api::core::prove_batch(requests)
+}
+
+/// Program sources given on the command line
+struct ProgramInputs {
+    /// `--circuit`
+    circuit: Option<String>,
+    /// `--zircon` (a string, file or `-`)
+    zircon: Option<String>,
+    /// `--json` (a string, file or `-`)
+    json: Option<String>,
+    /// `--preprocess`
+    preprocess: Vec<String>,
+    /// `--secret`
+    secret_signals: Vec<String>,
+    /// `--public`
+    public_signals: Vec<String>,
+    /// `--proof-strategy`
+    strategy: Option<Strategy>,
+}
+
+/// Build a Program from `--circuit` (with signal and preprocess options) or from
+/// `--zircon`/`--json` input with signal overrides applied
+fn program_from_inputs(inputs: &ProgramInputs, verbosity: Verbosity) -> Program {
+    let preprocess_inputs = &inputs.preprocess;
+    let secret_signals = &inputs.secret_signals;
+    let public_signals = &inputs.public_signals;
+    let proof_strategy = inputs.strategy;
+
+    if let Some(circuit) = inputs.circuit.clone() {
+        // Direct circuit mode - convert to Program
+        let signals_map = parse_signals_from_cli(secret_signals, public_signals);
+
+        // Convert signals to Program format
+        let mut secret_sigs = IndexMap::new();
+        let mut public_sigs = IndexMap::new();
+
+        for (name, sig) in &signals_map {
+            let prog_sig = ProgramSignal {
+                value: sig.value.clone(),
+                encoding: sig.encoding,
+                bits: sig.bits,
+                constant: sig.constant,
+            };
+            if sig.public {
+                public_sigs.insert(name.clone(), prog_sig);
+            } else {
+                secret_sigs.insert(name.clone(), prog_sig);
+            }
+        }
+
+        // Parse circuit and preprocess statements (split on semicolons)
+        let circuit_statements = match Program::parse_statements(&circuit) {
+            Ok(statements) => statements,
+            Err(e) => {
+                eprintln!("Error parsing circuit statements: {}", e);
+                process::exit(1);
+            }
+        };
+
+        // Join multiple --preprocess arguments
+        let preprocess_combined = preprocess_inputs.join(";");
+        let preprocess_statements = if !preprocess_combined.is_empty() {
+            match Program::parse_statements(&preprocess_combined) {
+                Ok(statements) => statements,
+                Err(e) => {
+                    eprintln!("Error parsing preprocess statements: {}", e);
+                    process::exit(1);
+                }
+            }
+        } else {
+            Vec::new()
+        };
+
+        Program {
+            version: zkplex_core::api::PROOF_VERSION,
+            secret: secret_sigs,
+            public: public_sigs,
+            preprocess: preprocess_statements,
+            circuit: circuit_statements,
+            strategy: proof_strategy.unwrap_or_default(),
+        }
+    } else {
+        // File format mode (zircon or json)
+        let (input, format) = if let Some(zircon) = &inputs.zircon {
+            (zircon, "zircon")
+        } else if let Some(json) = &inputs.json {
+            (json, "json")
+        } else {
+            unreachable!()
+        };
+
+        load_program_from_format(input, format, secret_signals, public_signals, verbosity)
+    }
+}
+
 /// Helper function to load program from different formats with error handling
 fn load_program_from_format(
     input: &str,
     format: &str,
     secret_signals: &[String],
     public_signals: &[String],
+    verbosity: Verbosity,
 ) -> Program {
     // Read content from file or use as string
     let content = read_input_or_file(input);
@@ -1103,7 +1923,7 @@ fn load_program_from_format(
     };
 
     // Check for privacy warnings BEFORE applying overrides
-    if let Some(warning) = check_program_privacy_warning(&program) {
+    if let Some(warning) = check_program_privacy_warning(&program).filter(|_| verbosity.info()) {
         eprintln!("{}", warning);
         eprintln!();
     }
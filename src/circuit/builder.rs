@@ -4,19 +4,22 @@
 //! into Halo2 circuits that can be proven and verified.
 
 use crate::parser::ast::*;
-use crate::encoding::{parse_value, parse_value_auto};
+use crate::encoding::{parse_value, parse_value_auto, split_array_value, ValueEncoding};
+use super::keccak::{BitwiseConfig, KeccakChip, keccak256_field};
+use super::optimize::simplify;
+use crate::error::ZkplexError;
 use halo2_proofs::{
     circuit::{AssignedCell, Layouter, SimpleFloorPlanner, Value},
     pasta::Fp,
     plonk::{
         Advice, Circuit as PlonkCircuit, Column, ConstraintSystem, Error,
-        Instance, Selector,
+        Fixed, Instance, Selector,
     },
 };
 use halo2_proofs::plonk::gadgets::{
     comparison::{ComparisonConfig, ComparisonChip},
 };
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use ff::{Field, PrimeField};
 use num_bigint::BigUint;
 use num_traits::Num;
@@ -30,10 +33,16 @@ pub struct CircuitConfig {
     /// Instance column for public inputs/outputs
     pub instance: Column<Instance>,
 
+    /// Fixed column holding circuit constants, so they are part of the verifying key
+    pub constants: Column<Fixed>,
+
     /// Selectors for operations
     pub s_add: Selector,
     pub s_mul: Selector,
 
+    /// Bitwise gates (XOR, AND-NOT, NOT) used by the keccak256 gadget
+    pub bitwise: BitwiseConfig,
+
     /// Comparison gadget configuration (None for arithmetic-only circuits)
     /// When None, circuit can only do arithmetic (+, -, *, /) - saves 7 columns!
     pub comparison: Option<ComparisonConfig>,
@@ -73,6 +82,10 @@ impl CircuitConfig {
         let instance = meta.instance_column();
         meta.enable_equality(instance);
 
+        // Constants are copied from a fixed column, binding them to the verifying key
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+
         // Selectors for operations
         let s_add = meta.selector();
         let s_mul = meta.selector();
@@ -104,11 +117,15 @@ impl CircuitConfig {
             vec![s * (a * b - c)]
         });
 
+        let bitwise = BitwiseConfig::configure(meta, &advice);
+
         Self {
             advice,
             instance,
+            constants,
             s_add,
             s_mul,
+            bitwise,
             comparison: Some(comparison), // Minimal comparison support for boolean ops
         }
     }
@@ -143,6 +160,10 @@ impl CircuitConfig {
         let instance = meta.instance_column();
         meta.enable_equality(instance);
 
+        // Constants are copied from a fixed column, binding them to the verifying key
+        let constants = meta.fixed_column();
+        meta.enable_constant(constants);
+
         // Selectors for operations
         let s_add = meta.selector();
         let s_mul = meta.selector();
@@ -174,11 +195,15 @@ impl CircuitConfig {
             vec![s * (a * b - c)]
         });
 
+        let bitwise = BitwiseConfig::configure(meta, &advice);
+
         Self {
             advice,
             instance,
+            constants,
             s_add,
             s_mul,
+            bitwise,
             comparison: Some(comparison), // Wrap in Some
         }
     }
@@ -236,6 +261,25 @@ pub struct Circuit {
     /// This is preserved even in without_witnesses() to ensure consistent lookup table loading
     pub cached_max_bits: Option<usize>,
 
+    /// Declared bit widths of input signals (signal name -> bits)
+    /// Each listed signal is range-checked against its width, and the widths
+    /// size the range table instead of the witness values
+    pub signal_bits: BTreeMap<String, usize>,
+
+    /// Signals wider than 248 bits (signal name -> limb count)
+    /// Equalities on them are compared limb by limb, on `name.limbN` signals
+    pub wide_signals: BTreeMap<String, usize>,
+
+    /// Array signals (signal name -> element count)
+    /// Elements are the signals `name_0`..`name_{n-1}`; `sum(name)` and
+    /// `product(name)` are expanded over them
+    pub array_lengths: BTreeMap<String, usize>,
+
+    /// Signals marked constant (signal name -> value)
+    /// They are substituted into the statements as literals, so they are not
+    /// in `signals` or `public_signal_names` and their values shape the circuit
+    pub constants: BTreeMap<String, Fp>,
+
     /// Range check strategy: "auto", "lookup", or "bitd"
     /// - "auto": Choose based on max_bits (balanced)
     /// - "lookup": Always use lookup tables (faster proving)
@@ -252,6 +296,10 @@ impl Default for Circuit {
             public_signal_names: Vec::new(),
             circuit_output: None,
             cached_max_bits: None,
+            signal_bits: BTreeMap::new(),
+            wide_signals: BTreeMap::new(),
+            array_lengths: BTreeMap::new(),
+            constants: BTreeMap::new(),
             strategy: "auto".to_string(),
         }
     }
@@ -274,6 +322,10 @@ impl Circuit {
             public_signal_names,
             circuit_output,
             cached_max_bits: None,
+            signal_bits: BTreeMap::new(),
+            wide_signals: BTreeMap::new(),
+            array_lengths: BTreeMap::new(),
+            constants: BTreeMap::new(),
             strategy: "auto".to_string(),
         };
 
@@ -291,8 +343,13 @@ impl Circuit {
     /// - Simple arithmetic: +, -, *, /
     /// - Boolean operations: AND, OR, NOT (use is_zero gadget)
     ///
-    /// Returns true only if circuit uses >, <, >=, <=
+    /// Returns true only if circuit uses >, <, >=, <= (or declares signal bit widths)
     pub fn uses_range_check_comparisons(&self) -> bool {
+        // Declared bit widths are enforced with range checks
+        if !self.signal_bits.is_empty() {
+            return true;
+        }
+
         // Check main expression
         if let Some(expr) = &self.expression {
             if Self::expr_uses_ordering_comparisons(expr) {
@@ -397,8 +454,10 @@ impl Circuit {
                     || Self::expr_uses_ordering_comparisons(right)
             }
 
-            Expression::BinaryOp { left, right, .. } => {
-                Self::expr_uses_ordering_comparisons(left)
+            Expression::BinaryOp { op, left, right } => {
+                // Integer division range-checks its remainder and quotient
+                matches!(op, BinaryOperator::IntDiv)
+                    || Self::expr_uses_ordering_comparisons(left)
                     || Self::expr_uses_ordering_comparisons(right)
             }
 
@@ -406,6 +465,16 @@ impl Circuit {
                 Self::expr_uses_ordering_comparisons(operand)
             }
 
+            // sqrt/is_square range-check the witnessed root,
+            // popcount decomposes its argument into max_bits bits,
+            // abs range-checks the magnitude;
+            // keccak256 uses its own fixed-width bit decomposition
+            // and is_bool is a single multiplication gate
+            Expression::Call { function, argument } => {
+                !matches!(function, BuiltinFunction::Keccak256 | BuiltinFunction::IsBool)
+                    || Self::expr_uses_ordering_comparisons(argument)
+            }
+
             Expression::BooleanOp { left, right, .. } => {
                 Self::expr_uses_ordering_comparisons(left)
                     || Self::expr_uses_ordering_comparisons(right)
@@ -415,7 +484,7 @@ impl Circuit {
         }
     }
 
-    /// Recursively check if expression contains boolean operations (AND, OR, NOT)
+    /// Recursively check if expression contains boolean operations (AND, OR, XOR, NAND, NOR, XNOR, NOT)
     fn expr_uses_boolean_ops(expr: &Expression) -> bool {
         match expr {
             Expression::BooleanOp { left, right, .. } => {
@@ -434,6 +503,8 @@ impl Circuit {
 
             Expression::UnaryOp { operand, .. } => Self::expr_uses_boolean_ops(operand),
 
+            Expression::Call { argument, .. } => Self::expr_uses_boolean_ops(argument),
+
             Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => false,
         }
     }
@@ -464,10 +535,45 @@ impl Circuit {
                 Self::expr_uses_equality_comparisons(operand)
             }
 
+            // is_square compares r*r with its argument
+            Expression::Call { function, argument } => {
+                *function == BuiltinFunction::IsSquare
+                    || Self::expr_uses_equality_comparisons(argument)
+            }
+
             Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => false,
         }
     }
 
+    /// Instance values of a public signal, empty if its value is unknown
+    ///
+    /// A wide signal contributes its limbs, least significant first, so the
+    /// instance pins its exact value rather than its reduction modulo the field prime.
+    pub fn public_values(&self, name: &str) -> Vec<Fp> {
+        match self.wide_signals.get(name) {
+            Some(count) => (0..*count)
+                .filter_map(|i| self.signals.get(&limb_name(name, i)).copied())
+                .collect(),
+            None => self.signals.get(name).copied().into_iter().collect(),
+        }
+    }
+
+    /// Total bits the circuit decomposes to range-check limbs
+    ///
+    /// Every limb of a wide signal, and of each field element compared against one.
+    pub fn limb_bits(&self) -> usize {
+        let statement_exprs = self.statements.iter().map(|stmt| match stmt {
+            Statement::Assignment { expression, .. } => expression,
+            Statement::Expression(expression) => expression,
+        });
+        let field_bases: BTreeSet<String> = self.expression.iter().chain(statement_exprs)
+            .flat_map(|expr| field_limb_bases(expr, &self.wide_signals))
+            .collect();
+
+        self.wide_signals.values().sum::<usize>() * LIMB_BITS
+            + field_bases.len() * ((FIELD_LIMBS - 1) * LIMB_BITS + FIELD_TOP_LIMB_BITS)
+    }
+
     /// Get maximum bit size needed for range checks in this circuit
     ///
     /// Returns the cached max_bits value if available (preserved from without_witnesses),
@@ -484,6 +590,32 @@ impl Circuit {
         self.compute_max_range_check_bits()
     }
 
+    /// Force the range table to `bits` instead of sizing it from the witness
+    ///
+    /// The computed size depends on the signal values, so proofs of the same
+    /// circuit with small and large inputs end up with different `k` and keys.
+    /// Forcing the size gives every witness the same circuit shape.
+    ///
+    /// Fails if `bits` is not a supported table size (8, 16, 32 or 64) or if
+    /// an operand of an ordering comparison does not fit it. Circuits without
+    /// ordering comparisons have no range table and are left unchanged.
+    pub fn force_max_bits(&mut self, bits: usize) -> Result<(), String> {
+        if !SUPPORTED_RANGE_BITS.contains(&bits) {
+            return Err(format!("max_bits must be one of 8, 16, 32 or 64, got {}", bits));
+        }
+
+        if let Some(needed) = self.compute_max_range_check_bits() {
+            if needed > bits {
+                return Err(format!(
+                    "comparison operands need {}-bit range checks, which exceeds max_bits={}",
+                    needed, bits
+                ));
+            }
+            self.cached_max_bits = Some(bits);
+        }
+        Ok(())
+    }
+
     /// Compute maximum bit size needed for range checks from signal values
     ///
     /// **OPTIMIZED**: Only analyzes values that are ACTUALLY used in ordering comparisons.
@@ -511,6 +643,11 @@ impl Circuit {
         // Find maximum value across values used in ordering comparisons
         let mut max_bits = 8; // Start with minimum
 
+        // Declared signal widths must fit the range table
+        for bits in self.signal_bits.values() {
+            max_bits = max_bits.max(Self::round_bits(*bits));
+        }
+
         // Analyze main expression
         if let Some(expr) = &self.expression {
             if let Some(bits) = self.max_bits_in_ordering_comparisons(expr) {
@@ -584,21 +721,58 @@ impl Circuit {
                 }
             }
 
-            Expression::BinaryOp { left, right, .. } => {
+            Expression::BinaryOp { op, left, right } => {
                 let left_bits = self.max_bits_in_ordering_comparisons(left);
                 let right_bits = self.max_bits_in_ordering_comparisons(right);
 
-                match (left_bits, right_bits) {
+                let mut max = match (left_bits, right_bits) {
                     (Some(l), Some(r)) => Some(l.max(r)),
                     (Some(bits), None) | (None, Some(bits)) => Some(bits),
                     (None, None) => None,
+                };
+
+                // Integer division checks r < divisor and q <= dividend,
+                // so both operands must fit the range table
+                if *op == BinaryOperator::IntDiv {
+                    let bits = self.evaluate_and_get_bits(left)
+                        .max(self.evaluate_and_get_bits(right));
+                    max = Some(max.map_or(bits, |m| m.max(bits)));
                 }
+
+                max
             }
 
             Expression::UnaryOp { operand, .. } => {
                 self.max_bits_in_ordering_comparisons(operand)
             }
 
+            Expression::Call { function: BuiltinFunction::Keccak256 | BuiltinFunction::IsBool, argument } => {
+                self.max_bits_in_ordering_comparisons(argument)
+            }
+
+            // A negative argument is a huge field element; only its magnitude is range-checked
+            Expression::Call { function: BuiltinFunction::Abs, argument } => {
+                let bits = self.evaluate_and_get_bits(expr);
+                Some(self.max_bits_in_ordering_comparisons(argument).map_or(bits, |b| b.max(bits)))
+            }
+
+            // popcount decomposes its argument into max_bits bits, and evaluation
+            // accepts any argument up to 64 bits, so size by the value itself
+            Expression::Call { function: BuiltinFunction::Popcount, argument } => {
+                let mut bits = self.evaluate_and_get_bits(argument);
+                if let Ok(value) = evaluate_expression(argument, &self.signals) {
+                    bits = bits.max(Self::field_to_bits(&value));
+                }
+                Some(self.max_bits_in_ordering_comparisons(argument).map_or(bits, |b| b.max(bits)))
+            }
+
+            // Every value the square root checks compare (r, r*r, x - r*r, 2r)
+            // fits in the argument's bit size
+            Expression::Call { argument, .. } => {
+                let bits = self.evaluate_and_get_bits(argument);
+                Some(self.max_bits_in_ordering_comparisons(argument).map_or(bits, |b| b.max(bits)))
+            }
+
             Expression::BooleanOp { left, right, .. } => {
                 let left_bits = self.max_bits_in_ordering_comparisons(left);
                 let right_bits = self.max_bits_in_ordering_comparisons(right);
@@ -621,6 +795,13 @@ impl Circuit {
     ///
     /// For example: `key1 == key2` where both are 256-bit returns 0 or 1 (8 bits)
     fn evaluate_and_get_bits(&self, expr: &Expression) -> usize {
+        // A declared width sizes the signal regardless of its value
+        if let Expression::Variable(name) = expr {
+            if let Some(bits) = self.signal_bits.get(name) {
+                return Self::round_bits(*bits);
+            }
+        }
+
         match evaluate_expression(expr, &self.signals) {
             Ok(value) => Self::field_to_bits(&value),
             Err(_) => {
@@ -636,6 +817,10 @@ impl Circuit {
     fn structural_max_bits(&self, expr: &Expression) -> usize {
         match expr {
             Expression::Variable(name) => {
+                if let Some(bits) = self.signal_bits.get(name) {
+                    return Self::round_bits(*bits);
+                }
+
                 // Get value if available
                 self.signals.get(name)
                     .map(|v| Self::field_to_bits(v))
@@ -667,11 +852,27 @@ impl Circuit {
             }
 
             Expression::BooleanOp { .. } => 8, // Boolean ops return 0 or 1 (8 bits)
+
+            Expression::Call { function, argument } => match function {
+                BuiltinFunction::Sqrt => self.structural_max_bits(argument),
+                BuiltinFunction::IsSquare => 8, // Returns 0 or 1
+                BuiltinFunction::IsBool => 8, // Constrained to 0 or 1
+                BuiltinFunction::Popcount => 8, // At most 64
+                BuiltinFunction::Keccak256 => 256, // Full-width digest
+                BuiltinFunction::Abs => self.structural_max_bits(argument),
+                // Expanded into element gates by from_program; unknown length here
+                BuiltinFunction::Sum | BuiltinFunction::Product => 64,
+            },
         }
     }
 
     /// Determine minimum bit size needed for a field element
     fn field_to_bits(value: &Fp) -> usize {
+        Self::round_bits(Self::bit_length(value))
+    }
+
+    /// Exact number of bits in the canonical representation of a field element
+    fn bit_length(value: &Fp) -> usize {
         let bytes = value.to_repr();
 
         // Find the position of the highest non-zero byte
@@ -683,15 +884,18 @@ impl Circuit {
             }
         }
 
-        let bits_needed = match highest_byte_pos {
+        match highest_byte_pos {
             None => 0, // Value is zero
             Some(pos) => {
                 let byte = bytes.as_ref()[pos];
                 let bits_in_byte = 8 - byte.leading_zeros() as usize;
                 pos * 8 + bits_in_byte
             }
-        };
+        }
+    }
 
+    /// Round a bit count up to a supported range table size
+    fn round_bits(bits_needed: usize) -> usize {
         // Round up to next supported size (8, 16, 32, or 64 bits)
         // Values requiring > 64 bits cannot use ordering comparisons
         match bits_needed {
@@ -704,6 +908,46 @@ impl Circuit {
     }
 
 
+    /// Compare the given wide signals limb by limb in `==` / `!=`
+    ///
+    /// `from_program` already does this for every wide value it sees. A verifier,
+    /// which lacks the secret values, calls this with the prover's wide signals
+    /// (from the verify context) to rebuild the same constraints.
+    pub fn split_wide_equalities(&mut self, wide_signals: &BTreeMap<String, usize>) {
+        self.wide_signals.extend(wide_signals.iter().map(|(name, count)| (name.clone(), *count)));
+
+        if let Some(expr) = &self.expression {
+            self.expression = Some(split_wide_equality_expr(expr, &self.wide_signals, &mut self.signals));
+        }
+        for stmt in &mut self.statements {
+            match stmt {
+                Statement::Assignment { expression, .. } | Statement::Expression(expression) => {
+                    *expression = split_wide_equality_expr(expression, &self.wide_signals, &mut self.signals);
+                }
+            }
+        }
+    }
+
+    /// Expand reductions over array signals whose values are not known
+    ///
+    /// `from_program` expands `sum`/`product` over arrays with values; a verifier
+    /// has no secret values, so it restores the lengths from the verify context.
+    pub fn expand_arrays(&mut self, array_lengths: &BTreeMap<String, usize>) -> Result<(), String> {
+        self.array_lengths.extend(array_lengths.iter().map(|(name, length)| (name.clone(), *length)));
+
+        if let Some(expr) = &self.expression {
+            self.expression = Some(expand_array_reductions(expr, &self.array_lengths, &self.signals)?);
+        }
+        for stmt in &mut self.statements {
+            match stmt {
+                Statement::Assignment { expression, .. } | Statement::Expression(expression) => {
+                    *expression = expand_array_reductions(expression, &self.array_lengths, &self.signals)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Build circuit from Zircon Program format
     ///
     /// # Example
@@ -713,13 +957,38 @@ impl Circuit {
     /// let program = Program::from_zircon("1/A:10,B:20/-/sum<==A+B;sum*2")?;
     /// let circuit = Circuit::from_program(&program)?;
     /// ```
-    pub fn from_program(program: &crate::api::Program) -> Result<Self, String> {
+    pub fn from_program(program: &crate::api::Program) -> Result<Self, ZkplexError> {
         use crate::parser::parse_circuit;
 
+        // Hash calls written inline in circuit statements run as preprocessing
+        let (preprocess, circuit_statements) = crate::preprocess::hoist_inline_hashes(&program.preprocess, &program.circuit);
+
         // Convert all input signals (secret + public) to field elements
         let mut signal_values = HashMap::new();
         let mut public_signal_names = Vec::new();
 
+        // Assignments marked `pub name<==expr` are public outputs of the circuit
+        let public_assignments: Vec<String> = circuit_statements.iter()
+            .filter_map(|stmt| match split_public_assignment(stmt) {
+                (true, stmt) => stmt.find("<==").map(|pos| stmt[..pos].trim().to_string()),
+                (false, _) => None,
+            })
+            .collect();
+        let circuit_statements: Vec<String> = circuit_statements.iter()
+            .map(|stmt| split_public_assignment(stmt).1.to_string())
+            .collect();
+
+        // Names assigned by circuit statements (`name<==expr`)
+        let assignment_targets: Vec<&str> = circuit_statements.iter()
+            .filter_map(|stmt| stmt.find("<==").map(|pos| stmt[..pos].trim()))
+            .collect();
+
+        // Signals too wide for the field, compared limb by limb
+        let mut wide_signals = BTreeMap::new();
+
+        // Array signals, expanded into one signal per element
+        let mut array_lengths = BTreeMap::new();
+
         // Process secret signals
         for (name, signal) in &program.secret {
             // Skip if value is None or "?" (placeholder)
@@ -734,14 +1003,22 @@ impl Circuit {
                 None => continue,
             };
 
+            if let Some(elements) = split_array_value(value) {
+                let values = parse_array_elements(name, &elements, signal.encoding)?;
+                insert_array_elements(name, &values, &mut signal_values, &mut wide_signals)?;
+                array_lengths.insert(name.clone(), values.len());
+                continue;
+            }
+
             let bytes = if let Some(encoding) = signal.encoding {
                 parse_value(value, encoding)
-                    .map_err(|e| format!("Failed to parse secret signal '{}': {}", name, e))?
+                    .map_err(|e| ZkplexError::Encoding(format!("Failed to parse secret signal '{}': {}", name, e)))?
             } else {
                 parse_value_auto(value)
-                    .map_err(|e| format!("Failed to parse secret signal '{}': {}", name, e))?
+                    .map_err(|e| ZkplexError::Encoding(format!("Failed to parse secret signal '{}': {}", name, e)))?
             };
 
+            insert_wide_limbs(name, &bytes, &mut signal_values, &mut wide_signals);
             let field_value = bytes_to_field(&bytes)?;
             signal_values.insert(name.clone(), field_value);
         }
@@ -750,32 +1027,106 @@ impl Circuit {
         for (name, signal) in &program.public {
             // Skip output signals (value is None, empty string, or "?")
             let value = match &signal.value {
-                Some(v) => {
-                    if v.is_empty() || v == "?" {
-                        // Empty string or "?" is treated as output signal
-                        continue;
+                Some(v) if !v.is_empty() && v != "?" => v,
+                _ => {
+                    // An output naming an assignment exposes that intermediate result;
+                    // its value is filled in when the statement is evaluated below
+                    if assignment_targets.contains(&name.as_str()) && !public_assignments.contains(name) {
+                        public_signal_names.push(name.clone());
                     }
-                    v
+                    // Any other empty string or "?" is the circuit output signal
+                    continue;
                 }
-                None => continue,  // Output signal, skip
             };
 
+            if let Some(elements) = split_array_value(value) {
+                let values = parse_array_elements(name, &elements, signal.encoding)?;
+                let element_names = insert_array_elements(name, &values, &mut signal_values, &mut wide_signals)?;
+                public_signal_names.extend(element_names);
+                array_lengths.insert(name.clone(), values.len());
+                continue;
+            }
+
             let bytes = if let Some(encoding) = signal.encoding {
                 parse_value(value, encoding)
-                    .map_err(|e| format!("Failed to parse public signal '{}' (value={:?}, encoding={:?}): {}", name, signal.value, signal.encoding, e))?
+                    .map_err(|e| ZkplexError::Encoding(format!("Failed to parse public signal '{}' (value={:?}, encoding={:?}): {}", name, signal.value, signal.encoding, e)))?
             } else {
                 parse_value_auto(value)
-                    .map_err(|e| format!("Failed to parse public signal '{}' (value={:?}): {}", name, signal.value, e))?
+                    .map_err(|e| ZkplexError::Encoding(format!("Failed to parse public signal '{}' (value={:?}): {}", name, signal.value, e)))?
             };
 
+            insert_wide_limbs(name, &bytes, &mut signal_values, &mut wide_signals);
             let field_value = bytes_to_field(&bytes)?;
             signal_values.insert(name.clone(), field_value);
-            public_signal_names.push(name.clone());
+            if !public_assignments.contains(name) {
+                public_signal_names.push(name.clone());
+            }
+        }
+
+        // `pub` assignments follow the declared public signals, in statement order,
+        // whether or not the caller declared them (a verifier passes their values)
+        public_signal_names.extend(public_assignments.iter().cloned());
+
+        // Constant values ship in cleartext with every proof, so they cannot be secret
+        if let Some(name) = program.secret.iter().find(|(_, signal)| signal.constant).map(|(name, _)| name) {
+            return Err(ZkplexError::Invalid(format!(
+                "Secret signal '{}' cannot be constant: constant values are stored in the proof",
+                name
+            )));
+        }
+
+        // Constant signals become literals in the statements below, not inputs
+        let mut constants = BTreeMap::new();
+        for (name, signal) in program.secret.iter().chain(program.public.iter()) {
+            if !signal.constant {
+                continue;
+            }
+            if array_lengths.contains_key(name) || wide_signals.contains_key(name) {
+                return Err(ZkplexError::Invalid(format!(
+                    "Constant signal '{}' must be a single value that fits the field",
+                    name
+                )));
+            }
+            let value = signal_values.get(name).copied().ok_or_else(|| ZkplexError::Invalid(format!(
+                "Constant signal '{}' needs a value",
+                name
+            )))?;
+            constants.insert(name.clone(), value);
+        }
+        public_signal_names.retain(|name| !constants.contains_key(name));
+
+        // Collect declared bit widths and reject values that exceed them
+        let mut signal_bits = BTreeMap::new();
+        for (name, signal) in program.secret.iter().chain(program.public.iter()) {
+            let Some(bits) = signal.bits.filter(|_| !signal.constant) else { continue };
+            if bits == 0 || bits > 64 {
+                return Err(ZkplexError::Invalid(format!(
+                    "Signal '{}' declares {} bits; supported widths are 1 to 64",
+                    name, bits
+                )));
+            }
+            // The width of an array signal applies to each of its elements
+            let names = match array_lengths.get(name) {
+                Some(&length) => (0..length).map(|i| array_element_name(name, i)).collect(),
+                None => vec![name.clone()],
+            };
+            for name in names {
+                if let Some(value) = signal_values.get(&name) {
+                    let actual = Self::bit_length(value);
+                    if actual > bits {
+                        return Err(ZkplexError::Invalid(format!(
+                            "Signal '{}' exceeds its declared {} bits (value needs {} bits)",
+                            name, bits, actual
+                        )));
+                    }
+                }
+                signal_bits.insert(name, bits);
+            }
         }
 
         // Execute preprocessing operations (hashing, encoding, etc.)
         // Outputs become intermediate signals available in circuit
-        if !program.preprocess.is_empty() {
+        if !preprocess.is_empty() {
             // Convert field elements back to bytes for preprocessing
             let mut signal_bytes: HashMap<String, Vec<u8>> = HashMap::new();
 
@@ -789,11 +1140,12 @@ impl Circuit {
             // This may fail during verification when secret signals are not available
             // In that case, we skip preprocessing (the preprocessed values should already be in signal_values from verify context)
             if let Ok(preprocess_outputs) = crate::preprocess::execute_preprocess(
-                &program.preprocess,
+                &preprocess,
                 &signal_bytes,
             ) {
                 // Convert preprocessing outputs back to field elements
                 for (name, output_bytes) in preprocess_outputs {
+                    insert_wide_limbs(&name, &output_bytes, &mut signal_values, &mut wide_signals);
                     let field_value = bytes_to_field(&output_bytes)?;
                     signal_values.insert(name, field_value);
                 }
@@ -802,9 +1154,14 @@ impl Circuit {
             // The preprocessed signal values should be provided in the verify context
         }
 
+        // Preprocessing could read constants; the statements get them as literals
+        for name in constants.keys() {
+            signal_values.remove(name);
+        }
+
         // Parse circuit statements
         let mut statements = Vec::new();
-        for circuit_str in &program.circuit {
+        for circuit_str in &circuit_statements {
             // Check if this is an assignment (contains <==)
             if let Some(pos) = circuit_str.find("<==") {
                 let name = circuit_str[..pos].trim().to_string();
@@ -812,14 +1169,27 @@ impl Circuit {
 
                 // Parse the expression
                 let expression = parse_circuit(expr_str)
-                    .map_err(|e| format!("Failed to parse assignment expression '{}': {}", expr_str, e))?;
+                    .map_err(|e| ZkplexError::Parse(format!("Failed to parse assignment expression '{}': {}", expr_str, e)))?;
+                let expression = expand_array_reductions(&expression, &array_lengths, &signal_values)?;
+                let expression = substitute_constants(&expression, &constants);
+                let expression = split_wide_equality_expr(&simplify(&expression), &wide_signals, &mut signal_values);
+
+                // Reject a zero divisor here rather than failing synthesis with an opaque error
+                check_expression_depth(&expression).map_err(ZkplexError::Parse)?;
+                check_division_by_zero(&expression, &signal_values).map_err(ZkplexError::DivisionByZero)?;
+                check_comparison_overflow(&expression, &signal_values).map_err(ZkplexError::Invalid)?;
 
                 // Evaluate the expression to get the intermediate signal value
                 // This may fail during verification when secret signals are not available
                 // In that case, we skip storing the value but still add the statement
-                if let Ok(value) = evaluate_expression(&expression, &signal_values) {
-                    // Store the intermediate signal value for use in subsequent statements
-                    signal_values.insert(name.clone(), value);
+                // A public value supplied by the caller (a verifier checking an exposed
+                // intermediate) is kept, so the proof is checked against that claim
+                let supplied_public = public_signal_names.contains(&name) && signal_values.contains_key(&name);
+                if !supplied_public {
+                    if let Ok(value) = evaluate_expression(&expression, &signal_values) {
+                        // Store the intermediate signal value for use in subsequent statements
+                        signal_values.insert(name.clone(), value);
+                    }
                 }
 
                 statements.push(Statement::Assignment {
@@ -829,7 +1199,14 @@ impl Circuit {
             } else {
                 // Regular expression
                 let expression = parse_circuit(circuit_str)
-                    .map_err(|e| format!("Failed to parse expression '{}': {}", circuit_str, e))?;
+                    .map_err(|e| ZkplexError::Parse(format!("Failed to parse expression '{}': {}", circuit_str, e)))?;
+                let expression = expand_array_reductions(&expression, &array_lengths, &signal_values)?;
+                let expression = substitute_constants(&expression, &constants);
+                let expression = split_wide_equality_expr(&simplify(&expression), &wide_signals, &mut signal_values);
+
+                check_expression_depth(&expression).map_err(ZkplexError::Parse)?;
+                check_division_by_zero(&expression, &signal_values).map_err(ZkplexError::DivisionByZero)?;
+                check_comparison_overflow(&expression, &signal_values).map_err(ZkplexError::Invalid)?;
 
                 statements.push(Statement::Expression(expression));
             }
@@ -852,6 +1229,10 @@ impl Circuit {
             public_signal_names,
             circuit_output,
             cached_max_bits: None,
+            signal_bits,
+            wide_signals,
+            array_lengths,
+            constants,
             strategy: "auto".to_string(),
         };
 
@@ -862,6 +1243,130 @@ impl Circuit {
     }
 }
 
+/// Fluent builder for circuits constructed from [`Expression`] values
+///
+/// Produces the same [`Circuit`] as [`Circuit::from_program`] without going
+/// through Zircon or JSON: signals are given as field elements and statements
+/// as expressions. Statements are simplified and checked like parsed ones, and
+/// assignments are evaluated in order so later statements can use them.
+///
+/// # Example
+///
+/// ```ignore
+/// // Circuit: (A + B) > C
+/// let circuit = CircuitBuilder::new()
+///     .add_secret("A", Fp::from(10))
+///     .add_secret("B", Fp::from(20))
+///     .add_public("C", Fp::from(25))
+///     .statement(Expression::compare(
+///         ComparisonOperator::Greater,
+///         Expression::add(Expression::var("A"), Expression::var("B")),
+///         Expression::var("C"),
+///     ))
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBuilder {
+    signals: HashMap<String, Fp>,
+    public_signal_names: Vec<String>,
+    statements: Vec<Statement>,
+    /// First signal added twice, reported by `build()`
+    duplicate: Option<String>,
+}
+
+impl CircuitBuilder {
+    /// Create an empty builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a secret (witness) signal
+    pub fn add_secret(mut self, name: impl Into<String>, value: Fp) -> Self {
+        self.add_signal(name.into(), value);
+        self
+    }
+
+    /// Add a public signal; public signals become instance inputs in the order added
+    pub fn add_public(mut self, name: impl Into<String>, value: Fp) -> Self {
+        let name = name.into();
+        self.add_signal(name.clone(), value);
+        self.public_signal_names.push(name);
+        self
+    }
+
+    /// Add an assignment `name <== expression`
+    pub fn assign(mut self, name: impl Into<String>, expression: Expression) -> Self {
+        self.statements.push(Statement::Assignment { name: name.into(), expression });
+        self
+    }
+
+    /// Add a statement; the last statement is the circuit output
+    pub fn statement(mut self, expression: Expression) -> Self {
+        self.statements.push(Statement::Expression(expression));
+        self
+    }
+
+    fn add_signal(&mut self, name: String, value: Fp) {
+        if self.signals.insert(name.clone(), value).is_some() && self.duplicate.is_none() {
+            self.duplicate = Some(name);
+        }
+    }
+
+    /// Build the circuit
+    pub fn build(self) -> Result<Circuit, ZkplexError> {
+        if let Some(name) = self.duplicate {
+            return Err(ZkplexError::Invalid(format!("Signal '{}' is added more than once", name)));
+        }
+        if self.statements.is_empty() {
+            return Err(ZkplexError::Invalid("Circuit has no statements".to_string()));
+        }
+
+        let mut signals = self.signals;
+        let mut statements = Vec::with_capacity(self.statements.len());
+        for statement in self.statements {
+            let statement = match statement {
+                Statement::Assignment { name, expression } => {
+                    let expression = simplify(&expression);
+                    check_expression_depth(&expression).map_err(ZkplexError::Parse)?;
+                    check_division_by_zero(&expression, &signals).map_err(ZkplexError::DivisionByZero)?;
+                    check_comparison_overflow(&expression, &signals).map_err(ZkplexError::Invalid)?;
+
+                    // Unknown when a signal is missing, as for a verifier without the secrets
+                    if let Ok(value) = evaluate_expression(&expression, &signals) {
+                        signals.insert(name.clone(), value);
+                    }
+                    Statement::Assignment { name, expression }
+                }
+                Statement::Expression(expression) => {
+                    let expression = simplify(&expression);
+                    check_expression_depth(&expression).map_err(ZkplexError::Parse)?;
+                    check_division_by_zero(&expression, &signals).map_err(ZkplexError::DivisionByZero)?;
+                    check_comparison_overflow(&expression, &signals).map_err(ZkplexError::Invalid)?;
+                    Statement::Expression(expression)
+                }
+            };
+            statements.push(statement);
+        }
+
+        let circuit_output = statements.last().and_then(|stmt| match stmt {
+            Statement::Expression(expression) | Statement::Assignment { expression, .. } => {
+                evaluate_expression(expression, &signals).ok()
+            }
+        });
+
+        let mut circuit = Circuit {
+            statements,
+            signals,
+            public_signal_names: self.public_signal_names,
+            circuit_output,
+            ..Circuit::default()
+        };
+        circuit.cached_max_bits = circuit.compute_max_range_check_bits();
+
+        Ok(circuit)
+    }
+}
+
 // Wrapper types for different strategies
 // Each type implements Circuit with its own configuration
 
@@ -995,6 +1500,10 @@ impl PlonkCircuit<Fp> for Circuit {
             public_signal_names: self.public_signal_names.clone(),
             circuit_output: None,  // Clear output (computed from witnesses)
             cached_max_bits: self.cached_max_bits,  // Preserve cached value!
+            signal_bits: self.signal_bits.clone(),
+            wide_signals: self.wide_signals.clone(),
+            array_lengths: self.array_lengths.clone(),
+            constants: self.constants.clone(),
             strategy: self.strategy.clone(),
         }
     }
@@ -1034,12 +1543,54 @@ impl PlonkCircuit<Fp> for Circuit {
         // Create chip for circuit operations with the correct bit size
         let chip = CircuitChip::new(config.clone(), max_bits);
 
+        // Cells computed by assignment statements, by target name
+        let mut intermediates = HashMap::new();
+
+        // Range-check signals with declared bit widths. The checked cell is
+        // the one every use of the signal loads, so the width binds the witness.
+        for (name, bits) in &self.signal_bits {
+            let value = self.signals.get(name).copied()
+                .map(Value::known)
+                .unwrap_or(Value::unknown());
+            let cell = chip.assign_advice(
+                layouter.namespace(|| format!("bits_{}", name)),
+                config.advice[0],
+                value,
+            )?;
+            chip.assert_max_bits(layouter.namespace(|| format!("assert_bits_{}", name)), &cell, *bits)?;
+            intermediates.insert(name.clone(), cell);
+        }
+
+        // Wide signals are carried as their range-checked limbs: limb-wise
+        // equalities load the limb cells, any other use loads their recomposition
+        for (name, count) in &self.wide_signals {
+            let (limbs, value) = chip.assign_limbs(
+                layouter.namespace(|| format!("limbs_{}", name)),
+                name,
+                *count,
+                LIMB_BITS,
+                &self.signals,
+            )?;
+            for (i, limb) in limbs.into_iter().enumerate() {
+                intermediates.insert(limb_name(name, i), limb);
+            }
+            intermediates.insert(name.clone(), value);
+        }
+
         // Synthesize main expression if present and capture result
         let circuit_result = if let Some(expr) = &self.expression {
+            chip.bind_field_limbs(
+                layouter.namespace(|| "circuit_limbs"),
+                expr,
+                &self.wide_signals,
+                &self.signals,
+                &mut intermediates,
+            )?;
             Some(chip.synthesize_expr(
                 layouter.namespace(|| "circuit"),
                 expr,
                 &self.signals,
+                &intermediates,
             )?)
         } else {
             None
@@ -1048,19 +1599,35 @@ impl PlonkCircuit<Fp> for Circuit {
         // Synthesize statements if present and capture last result
         let mut last_stmt_result = None;
         for (idx, stmt) in self.statements.iter().enumerate() {
+            let expression = match stmt {
+                Statement::Assignment { expression, .. } => expression,
+                Statement::Expression(expression) => expression,
+            };
+            chip.bind_field_limbs(
+                layouter.namespace(|| format!("limbs_{}", idx)),
+                expression,
+                &self.wide_signals,
+                &self.signals,
+                &mut intermediates,
+            )?;
+
             match stmt {
                 Statement::Assignment { name, expression } => {
-                    last_stmt_result = Some(chip.synthesize_expr(
+                    let cell = chip.synthesize_expr(
                         layouter.namespace(|| format!("assign_{}", name)),
                         expression,
                         &self.signals,
-                    )?);
+                        &intermediates,
+                    )?;
+                    intermediates.insert(name.clone(), cell.clone());
+                    last_stmt_result = Some(cell);
                 }
                 Statement::Expression(expression) => {
                     last_stmt_result = Some(chip.synthesize_expr(
                         layouter.namespace(|| format!("expr_{}", idx)),
                         expression,
                         &self.signals,
+                        &intermediates,
                     )?);
                 }
             }
@@ -1068,7 +1635,26 @@ impl PlonkCircuit<Fp> for Circuit {
 
         // Constrain public signals to instance column
         // Public signals are passed as instance inputs during proof creation/verification
-        for (idx, signal_name) in self.public_signal_names.iter().enumerate() {
+        let mut idx = 0;
+        for signal_name in &self.public_signal_names {
+            // A wide public signal exposes its limbs, which pin the exact value
+            // rather than its reduction modulo the field prime
+            if let Some(count) = self.wide_signals.get(signal_name) {
+                for i in 0..*count {
+                    let limb = &intermediates[&limb_name(signal_name, i)];
+                    layouter.constrain_instance(limb.cell(), config.instance, idx)?;
+                    idx += 1;
+                }
+                continue;
+            }
+
+            // A public intermediate exposes the cell its assignment computed
+            if let Some(cell) = intermediates.get(signal_name) {
+                layouter.constrain_instance(cell.cell(), config.instance, idx)?;
+                idx += 1;
+                continue;
+            }
+
             // Get signal value if available (will be None for without_witnesses)
             let signal_value = self.signals.get(signal_name).copied();
 
@@ -1078,14 +1664,14 @@ impl PlonkCircuit<Fp> for Circuit {
                 signal_value.map(Value::known).unwrap_or(Value::unknown()),
             )?;
             layouter.constrain_instance(cell.cell(), config.instance, idx)?;
+            idx += 1;
         }
 
         // Constrain circuit output as additional public signal (last instance)
         // This ensures the proof commits to the actual circuit result
         let final_result = circuit_result.or(last_stmt_result);
         if let Some(result_cell) = final_result {
-            let output_idx = self.public_signal_names.len();
-            layouter.constrain_instance(result_cell.cell(), config.instance, output_idx)?;
+            layouter.constrain_instance(result_cell.cell(), config.instance, idx)?;
         }
 
         Ok(())
@@ -1119,6 +1705,24 @@ impl CircuitChip {
         )
     }
 
+    /// Assign a circuit constant
+    ///
+    /// The cell is constrained equal to a fixed cell, so the prover cannot
+    /// change the value and verifying keys differ when constants differ.
+    fn assign_constant(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        column: Column<Advice>,
+        value: Fp,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        layouter.assign_region(
+            || "assign constant",
+            |mut region| {
+                region.assign_advice_from_constant(|| "constant", column, 0, value)
+            },
+        )
+    }
+
     /// Add two values
     fn add(
         &self,
@@ -1217,75 +1821,446 @@ impl CircuitChip {
         )
     }
 
-    /// Compare two values using range checks and is_zero gadget
-    ///
-    /// This uses the ComparisonChip which provides cryptographically sound comparisons:
-    /// - Equality/Inequality: Uses is_zero gadget with full constraints
-    /// - Greater/Less: Uses range checks + is_zero
-    /// - GreaterEqual/LessEqual: Uses only range checks
+    /// Integer division: returns q = floor(a / b)
     ///
-    /// All comparisons return 1 (true) or 0 (false).
-    fn compare(
+    /// Witnesses quotient and remainder (unknown on division by zero) and
+    /// constrains them with [`Self::int_div_with_witness`].
+    fn int_div(
         &self,
-        mut layouter: impl Layouter<Fp>,
-        op: &ComparisonOperator,
+        layouter: impl Layouter<Fp>,
         a: &AssignedCell<Fp, Fp>,
         b: &AssignedCell<Fp, Fp>,
     ) -> Result<AssignedCell<Fp, Fp>, Error> {
-        // Get comparison config (should always be Some if circuit uses comparisons)
-        let comparison_config = self.config.comparison.as_ref()
-            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use comparisons
-
-        // Create comparison chip
-        let chip = ComparisonChip::new(comparison_config.clone());
-
-        // Use the bit size that was determined during circuit construction
-        // This ensures we use the correct lookup table (8, 16, 32, or 64 bits)
-        let bits = self.max_bits;
-
-        match op {
-            ComparisonOperator::Equal => {
-                chip.is_equal(layouter.namespace(|| "is_equal"), a, b)
-            }
-            ComparisonOperator::NotEqual => {
-                chip.is_not_equal(layouter.namespace(|| "is_not_equal"), a, b)
-            }
-            ComparisonOperator::Greater => {
-                chip.is_greater(layouter.namespace(|| "is_greater"), a, b, bits)
-            }
-            ComparisonOperator::Less => {
-                chip.is_less(layouter.namespace(|| "is_less"), a, b, bits)
-            }
-            ComparisonOperator::GreaterEqual => {
-                chip.is_greater_or_equal(layouter.namespace(|| "is_greater_or_equal"), a, b, bits)
-            }
-            ComparisonOperator::LessEqual => {
-                chip.is_less_or_equal(layouter.namespace(|| "is_less_or_equal"), a, b, bits)
+        let qr = a.value().zip(b.value()).and_then(|(a, b)| {
+            match field_div_rem(a, b) {
+                Some((q, r)) => Value::known((q, r)),
+                None => Value::unknown(),
             }
-        }
+        });
+        self.int_div_with_witness(layouter, a, b, qr)
     }
 
-    /// Boolean AND: both values non-zero -> 1, else 0
+    /// Integer division from a witnessed quotient q and remainder r
     ///
-    /// Uses is_zero gadget to convert to bool, then multiplies with constraint
-    fn boolean_and(
+    /// Enforces:
+    /// - a = q * b + r (mul and add gates, copy constraint back to a)
+    /// - q, r <= 2^max_bits - 1, since the comparisons below only range-check
+    ///   differences and would accept a wrapped r such as p - 1
+    /// - r < b (range check), which also rejects b == 0
+    /// - q <= a (range check), so q * b + r cannot wrap around the field modulus
+    fn int_div_with_witness(
         &self,
         mut layouter: impl Layouter<Fp>,
         a: &AssignedCell<Fp, Fp>,
         b: &AssignedCell<Fp, Fp>,
+        qr: Value<(Fp, Fp)>,
     ) -> Result<AssignedCell<Fp, Fp>, Error> {
-        // Get comparison config (should always be Some if circuit uses boolean ops)
         let comparison_config = self.config.comparison.as_ref()
-            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use boolean ops
-
+            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use range checks
         let chip = ComparisonChip::new(comparison_config.clone());
 
-        // Convert a to boolean: is_not_zero(a) = NOT(is_zero(a))
-        let a_is_zero = chip.is_zero(layouter.namespace(|| "a_is_zero"), a)?;
-        let a_bool = chip.is_zero(layouter.namespace(|| "a_to_bool"), &a_is_zero)?;
+        let q = self.assign_advice(
+            layouter.namespace(|| "quotient"),
+            self.config.advice[0],
+            qr.map(|(q, _)| q),
+        )?;
+        let r = self.assign_advice(
+            layouter.namespace(|| "remainder"),
+            self.config.advice[0],
+            qr.map(|(_, r)| r),
+        )?;
+
+        // a = q * b + r
+        let qb = self.mul(layouter.namespace(|| "q_mul_b"), &q, b)?;
+        let sum = self.add(layouter.namespace(|| "qb_add_r"), &qb, &r)?;
+        layouter.assign_region(
+            || "int_div_recompose",
+            |mut region| region.constrain_equal(sum.cell(), a.cell()),
+        )?;
 
-        // Convert b to boolean: is_not_zero(b) = NOT(is_zero(b))
-        let b_is_zero = chip.is_zero(layouter.namespace(|| "b_is_zero"), b)?;
+        // 0 <= q, r < 2^max_bits
+        self.assert_at_most(layouter.namespace(|| "q_in_range"), &q, max_value(self.max_bits))?;
+        self.assert_at_most(layouter.namespace(|| "r_in_range"), &r, max_value(self.max_bits))?;
+
+        // r < b and q <= a
+        let r_lt_b = chip.is_less(layouter.namespace(|| "r_lt_b"), &r, b, self.max_bits)?;
+        self.assert_true(layouter.namespace(|| "assert_r_lt_b"), &r_lt_b)?;
+
+        let q_le_a = chip.is_less_or_equal(layouter.namespace(|| "q_le_a"), &q, a, self.max_bits)?;
+        self.assert_true(layouter.namespace(|| "assert_q_le_a"), &q_le_a)?;
+
+        Ok(q)
+    }
+
+    /// Floor integer square root with constraints
+    ///
+    /// Witnesses r and proves r*r <= x < (r+1)*(r+1), using the equivalent
+    /// x - r*r <= 2r for the upper bound so every compared value fits the
+    /// argument's bit size. Also checks r <= x so r itself is range-checked.
+    ///
+    /// Returns (r, r*r); `is_square` reuses r*r.
+    fn int_sqrt(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+    ) -> Result<(AssignedCell<Fp, Fp>, AssignedCell<Fp, Fp>), Error> {
+        let comparison_config = self.config.comparison.as_ref()
+            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use range checks
+        let chip = ComparisonChip::new(comparison_config.clone());
+
+        let r = self.assign_advice(
+            layouter.namespace(|| "root"),
+            self.config.advice[0],
+            x.value().map(field_isqrt),
+        )?;
+
+        let r_le_x = chip.is_less_or_equal(layouter.namespace(|| "r_le_x"), &r, x, self.max_bits)?;
+        self.assert_true(layouter.namespace(|| "assert_r_le_x"), &r_le_x)?;
+
+        // r*r <= x
+        let r_squared = self.mul(layouter.namespace(|| "r_mul_r"), &r, &r)?;
+        let lower = chip.is_less_or_equal(layouter.namespace(|| "r2_le_x"), &r_squared, x, self.max_bits)?;
+        self.assert_true(layouter.namespace(|| "assert_r2_le_x"), &lower)?;
+
+        // x < (r+1)^2  <=>  x - r*r <= 2r
+        let gap = self.sub(layouter.namespace(|| "x_sub_r2"), x, &r_squared)?;
+        let two_r = self.add(layouter.namespace(|| "two_r"), &r, &r)?;
+        let upper = chip.is_less_or_equal(layouter.namespace(|| "gap_le_2r"), &gap, &two_r, self.max_bits)?;
+        self.assert_true(layouter.namespace(|| "assert_gap_le_2r"), &upper)?;
+
+        Ok((r, r_squared))
+    }
+
+    /// Signed absolute value with constraints
+    ///
+    /// Witnesses a sign bit s (b * b == b) and muxes m = x - 2 * s * x, i.e. x
+    /// when s = 0 and -x when s = 1, then range-checks m <= 2^max_bits - 1.
+    /// Only one of x and -x fits in max_bits (unless x = 0), so the prover
+    /// cannot pick the wrong branch.
+    fn abs(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        let comparison_config = self.config.comparison.as_ref()
+            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use range checks
+        let chip = ComparisonChip::new(comparison_config.clone());
+
+        // Sign bit: 1 if x reads as negative
+        let sign = self.assign_advice(
+            layouter.namespace(|| "sign"),
+            self.config.advice[0],
+            x.value().map(|v| if field_abs(v) == *v { Fp::zero() } else { Fp::one() }),
+        )?;
+        let sign_squared = self.mul(layouter.namespace(|| "sign_squared"), &sign, &sign)?;
+        layouter.assign_region(
+            || "sign_boolean",
+            |mut region| region.constrain_equal(sign_squared.cell(), sign.cell()),
+        )?;
+
+        // m = x - 2 * s * x
+        let sx = self.mul(layouter.namespace(|| "sign_mul_x"), &sign, x)?;
+        let two_sx = self.add(layouter.namespace(|| "double_sign_mul_x"), &sx, &sx)?;
+        let magnitude = self.sub(layouter.namespace(|| "magnitude"), x, &two_sx)?;
+
+        // m < 2^max_bits
+        let bound = self.assign_constant(
+            layouter.namespace(|| "magnitude_bound"),
+            self.config.advice[1],
+            Fp::from(2).pow_vartime([self.max_bits as u64]) - Fp::one(),
+        )?;
+        let in_range = chip.is_less_or_equal(layouter.namespace(|| "magnitude_in_range"), &magnitude, &bound, self.max_bits)?;
+        self.assert_true(layouter.namespace(|| "assert_magnitude_in_range"), &in_range)?;
+
+        Ok(magnitude)
+    }
+
+    /// Constrain x <= bound for a constant bound
+    ///
+    /// The less-or-equal gadget range-checks `bound - x` to max_bits, so this
+    /// only proves x lies in (bound - 2^max_bits, bound] modulo the field.
+    /// With bound = 2^max_bits - 1 that is exactly [0, 2^max_bits).
+    fn assert_at_most(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+        bound: Fp,
+    ) -> Result<(), Error> {
+        let comparison_config = self.config.comparison.as_ref()
+            .ok_or(Error::Synthesis)?;
+        let chip = ComparisonChip::new(comparison_config.clone());
+
+        let bound = self.assign_constant(
+            layouter.namespace(|| "bound"),
+            self.config.advice[1],
+            bound,
+        )?;
+        let in_range = chip.is_less_or_equal(layouter.namespace(|| "le_bound"), x, &bound, self.max_bits)?;
+        self.assert_true(layouter.namespace(|| "assert_le_bound"), &in_range)
+    }
+
+    /// Constrain x < 2^bits, for bits <= max_bits
+    ///
+    /// x <= 2^max_bits - 1 first pins x to [0, 2^max_bits); on its own,
+    /// x <= 2^bits - 1 would also accept wrapped "negative" values such as
+    /// p - 1 whenever bits < max_bits.
+    fn assert_max_bits(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+        bits: usize,
+    ) -> Result<(), Error> {
+        if bits < self.max_bits {
+            self.assert_at_most(layouter.namespace(|| "table_bound"), x, max_value(self.max_bits))?;
+        }
+        self.assert_at_most(layouter.namespace(|| "bits_bound"), x, max_value(bits))
+    }
+
+    /// Count set bits with constraints
+    ///
+    /// Decomposes x into `max_bits` constrained bits; the count is their sum.
+    fn popcount(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        let bits = self.decompose_bits(layouter.namespace(|| "decompose"), x, self.max_bits)?;
+
+        // Sum of bits
+        let mut count = bits[0].clone();
+        for (i, bit) in bits.iter().enumerate().skip(1) {
+            count = self.add(layouter.namespace(|| format!("count_{}", i)), &count, bit)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Decompose x into `n` bits, least significant first
+    ///
+    /// Each witnessed bit is constrained with b * b == b, and the bits are
+    /// recomposed (acc = 2 * acc + b, most significant first) and constrained
+    /// equal to x, which also proves x < 2^n.
+    fn decompose_bits(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+        n: usize,
+    ) -> Result<Vec<AssignedCell<Fp, Fp>>, Error> {
+        let mut bits = Vec::with_capacity(n);
+        for i in 0..n {
+            let bit = self.assign_advice(
+                layouter.namespace(|| format!("bit_{}", i)),
+                self.config.advice[0],
+                x.value().map(|v| Fp::from(((v.to_repr().as_ref()[i / 8] >> (i % 8)) & 1) as u64)),
+            )?;
+
+            // b * b == b  <=>  b is 0 or 1
+            let squared = self.mul(layouter.namespace(|| format!("bit_{}_squared", i)), &bit, &bit)?;
+            layouter.assign_region(
+                || format!("bit_{}_boolean", i),
+                |mut region| region.constrain_equal(squared.cell(), bit.cell()),
+            )?;
+
+            bits.push(bit);
+        }
+
+        // Recompose and bind to x
+        let mut acc = bits[n - 1].clone();
+        for (i, bit) in bits.iter().enumerate().rev().skip(1) {
+            let doubled = self.add(layouter.namespace(|| format!("double_{}", i)), &acc, &acc)?;
+            acc = self.add(layouter.namespace(|| format!("recompose_{}", i)), &doubled, bit)?;
+        }
+        layouter.assign_region(
+            || "bits_recompose",
+            |mut region| region.constrain_equal(acc.cell(), x.cell()),
+        )?;
+
+        Ok(bits)
+    }
+
+    /// Assign the limbs of `name` and recompose them into one cell
+    ///
+    /// The limbs come from the `name.limbN` signals. Each is range-checked to
+    /// `LIMB_BITS` bits (the most significant one to `top_bits`) by bit
+    /// decomposition, so they are the exact value and not one congruent to it.
+    /// Returns the limb cells, least significant first, and Σ limb_i·2^(128·i).
+    fn assign_limbs(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        name: &str,
+        count: usize,
+        top_bits: usize,
+        signals: &HashMap<String, Fp>,
+    ) -> Result<(Vec<AssignedCell<Fp, Fp>>, AssignedCell<Fp, Fp>), Error> {
+        // A limb count from an untrusted verify context may be zero
+        if count == 0 {
+            return Err(Error::Synthesis);
+        }
+
+        let mut limbs = Vec::with_capacity(count);
+        for i in 0..count {
+            let limb_signal = limb_name(name, i);
+            let value = signals.get(&limb_signal).copied()
+                .map(Value::known)
+                .unwrap_or(Value::unknown());
+            let limb = self.assign_advice(
+                layouter.namespace(|| limb_signal.clone()),
+                self.config.advice[0],
+                value,
+            )?;
+            let bits = if i + 1 == count { top_bits } else { LIMB_BITS };
+            self.decompose_bits(layouter.namespace(|| format!("{}_bits", limb_signal)), &limb, bits)?;
+            limbs.push(limb);
+        }
+
+        let shift = self.assign_constant(
+            layouter.namespace(|| "limb_shift"),
+            self.config.advice[0],
+            Fp::from(2).pow_vartime([LIMB_BITS as u64]),
+        )?;
+        let mut acc = limbs[count - 1].clone();
+        for (i, limb) in limbs.iter().enumerate().rev().skip(1) {
+            let shifted = self.mul(layouter.namespace(|| format!("limb_shift_{}", i)), &acc, &shift)?;
+            acc = self.add(layouter.namespace(|| format!("limb_recompose_{}", i)), &shifted, limb)?;
+        }
+
+        Ok((limbs, acc))
+    }
+
+    /// Bind the limbs a wide equality split off field-sized signals in `expr`
+    ///
+    /// The limbs must recompose to the cell every use of the signal loads:
+    /// the one an earlier statement or width check produced, or else the
+    /// recomposed cell itself, which later uses then share.
+    fn bind_field_limbs(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        expr: &Expression,
+        wide_signals: &BTreeMap<String, usize>,
+        signals: &HashMap<String, Fp>,
+        intermediates: &mut HashMap<String, AssignedCell<Fp, Fp>>,
+    ) -> Result<(), Error> {
+        for base in field_limb_bases(expr, wide_signals) {
+            if intermediates.contains_key(&limb_name(&base, 0)) {
+                continue;
+            }
+
+            let (limbs, value) = self.assign_limbs(
+                layouter.namespace(|| format!("limbs_{}", base)),
+                &base,
+                FIELD_LIMBS,
+                FIELD_TOP_LIMB_BITS,
+                signals,
+            )?;
+            match intermediates.get(&base) {
+                Some(cell) => {
+                    layouter.assign_region(
+                        || format!("limbs_{}_bind", base),
+                        |mut region| region.constrain_equal(value.cell(), cell.cell()),
+                    )?;
+                }
+                None => {
+                    intermediates.insert(base.clone(), value);
+                }
+            }
+            for (i, limb) in limbs.into_iter().enumerate() {
+                intermediates.insert(limb_name(&base, i), limb);
+            }
+        }
+        Ok(())
+    }
+
+    /// Constrain a boolean cell to be 1
+    ///
+    /// `x == x` is always 1 under the is_zero gadget's own constraints, which
+    /// gives a constrained constant to copy against without a fixed column.
+    fn assert_true(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        cond: &AssignedCell<Fp, Fp>,
+    ) -> Result<(), Error> {
+        let comparison_config = self.config.comparison.as_ref()
+            .ok_or(Error::Synthesis)?;
+        let chip = ComparisonChip::new(comparison_config.clone());
+
+        let one = chip.is_equal(layouter.namespace(|| "one"), cond, cond)?;
+        layouter.assign_region(
+            || "assert_true",
+            |mut region| region.constrain_equal(cond.cell(), one.cell()),
+        )
+    }
+
+    /// Compare two values using range checks and is_zero gadget
+    ///
+    /// This uses the ComparisonChip which provides cryptographically sound comparisons:
+    /// - Equality/Inequality: Uses is_zero gadget with full constraints
+    /// - Greater/Less: Uses range checks + is_zero
+    /// - GreaterEqual/LessEqual: Uses only range checks
+    ///
+    /// All comparisons return 1 (true) or 0 (false).
+    fn compare(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        op: &ComparisonOperator,
+        a: &AssignedCell<Fp, Fp>,
+        b: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        // Get comparison config (should always be Some if circuit uses comparisons)
+        let comparison_config = self.config.comparison.as_ref()
+            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use comparisons
+
+        // Create comparison chip
+        let chip = ComparisonChip::new(comparison_config.clone());
+
+        // Use the bit size that was determined during circuit construction
+        // This ensures we use the correct lookup table (8, 16, 32, or 64 bits)
+        let bits = self.max_bits;
+
+        match op {
+            // Equality never takes `bits`: it is pure is_zero and its operands never
+            // size the range table, even when the circuit also has ordering comparisons
+            ComparisonOperator::Equal => {
+                chip.is_equal(layouter.namespace(|| "is_equal"), a, b)
+            }
+            ComparisonOperator::NotEqual => {
+                chip.is_not_equal(layouter.namespace(|| "is_not_equal"), a, b)
+            }
+            ComparisonOperator::Greater => {
+                chip.is_greater(layouter.namespace(|| "is_greater"), a, b, bits)
+            }
+            ComparisonOperator::Less => {
+                chip.is_less(layouter.namespace(|| "is_less"), a, b, bits)
+            }
+            ComparisonOperator::GreaterEqual => {
+                chip.is_greater_or_equal(layouter.namespace(|| "is_greater_or_equal"), a, b, bits)
+            }
+            ComparisonOperator::LessEqual => {
+                chip.is_less_or_equal(layouter.namespace(|| "is_less_or_equal"), a, b, bits)
+            }
+        }
+    }
+
+    /// Boolean AND: both values non-zero -> 1, else 0
+    ///
+    /// Uses is_zero gadget to convert to bool, then multiplies with constraint
+    fn boolean_and(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: &AssignedCell<Fp, Fp>,
+        b: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        // Get comparison config (should always be Some if circuit uses boolean ops)
+        let comparison_config = self.config.comparison.as_ref()
+            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use boolean ops
+
+        let chip = ComparisonChip::new(comparison_config.clone());
+
+        // Convert a to boolean: is_not_zero(a) = NOT(is_zero(a))
+        let a_is_zero = chip.is_zero(layouter.namespace(|| "a_is_zero"), a)?;
+        let a_bool = chip.is_zero(layouter.namespace(|| "a_to_bool"), &a_is_zero)?;
+
+        // Convert b to boolean: is_not_zero(b) = NOT(is_zero(b))
+        let b_is_zero = chip.is_zero(layouter.namespace(|| "b_is_zero"), b)?;
         let b_bool = chip.is_zero(layouter.namespace(|| "b_to_bool"), &b_is_zero)?;
 
         // Multiply bool values: bool_a * bool_b = output
@@ -1316,6 +2291,35 @@ impl CircuitChip {
         self.boolean_not(layouter.namespace(|| "not_both_false"), &both_false)
     }
 
+    /// Boolean XOR: exactly one value non-zero -> 1, else 0
+    ///
+    /// Converts both operands to bits, then computes a + b - 2ab
+    /// All operations use proper constraints
+    fn boolean_xor(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: &AssignedCell<Fp, Fp>,
+        b: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        // Get comparison config (should always be Some if circuit uses boolean ops)
+        let comparison_config = self.config.comparison.as_ref()
+            .ok_or(Error::Synthesis)?; // Error if minimal circuit tries to use boolean ops
+
+        let chip = ComparisonChip::new(comparison_config.clone());
+
+        // Convert a and b to booleans: is_not_zero(x) = NOT(is_zero(x))
+        let a_is_zero = chip.is_zero(layouter.namespace(|| "a_is_zero"), a)?;
+        let a_bool = chip.is_zero(layouter.namespace(|| "a_to_bool"), &a_is_zero)?;
+        let b_is_zero = chip.is_zero(layouter.namespace(|| "b_is_zero"), b)?;
+        let b_bool = chip.is_zero(layouter.namespace(|| "b_to_bool"), &b_is_zero)?;
+
+        // a + b - 2ab
+        let ab = self.mul(layouter.namespace(|| "xor_ab"), &a_bool, &b_bool)?;
+        let two_ab = self.add(layouter.namespace(|| "xor_2ab"), &ab, &ab)?;
+        let sum = self.add(layouter.namespace(|| "xor_sum"), &a_bool, &b_bool)?;
+        self.sub(layouter.namespace(|| "xor_result"), &sum, &two_ab)
+    }
+
     /// Boolean NOT: 0 -> 1, non-zero -> 0
     ///
     /// Uses is_zero gadget with proper constraints
@@ -1351,12 +2355,12 @@ impl CircuitChip {
                 // Copy a to advice[0]
                 let a_val = a.copy_advice(|| "operand", &mut region, self.config.advice[0], 0)?;
 
-                // Assign -1 to advice[1]
-                region.assign_advice(
+                // Assign the constant -1 to advice[1]
+                region.assign_advice_from_constant(
                     || "minus_one",
                     self.config.advice[1],
                     0,
-                    || Value::known(-Fp::one()),
+                    -Fp::one(),
                 )?;
 
                 // Compute output = -a
@@ -1370,14 +2374,23 @@ impl CircuitChip {
     }
 
     /// Recursively synthesize an expression
+    ///
+    /// `intermediates` holds the cells computed by earlier assignment statements;
+    /// a variable naming one of them reuses that cell, so later statements are
+    /// bound to the constrained result rather than a fresh witness.
     fn synthesize_expr(
         &self,
         mut layouter: impl Layouter<Fp>,
         expr: &Expression,
         signals: &HashMap<String, Fp>,
+        intermediates: &HashMap<String, AssignedCell<Fp, Fp>>,
     ) -> Result<AssignedCell<Fp, Fp>, Error> {
         match expr {
             Expression::Variable(name) => {
+                if let Some(cell) = intermediates.get(name) {
+                    return Ok(cell.clone());
+                }
+
                 // Get value if available (will be None for without_witnesses)
                 let value = signals.get(name).copied()
                     .map(Value::known)
@@ -1393,36 +2406,37 @@ impl CircuitChip {
                 // Parse constant with arbitrary precision support
                 let field_value = parse_constant_to_field(s)
                     .map_err(|_| Error::Synthesis)?;
-                self.assign_advice(
+                self.assign_constant(
                     layouter.namespace(|| format!("const_{}", s)),
                     self.config.advice[0],
-                    Value::known(field_value),
+                    field_value,
                 )
             }
 
             Expression::Boolean(b) => {
                 let value = if *b { Fp::one() } else { Fp::zero() };
-                self.assign_advice(
+                self.assign_constant(
                     layouter.namespace(|| format!("bool_{}", b)),
                     self.config.advice[0],
-                    Value::known(value),
+                    value,
                 )
             }
 
             Expression::BinaryOp { op, left, right } => {
-                let l = self.synthesize_expr(layouter.namespace(|| "left"), left, signals)?;
-                let r = self.synthesize_expr(layouter.namespace(|| "right"), right, signals)?;
+                let l = self.synthesize_expr(layouter.namespace(|| "left"), left, signals, intermediates)?;
+                let r = self.synthesize_expr(layouter.namespace(|| "right"), right, signals, intermediates)?;
 
                 match op {
                     BinaryOperator::Add => self.add(layouter.namespace(|| "add"), &l, &r),
                     BinaryOperator::Sub => self.sub(layouter.namespace(|| "sub"), &l, &r),
                     BinaryOperator::Mul => self.mul(layouter.namespace(|| "mul"), &l, &r),
                     BinaryOperator::Div => self.div(layouter.namespace(|| "div"), &l, &r),
+                    BinaryOperator::IntDiv => self.int_div(layouter.namespace(|| "int_div"), &l, &r),
                 }
             }
 
             Expression::UnaryOp { op, operand } => {
-                let val = self.synthesize_expr(layouter.namespace(|| "operand"), operand, signals)?;
+                let val = self.synthesize_expr(layouter.namespace(|| "operand"), operand, signals, intermediates)?;
 
                 match op {
                     UnaryOperator::Neg => self.negate(layouter.namespace(|| "neg"), &val),
@@ -1431,19 +2445,74 @@ impl CircuitChip {
             }
 
             Expression::Comparison { op, left, right } => {
-                let l = self.synthesize_expr(layouter.namespace(|| "left"), left, signals)?;
-                let r = self.synthesize_expr(layouter.namespace(|| "right"), right, signals)?;
+                let l = self.synthesize_expr(layouter.namespace(|| "left"), left, signals, intermediates)?;
+                let r = self.synthesize_expr(layouter.namespace(|| "right"), right, signals, intermediates)?;
 
                 self.compare(layouter.namespace(|| "compare"), op, &l, &r)
             }
 
+            // Reductions are expanded into add/mul gates when the circuit is built;
+            // one left here names an array of unknown length
+            Expression::Call { function: BuiltinFunction::Sum | BuiltinFunction::Product, .. } => {
+                Err(Error::Synthesis)
+            }
+
+            Expression::Call { function, argument } => {
+                let x = self.synthesize_expr(layouter.namespace(|| "argument"), argument, signals, intermediates)?;
+
+                match function {
+                    BuiltinFunction::Sqrt => {
+                        let (root, _) = self.int_sqrt(layouter.namespace(|| "sqrt"), &x)?;
+                        Ok(root)
+                    }
+                    BuiltinFunction::IsSquare => {
+                        let (_, root_squared) = self.int_sqrt(layouter.namespace(|| "sqrt"), &x)?;
+                        let comparison_config = self.config.comparison.as_ref()
+                            .ok_or(Error::Synthesis)?;
+                        let chip = ComparisonChip::new(comparison_config.clone());
+                        chip.is_equal(layouter.namespace(|| "is_square"), &root_squared, &x)
+                    }
+                    BuiltinFunction::IsBool => {
+                        // x * x == x holds exactly when x * (x - 1) == 0
+                        let squared = self.mul(layouter.namespace(|| "is_bool_square"), &x, &x)?;
+                        layouter.assign_region(
+                            || "is_bool",
+                            |mut region| {
+                                let squared = squared.copy_advice(|| "x_squared", &mut region, self.config.advice[0], 0)?;
+                                region.constrain_equal(squared.cell(), x.cell())
+                            },
+                        )?;
+                        Ok(x)
+                    }
+                    BuiltinFunction::Popcount => self.popcount(layouter.namespace(|| "popcount"), &x),
+                    BuiltinFunction::Keccak256 => {
+                        KeccakChip::new(&self.config).keccak256(layouter.namespace(|| "keccak256"), &x)
+                    }
+                    BuiltinFunction::Abs => self.abs(layouter.namespace(|| "abs"), &x),
+                    BuiltinFunction::Sum | BuiltinFunction::Product => Err(Error::Synthesis),
+                }
+            }
+
             Expression::BooleanOp { op, left, right } => {
-                let l = self.synthesize_expr(layouter.namespace(|| "left"), left, signals)?;
-                let r = self.synthesize_expr(layouter.namespace(|| "right"), right, signals)?;
+                let l = self.synthesize_expr(layouter.namespace(|| "left"), left, signals, intermediates)?;
+                let r = self.synthesize_expr(layouter.namespace(|| "right"), right, signals, intermediates)?;
 
                 match op {
                     BooleanOperator::And => self.boolean_and(layouter.namespace(|| "and"), &l, &r),
                     BooleanOperator::Or => self.boolean_or(layouter.namespace(|| "or"), &l, &r),
+                    BooleanOperator::Xor => self.boolean_xor(layouter.namespace(|| "xor"), &l, &r),
+                    BooleanOperator::Nand => {
+                        let and = self.boolean_and(layouter.namespace(|| "nand_and"), &l, &r)?;
+                        self.boolean_not(layouter.namespace(|| "nand"), &and)
+                    }
+                    BooleanOperator::Nor => {
+                        let or = self.boolean_or(layouter.namespace(|| "nor_or"), &l, &r)?;
+                        self.boolean_not(layouter.namespace(|| "nor"), &or)
+                    }
+                    BooleanOperator::Xnor => {
+                        let xor = self.boolean_xor(layouter.namespace(|| "xnor_xor"), &l, &r)?;
+                        self.boolean_not(layouter.namespace(|| "xnor"), &xor)
+                    }
                 }
             }
         }
@@ -1505,40 +2574,371 @@ fn parse_constant_to_field(value: &str) -> Result<Fp, String> {
 /// let field = bytes_to_field(&bytes)?;  // Automatically reduced modulo field
 /// ```
 fn bytes_to_field(bytes: &[u8]) -> Result<Fp, String> {
+    bytes_to_prime_field::<Fp>(bytes)
+}
+
+/// Convert bytes to an element of any 32-byte prime field
+///
+/// The modulus is derived from the field itself, so the reduction is correct for both
+/// Pallas (`Fp`) and Vesta (`Fq`). The circuit itself is always built over
+/// `Fp` (see "Field Choice" in `docs/ARCHITECTURE.md`).
+fn bytes_to_prime_field<F: PrimeField<Repr = [u8; 32]>>(bytes: &[u8]) -> Result<F, String> {
     // Handle empty bytes
     if bytes.is_empty() {
-        return Ok(Fp::zero());
+        return Ok(F::from(0));
     }
 
     // Convert bytes to BigUint (big-endian input)
     let num = BigUint::from_bytes_be(bytes);
 
-    // Pallas field modulus: p = 0x40000000000000000000000000000000224698fc094cf91b992d30ed00000001
-    let modulus = BigUint::parse_bytes(
-        b"40000000000000000000000000000000224698fc094cf91b992d30ed00000001",
-        16
-    ).expect("Valid Pallas modulus");
+    // The field modulus is -1 + 1 (the representation is little-endian)
+    let modulus = BigUint::from_bytes_le((-F::from(1)).to_repr().as_ref()) + 1u32;
 
-    // Reduce modulo p (automatically handles values larger than field)
+    // Reduce modulo the field (automatically handles values larger than field)
     let reduced = num % modulus;
 
-    // Convert to little-endian bytes (Fp internal representation is little-endian)
+    // Convert to little-endian bytes (the field representation is little-endian)
     let mut le_bytes = reduced.to_bytes_le();
 
-    // Pad to 32 bytes if needed (Pallas field elements are 32 bytes)
+    // Pad to 32 bytes if needed (pasta field elements are 32 bytes)
     le_bytes.resize(32, 0);
 
-    // Create Fp byte representation
     let mut repr = [0u8; 32];
     repr.copy_from_slice(&le_bytes[..32]);
 
-    // Convert to Fp using from_repr
     // This should always succeed since we reduced modulo field
-    Fp::from_repr(repr)
+    F::from_repr(repr)
         .into_option()
         .ok_or_else(|| "Failed to convert to field element (should never happen)".to_string())
 }
 
+/// Range table sizes a circuit can be built with
+const SUPPORTED_RANGE_BITS: [usize; 4] = [8, 16, 32, 64];
+
+/// Values longer than this many bytes (over 248 bits) may not fit the field
+/// and are compared for equality limb by limb
+const WIDE_SIGNAL_BYTES: usize = 31;
+
+/// Limb size used to split wide values (128 bits, well below the field size)
+const LIMB_BYTES: usize = 16;
+
+/// Bits in a limb, each range-checked in the circuit
+const LIMB_BITS: usize = LIMB_BYTES * 8;
+
+/// Limbs a field element (below 2^255) is split into
+const FIELD_LIMBS: usize = (WIDE_SIGNAL_BYTES + 1) / LIMB_BYTES;
+
+/// Bits allowed in the top limb of a field element split into limbs
+///
+/// Below 2^254, under the field prime, so only one pair of limbs recomposes
+/// to a given field element. Elements in [2^254, p), such as negative
+/// numbers, cannot be compared against wide signals.
+const FIELD_TOP_LIMB_BITS: usize = 126;
+
+/// Name of the intermediate signal holding limb `index` of `name`
+fn limb_name(name: &str, index: usize) -> String {
+    format!("{}.limb{}", name, index)
+}
+
+/// Split big-endian bytes into 128-bit limbs, least significant first
+fn bytes_to_limbs(bytes: &[u8]) -> Vec<Fp> {
+    if bytes.is_empty() {
+        return vec![Fp::zero()];
+    }
+    bytes.rchunks(LIMB_BYTES)
+        .map(|chunk| bytes_to_field(chunk).expect("128-bit limb fits the field"))
+        .collect()
+}
+
+/// Record a wide signal's limbs as signals, returning whether the value is wide
+fn insert_wide_limbs(
+    name: &str,
+    bytes: &[u8],
+    signals: &mut HashMap<String, Fp>,
+    wide_signals: &mut BTreeMap<String, usize>,
+) -> bool {
+    if bytes.len() <= WIDE_SIGNAL_BYTES {
+        return false;
+    }
+    let limbs = bytes_to_limbs(bytes);
+    wide_signals.insert(name.to_string(), limbs.len());
+    for (i, limb) in limbs.into_iter().enumerate() {
+        signals.insert(limb_name(name, i), limb);
+    }
+    true
+}
+
+/// Rewrite `==` / `!=` with a wide signal operand into limb-wise equalities
+///
+/// `a == b` becomes `(a.limb0 == b.limb0) AND (a.limb1 == b.limb1)`, so two
+/// values that are congruent modulo the field prime no longer compare equal.
+/// The other operand may be a variable (its field value is split into limbs,
+/// which are added to `signals` when known) or a constant; any other operand
+/// is compared on the reduced field values as before. The circuit range-checks
+/// the limbs and binds them to the value they split (see `assign_limbs`).
+fn split_wide_equality_expr(
+    expr: &Expression,
+    wide_signals: &BTreeMap<String, usize>,
+    signals: &mut HashMap<String, Fp>,
+) -> Expression {
+    macro_rules! split {
+        ($e:expr) => {
+            Box::new(split_wide_equality_expr($e, wide_signals, signals))
+        };
+    }
+
+    match expr {
+        Expression::Comparison { op, left, right }
+            if matches!(op, ComparisonOperator::Equal | ComparisonOperator::NotEqual) =>
+        {
+            let is_wide = |e: &Expression| matches!(e, Expression::Variable(name) if wide_signals.contains_key(name));
+            if is_wide(left) || is_wide(right) {
+                let left_limbs = operand_limbs(left, wide_signals, signals);
+                let right_limbs = operand_limbs(right, wide_signals, signals);
+
+                if let (Some(mut left_limbs), Some(mut right_limbs)) = (left_limbs, right_limbs) {
+                    let count = left_limbs.len().max(right_limbs.len());
+                    left_limbs.resize(count, Expression::constant("0"));
+                    right_limbs.resize(count, Expression::constant("0"));
+
+                    let equal = left_limbs.into_iter().zip(right_limbs)
+                        .map(|(l, r)| Expression::compare(ComparisonOperator::Equal, l, r))
+                        .reduce(Expression::and)
+                        .expect("at least one limb");
+
+                    return if *op == ComparisonOperator::NotEqual {
+                        Expression::not(equal)
+                    } else {
+                        equal
+                    };
+                }
+            }
+
+            Expression::Comparison { op: *op, left: split!(left), right: split!(right) }
+        }
+        Expression::Comparison { op, left, right } => {
+            Expression::Comparison { op: *op, left: split!(left), right: split!(right) }
+        }
+        Expression::BinaryOp { op, left, right } => {
+            Expression::BinaryOp { op: *op, left: split!(left), right: split!(right) }
+        }
+        Expression::BooleanOp { op, left, right } => {
+            Expression::BooleanOp { op: *op, left: split!(left), right: split!(right) }
+        }
+        Expression::UnaryOp { op, operand } => {
+            Expression::UnaryOp { op: *op, operand: split!(operand) }
+        }
+        Expression::Call { function, argument } => {
+            Expression::Call { function: *function, argument: split!(argument) }
+        }
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => expr.clone(),
+    }
+}
+
+/// Limb expressions for one side of a wide equality, or None if it can't be split
+fn operand_limbs(
+    expr: &Expression,
+    wide_signals: &BTreeMap<String, usize>,
+    signals: &mut HashMap<String, Fp>,
+) -> Option<Vec<Expression>> {
+    match expr {
+        Expression::Variable(name) => {
+            let count = match wide_signals.get(name) {
+                Some(count) => *count,
+                None => {
+                    // A field element is below 2^255: two limbs
+                    if let Some(value) = signals.get(name).copied() {
+                        let mut bytes = value.to_repr().as_ref().to_vec();
+                        bytes.reverse();
+                        for (i, limb) in bytes_to_limbs(&bytes).into_iter().enumerate() {
+                            signals.insert(limb_name(name, i), limb);
+                        }
+                    }
+                    FIELD_LIMBS
+                }
+            };
+            Some((0..count).map(|i| Expression::var(limb_name(name, i))).collect())
+        }
+        Expression::Constant(value) => {
+            let bytes = BigUint::from_str_radix(value, 10).ok()?.to_bytes_be();
+            Some(bytes.rchunks(LIMB_BYTES)
+                .map(|chunk| Expression::constant(BigUint::from_bytes_be(chunk).to_string()))
+                .collect())
+        }
+        _ => None,
+    }
+}
+
+/// Signals in `expr` that a wide equality split into limbs without being wide
+///
+/// These are field elements compared against a wide value. Their limbs
+/// (`x.limb0`, `x.limb1`) are bound to the field element in the circuit.
+fn field_limb_bases(expr: &Expression, wide_signals: &BTreeMap<String, usize>) -> Vec<String> {
+    // Signal names cannot contain '.', so the suffix only marks limbs
+    let mut bases: Vec<String> = expr.variables().iter()
+        .filter_map(|name| name.rsplit_once(".limb").map(|(base, _)| base.to_string()))
+        .filter(|base| !wide_signals.contains_key(base))
+        .collect();
+    bases.dedup();
+    bases
+}
+
+/// Strip a `pub` prefix from an assignment statement
+///
+/// `pub hash<==A+B` exposes the assigned value as a public signal. Returns
+/// whether the prefix was present and the statement without it; anything
+/// other than a `pub`-prefixed assignment is returned unchanged.
+pub fn split_public_assignment(statement: &str) -> (bool, &str) {
+    let trimmed = statement.trim_start();
+    match trimmed.strip_prefix("pub") {
+        Some(rest) if rest.starts_with(char::is_whitespace) && rest.contains("<==") => (true, rest.trim_start()),
+        _ => (false, statement),
+    }
+}
+
+/// Name of the signal holding element `index` of array signal `name`
+pub fn array_element_name(name: &str, index: usize) -> String {
+    format!("{}_{}", name, index)
+}
+
+/// Decode each element of an array signal value
+fn parse_array_elements(
+    name: &str,
+    elements: &[&str],
+    encoding: Option<ValueEncoding>,
+) -> Result<Vec<Vec<u8>>, String> {
+    if elements.iter().all(|e| e.is_empty()) {
+        return Err(format!("Array signal '{}' is empty; arrays need at least one element", name));
+    }
+    elements.iter().enumerate()
+        .map(|(i, element)| {
+            match encoding {
+                Some(encoding) => parse_value(element, encoding),
+                None => parse_value_auto(element),
+            }
+            .map_err(|e| format!("Failed to parse element {} of array signal '{}': {}", i, name, e))
+        })
+        .collect()
+}
+
+/// Record the elements of an array signal as `name_0`, `name_1`, ... and
+/// return their names
+fn insert_array_elements(
+    name: &str,
+    values: &[Vec<u8>],
+    signals: &mut HashMap<String, Fp>,
+    wide_signals: &mut BTreeMap<String, usize>,
+) -> Result<Vec<String>, String> {
+    let mut names = Vec::with_capacity(values.len());
+    for (i, bytes) in values.iter().enumerate() {
+        let element = array_element_name(name, i);
+        insert_wide_limbs(&element, bytes, signals, wide_signals);
+        signals.insert(element.clone(), bytes_to_field(bytes)?);
+        names.push(element);
+    }
+    Ok(names)
+}
+
+/// Expand `sum(name)` / `product(name)` into add/mul gates over the elements
+/// of arrays with a known length
+///
+/// The gates form a balanced tree, so long arrays stay well within
+/// `MAX_EXPRESSION_DEPTH`.
+///
+/// A reduction over a name that is not in `array_lengths` is kept as is (the
+/// verifier learns secret array lengths from the verify context), unless
+/// `signals` holds a scalar value under that name.
+fn expand_array_reductions(
+    expr: &Expression,
+    array_lengths: &BTreeMap<String, usize>,
+    signals: &HashMap<String, Fp>,
+) -> Result<Expression, String> {
+    macro_rules! expand {
+        ($e:expr) => {
+            Box::new(expand_array_reductions($e, array_lengths, signals)?)
+        };
+    }
+
+    Ok(match expr {
+        Expression::Call { function: function @ (BuiltinFunction::Sum | BuiltinFunction::Product), argument } => {
+            let Expression::Variable(name) = argument.as_ref() else { return Ok(expr.clone()) };
+            let Some(&length) = array_lengths.get(name) else {
+                if signals.contains_key(name) {
+                    return Err(format!("{}() expects an array signal, but '{}' is a single value", function, name));
+                }
+                return Ok(expr.clone());
+            };
+
+            let combine = match function {
+                BuiltinFunction::Sum => Expression::add,
+                _ => Expression::mul,
+            };
+            let mut level: Vec<Expression> = (0..length)
+                .map(|i| Expression::var(array_element_name(name, i)))
+                .collect();
+            while level.len() > 1 {
+                let mut pairs = level.into_iter();
+                let mut next = Vec::new();
+                while let Some(left) = pairs.next() {
+                    next.push(match pairs.next() {
+                        Some(right) => combine(left, right),
+                        None => left,
+                    });
+                }
+                level = next;
+            }
+            level.pop().expect("arrays have at least one element")
+        }
+        Expression::Comparison { op, left, right } => {
+            Expression::Comparison { op: *op, left: expand!(left), right: expand!(right) }
+        }
+        Expression::BinaryOp { op, left, right } => {
+            Expression::BinaryOp { op: *op, left: expand!(left), right: expand!(right) }
+        }
+        Expression::BooleanOp { op, left, right } => {
+            Expression::BooleanOp { op: *op, left: expand!(left), right: expand!(right) }
+        }
+        Expression::UnaryOp { op, operand } => {
+            Expression::UnaryOp { op: *op, operand: expand!(operand) }
+        }
+        Expression::Call { function, argument } => {
+            Expression::Call { function: *function, argument: expand!(argument) }
+        }
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => expr.clone(),
+    })
+}
+
+/// Replace variables naming constant signals with their values as literals
+fn substitute_constants(expr: &Expression, constants: &BTreeMap<String, Fp>) -> Expression {
+    if constants.is_empty() {
+        return expr.clone();
+    }
+
+    let substitute = |e: &Expression| Box::new(substitute_constants(e, constants));
+    match expr {
+        Expression::Variable(name) => match constants.get(name) {
+            Some(value) => Expression::constant(BigUint::from_bytes_le(value.to_repr().as_ref()).to_string()),
+            None => expr.clone(),
+        },
+        Expression::Comparison { op, left, right } => {
+            Expression::Comparison { op: *op, left: substitute(left), right: substitute(right) }
+        }
+        Expression::BinaryOp { op, left, right } => {
+            Expression::BinaryOp { op: *op, left: substitute(left), right: substitute(right) }
+        }
+        Expression::BooleanOp { op, left, right } => {
+            Expression::BooleanOp { op: *op, left: substitute(left), right: substitute(right) }
+        }
+        Expression::UnaryOp { op, operand } => {
+            Expression::UnaryOp { op: *op, operand: substitute(operand) }
+        }
+        Expression::Call { function, argument } => {
+            Expression::Call { function: *function, argument: substitute(argument) }
+        }
+        Expression::Constant(_) | Expression::Boolean(_) => expr.clone(),
+    }
+}
+
 /// Helper to evaluate expressions (for witness generation)
 pub fn evaluate_expression(
     expr: &Expression,
@@ -1569,9 +2969,15 @@ pub fn evaluate_expression(
                 BinaryOperator::Sub => Ok(l - r),
                 BinaryOperator::Mul => Ok(l * r),
                 BinaryOperator::Div => {
-                    let r_inv = r.invert().unwrap_or(Fp::zero());
+                    let r_inv = Option::<Fp>::from(r.invert())
+                        .ok_or_else(|| division_by_zero_error(expr))?;
                     Ok(l * r_inv)
                 }
+                BinaryOperator::IntDiv => {
+                    field_div_rem(&l, &r)
+                        .map(|(q, _)| q)
+                        .ok_or_else(|| division_by_zero_error(expr))
+                }
             }
         }
 
@@ -1587,6 +2993,53 @@ pub fn evaluate_expression(
             }
         }
 
+        // Reduce over the elements `name_0`, `name_1`, ... of an array signal
+        Expression::Call { function: function @ (BuiltinFunction::Sum | BuiltinFunction::Product), argument } => {
+            let Expression::Variable(name) = argument.as_ref() else {
+                return Err(format!("{}() takes the name of an array signal in expression '{}'", function, expr));
+            };
+            let elements: Vec<Fp> = (0..)
+                .map_while(|i| signals.get(&array_element_name(name, i)).copied())
+                .collect();
+            if elements.is_empty() {
+                return Err(format!("Array signal '{}' not found", name));
+            }
+
+            Ok(match function {
+                BuiltinFunction::Sum => elements.into_iter().fold(Fp::zero(), |acc, x| acc + x),
+                _ => elements.into_iter().fold(Fp::one(), |acc, x| acc * x),
+            })
+        }
+
+        Expression::Call { function, argument } => {
+            let x = evaluate_expression(argument, signals)?;
+
+            match function {
+                BuiltinFunction::Sqrt => Ok(field_isqrt(&x)),
+                BuiltinFunction::IsSquare => {
+                    let root = field_isqrt(&x);
+                    Ok(if root * root == x { Fp::one() } else { Fp::zero() })
+                }
+                BuiltinFunction::IsBool => {
+                    if x != Fp::zero() && x != Fp::one() {
+                        return Err(format!("is_bool argument is neither 0 nor 1 in expression '{}'", expr));
+                    }
+                    Ok(x)
+                }
+                BuiltinFunction::Popcount => {
+                    // Bit decomposition covers at most the 64-bit range table
+                    if x.to_repr().as_ref()[8..].iter().any(|b| *b != 0) {
+                        return Err(format!("popcount argument exceeds 64 bits in expression '{}'", expr));
+                    }
+                    Ok(Fp::from(field_to_u64(&x).count_ones() as u64))
+                }
+                BuiltinFunction::Keccak256 => keccak256_field(&x)
+                    .map_err(|e| format!("{} in expression '{}'", e, expr)),
+                BuiltinFunction::Abs => Ok(field_abs(&x)),
+                BuiltinFunction::Sum | BuiltinFunction::Product => unreachable!("reductions are handled above"),
+            }
+        }
+
         Expression::Comparison { op, left, right } => {
             let l = evaluate_expression(left, signals)?;
             let r = evaluate_expression(right, signals)?;
@@ -1607,24 +3060,181 @@ pub fn evaluate_expression(
             Ok(if result { Fp::one() } else { Fp::zero() })
         }
 
-        Expression::BooleanOp { op, left, right } => {
-            let l = evaluate_expression(left, signals)?;
-            let r = evaluate_expression(right, signals)?;
+        Expression::BooleanOp { op, left, right } => {
+            let l = evaluate_expression(left, signals)?;
+            let r = evaluate_expression(right, signals)?;
+
+            // Treat any non-zero as true
+            let l_bool = l != Fp::zero();
+            let r_bool = r != Fp::zero();
+
+            let result = match op {
+                BooleanOperator::And => l_bool && r_bool,
+                BooleanOperator::Or => l_bool || r_bool,
+                BooleanOperator::Xor => l_bool != r_bool,
+                BooleanOperator::Nand => !(l_bool && r_bool),
+                BooleanOperator::Nor => !(l_bool || r_bool),
+                BooleanOperator::Xnor => l_bool == r_bool,
+            };
+
+            Ok(if result { Fp::one() } else { Fp::zero() })
+        }
+    }
+}
+
+/// Error message for a zero divisor in `expr`
+fn division_by_zero_error(expr: &Expression) -> String {
+    format!("division by zero in expression '{}'", expr)
+}
+
+/// Reject an expression too deep to evaluate and synthesize recursively
+///
+/// `parse_circuit` already limits what users write; this covers the tree after
+/// array reductions and wide equalities have been expanded.
+fn check_expression_depth(expr: &Expression) -> Result<(), String> {
+    use crate::parser::{ParseError, MAX_EXPRESSION_DEPTH};
+
+    if expr.depth() > MAX_EXPRESSION_DEPTH {
+        return Err(ParseError::TooDeep { depth: MAX_EXPRESSION_DEPTH }.to_string());
+    }
+    Ok(())
+}
+
+/// Find a division whose divisor evaluates to zero
+///
+/// Divisors that cannot be evaluated (e.g. secret signals missing during
+/// verification) are skipped, so this only fails when the witness is known
+/// to be invalid.
+fn check_division_by_zero(
+    expr: &Expression,
+    signals: &HashMap<String, Fp>,
+) -> Result<(), String> {
+    match expr {
+        Expression::BinaryOp { op, left, right } => {
+            if matches!(op, BinaryOperator::Div | BinaryOperator::IntDiv)
+                && evaluate_expression(right, signals).ok() == Some(Fp::zero())
+            {
+                return Err(division_by_zero_error(expr));
+            }
+            check_division_by_zero(left, signals)?;
+            check_division_by_zero(right, signals)
+        }
+
+        Expression::Comparison { left, right, .. } |
+        Expression::BooleanOp { left, right, .. } => {
+            check_division_by_zero(left, signals)?;
+            check_division_by_zero(right, signals)
+        }
+
+        Expression::UnaryOp { operand, .. } => check_division_by_zero(operand, signals),
+
+        Expression::Call { argument, .. } => check_division_by_zero(argument, signals),
+
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => Ok(()),
+    }
+}
+
+/// Widest operand an ordering comparison can range-check
+const MAX_COMPARISON_BITS: usize = 64;
+
+/// Find an ordering comparison with an operand wider than the range table can check
+///
+/// Operands are sized from their evaluated values, so arithmetic that grows
+/// past the operands' own widths (`A * B > C`) is caught here with a clear
+/// error instead of failing the range check during proving. A negative
+/// difference wraps around the field and is rejected the same way. Operands
+/// that cannot be evaluated (e.g. during verification) are skipped.
+fn check_comparison_overflow(
+    expr: &Expression,
+    signals: &HashMap<String, Fp>,
+) -> Result<(), String> {
+    use crate::parser::ComparisonOperator;
+
+    match expr {
+        Expression::Comparison { op, left, right } => {
+            let is_ordering = !matches!(op, ComparisonOperator::Equal | ComparisonOperator::NotEqual);
+            if is_ordering {
+                for operand in [left, right] {
+                    let Ok(value) = evaluate_expression(operand, signals) else { continue };
+                    let bits = Circuit::bit_length(&value);
+                    if bits > MAX_COMPARISON_BITS {
+                        return Err(format!(
+                            "Value exceeds 2^{} for ordering comparison: '{}' in '{}' needs {} bits",
+                            MAX_COMPARISON_BITS, operand, expr, bits
+                        ));
+                    }
+                }
+            }
+            check_comparison_overflow(left, signals)?;
+            check_comparison_overflow(right, signals)
+        }
+
+        Expression::BinaryOp { left, right, .. } |
+        Expression::BooleanOp { left, right, .. } => {
+            check_comparison_overflow(left, signals)?;
+            check_comparison_overflow(right, signals)
+        }
+
+        Expression::UnaryOp { operand, .. } => check_comparison_overflow(operand, signals),
+
+        Expression::Call { argument, .. } => check_comparison_overflow(argument, signals),
+
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => Ok(()),
+    }
+}
+
+/// Integer quotient and remainder of two field elements
+///
+/// Operands are read as unsigned integers (canonical representatives), not as
+/// field elements, so `7 // 2` is 3 rather than 7 * 2^-1.
+///
+/// Returns None if `b` is zero.
+fn field_div_rem(a: &Fp, b: &Fp) -> Option<(Fp, Fp)> {
+    let a = BigUint::from_bytes_le(a.to_repr().as_ref());
+    let b = BigUint::from_bytes_le(b.to_repr().as_ref());
+
+    if b == BigUint::from(0u32) {
+        return None;
+    }
+
+    // Both results are below the modulus, so the conversion is lossless
+    let q = bytes_to_field(&(&a / &b).to_bytes_be()).ok()?;
+    let r = bytes_to_field(&(&a % &b).to_bytes_be()).ok()?;
+    Some((q, r))
+}
 
-            // Treat any non-zero as true
-            let l_bool = l != Fp::zero();
-            let r_bool = r != Fp::zero();
+/// Floor integer square root of a field element
+///
+/// The value is read as an unsigned integer (canonical representative), so the
+/// result is the integer root, not a field square root.
+fn field_isqrt(x: &Fp) -> Fp {
+    let x = BigUint::from_bytes_le(x.to_repr().as_ref());
 
-            let result = match op {
-                BooleanOperator::And => l_bool && r_bool,
-                BooleanOperator::Or => l_bool || r_bool,
-            };
+    // The root is below the modulus, so the conversion is lossless
+    bytes_to_field(&x.sqrt().to_bytes_be()).expect("square root is below the modulus")
+}
 
-            Ok(if result { Fp::one() } else { Fp::zero() })
-        }
+/// Signed absolute value of a field element
+///
+/// Values above (p - 1) / 2 are read as negative, so `x` and `-x` map to the
+/// same magnitude: whichever of the two is the smaller integer.
+fn field_abs(x: &Fp) -> Fp {
+    let neg = -*x;
+    let (x_repr, neg_repr) = (x.to_repr(), neg.to_repr());
+
+    // Compare canonical representatives, most significant byte first
+    if neg_repr.as_ref().iter().rev().lt(x_repr.as_ref().iter().rev()) {
+        neg
+    } else {
+        *x
     }
 }
 
+/// Largest value that fits in `bits` bits: 2^bits - 1
+fn max_value(bits: usize) -> Fp {
+    Fp::from(2).pow_vartime([bits as u64]) - Fp::one()
+}
+
 /// Helper to convert field element to u64 (for comparisons)
 fn field_to_u64(f: &Fp) -> u64 {
     let bytes = f.to_repr();
@@ -1639,6 +3249,23 @@ fn field_to_u64(f: &Fp) -> u64 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_bytes_to_prime_field_uses_field_modulus() {
+        use halo2_proofs::pasta::Fq;
+
+        // Modulus + k, big-endian
+        let over = |modulus: &str, k: u32| (BigUint::parse_bytes(modulus.as_bytes(), 16).unwrap() + k).to_bytes_be();
+        let pallas = over("40000000000000000000000000000000224698fc094cf91b992d30ed00000001", 5);
+        let vesta = over("40000000000000000000000000000000224698fc0994a8dd8c46eb2100000001", 3);
+
+        assert_eq!(bytes_to_prime_field::<Fp>(&pallas).unwrap(), Fp::from(5));
+        assert_eq!(bytes_to_prime_field::<Fq>(&vesta).unwrap(), Fq::from(3));
+        assert_eq!(bytes_to_field(&pallas).unwrap(), Fp::from(5));
+
+        // The moduli differ, so the same bytes reduce differently
+        assert_ne!(bytes_to_prime_field::<Fq>(&pallas).unwrap(), Fq::from(5));
+    }
+
     #[test]
     fn test_evaluate_arithmetic() {
         let mut signals = HashMap::new();
@@ -1788,6 +3415,36 @@ mod tests {
         assert!(parse_constant_to_field("0x123").is_err());  // No hex prefix
     }
 
+    #[test]
+    fn test_equality_operands_do_not_size_range_table() {
+        use crate::parser::parse_circuit;
+
+        let mut signals = HashMap::new();
+        signals.insert("key".to_string(), bytes_to_field(&[0xab; 32]).unwrap());
+        signals.insert("target".to_string(), bytes_to_field(&[0xab; 32]).unwrap());
+        signals.insert("age".to_string(), Fp::from(25));
+
+        // Only `age > 18` should contribute to max_bits, not the 256-bit key
+        let expr = parse_circuit("(key == target) AND (age > 18)").unwrap();
+        let circuit = Circuit::new(expr, signals.clone(), vec![]);
+        assert_eq!(circuit.max_range_check_bits(), Some(8));
+
+        // Equality alone needs no range checks at all
+        let expr = parse_circuit("key == target").unwrap();
+        let circuit = Circuit::new(expr, signals, vec![]);
+        assert_eq!(circuit.max_range_check_bits(), None);
+    }
+
+    #[test]
+    fn test_radix_literal_overflow_reduces_modulo_field() {
+        // 256-bit hex literal exceeds the Pallas modulus and must be reduced
+        let literal = format!("0x{}", "ff".repeat(32));
+        let expr = crate::parser::parse_circuit(&literal).unwrap();
+
+        let result = evaluate_expression(&expr, &HashMap::new()).unwrap();
+        assert_eq!(result, bytes_to_field(&[0xff; 32]).unwrap());
+    }
+
     #[test]
     fn test_from_program_with_preprocess() {
         use crate::api::Program;
@@ -1887,4 +3544,728 @@ mod tests {
         assert_eq!(*circuit.signals.get("B").unwrap(), Fp::from(20));
         assert_eq!(*circuit.signals.get("C").unwrap(), Fp::from(30));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_int_div_exact() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), Fp::from(20));
+        signals.insert("B".to_string(), Fp::from(5));
+
+        let expr = Expression::int_div(Expression::var("A"), Expression::var("B"));
+        assert_eq!(evaluate_expression(&expr, &signals).unwrap(), Fp::from(4));
+
+        // Exact division agrees with field division
+        let field = Expression::div(Expression::var("A"), Expression::var("B"));
+        assert_eq!(evaluate_expression(&field, &signals).unwrap(), Fp::from(4));
+    }
+
+    #[test]
+    fn test_int_div_non_exact() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), Fp::from(20));
+        signals.insert("B".to_string(), Fp::from(3));
+
+        let expr = Expression::int_div(Expression::var("A"), Expression::var("B"));
+        assert_eq!(evaluate_expression(&expr, &signals).unwrap(), Fp::from(6));
+
+        // Field division gives 20 * 3^-1, which is not 6
+        let field = Expression::div(Expression::var("A"), Expression::var("B"));
+        assert_ne!(evaluate_expression(&field, &signals).unwrap(), Fp::from(6));
+
+        assert_eq!(
+            field_div_rem(&Fp::from(20), &Fp::from(3)),
+            Some((Fp::from(6), Fp::from(2)))
+        );
+    }
+
+    #[test]
+    fn test_int_div_by_zero() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), Fp::from(20));
+        signals.insert("B".to_string(), Fp::zero());
+
+        let expr = Expression::int_div(Expression::var("A"), Expression::var("B"));
+        let err = evaluate_expression(&expr, &signals).unwrap_err();
+        assert_eq!(err, "division by zero in expression 'A // B'");
+    }
+
+    #[test]
+    fn test_int_div_uses_range_checks() {
+        use crate::parser::parse_circuit;
+
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), Fp::from(70000));
+        signals.insert("B".to_string(), Fp::from(7));
+
+        let circuit = Circuit::new(parse_circuit("A // B").unwrap(), signals, vec![]);
+        assert!(circuit.uses_range_check_comparisons());
+        // Dividend needs 17 bits, so the 32-bit table is required
+        assert_eq!(circuit.max_range_check_bits(), Some(32));
+        assert_eq!(circuit.circuit_output, Some(Fp::from(10000)));
+    }
+
+    /// `a // b` proven from a chosen quotient and remainder, with an 8-bit range table
+    #[derive(Clone)]
+    struct IntDivWitness {
+        a: Fp,
+        b: Fp,
+        q: Fp,
+        r: Fp,
+    }
+
+    impl PlonkCircuit<Fp> for IntDivWitness {
+        type Config = CircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            self.clone()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fp>) -> Self::Config {
+            CircuitConfig::configure(meta)
+        }
+
+        fn synthesize(&self, config: Self::Config, mut layouter: impl Layouter<Fp>) -> Result<(), Error> {
+            let comparison = config.comparison.as_ref().ok_or(Error::Synthesis)?;
+            comparison.range_check.load_up_to(&mut layouter, 8)?;
+
+            let chip = CircuitChip::new(config.clone(), 8);
+            let a = chip.assign_advice(layouter.namespace(|| "a"), config.advice[0], Value::known(self.a))?;
+            let b = chip.assign_advice(layouter.namespace(|| "b"), config.advice[0], Value::known(self.b))?;
+            let q = chip.int_div_with_witness(layouter.namespace(|| "int_div"), &a, &b, Value::known((self.q, self.r)))?;
+            layouter.constrain_instance(q.cell(), config.instance, 0)
+        }
+    }
+
+    #[test]
+    fn test_int_div_rejects_forged_remainder() {
+        use halo2_proofs::dev::MockProver;
+
+        let run = |q: Fp, r: Fp| {
+            let circuit = IntDivWitness { a: Fp::from(20), b: Fp::from(3), q, r };
+            MockProver::run(10, &circuit, vec![vec![q]]).unwrap().verify()
+        };
+        assert_eq!(run(Fp::from(6), Fp::from(2)), Ok(()));
+
+        // 7 * 3 + (p - 1) == 20, and 3 - (p - 1) - 1 passes the r < b difference check
+        assert!(run(Fp::from(7), -Fp::one()).is_err());
+    }
+
+    #[test]
+    fn test_field_div_by_zero_is_error() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), Fp::from(20));
+        signals.insert("B".to_string(), Fp::zero());
+
+        let expr = Expression::div(Expression::var("A"), Expression::var("B"));
+        let err = evaluate_expression(&expr, &signals).unwrap_err();
+        assert_eq!(err, "division by zero in expression 'A / B'");
+    }
+
+    #[test]
+    fn test_from_program_rejects_division_by_zero() {
+        use crate::api::Program;
+
+        // Zero divisor inside a non-final statement is still caught
+        let mut program = Program::from_zircon("1/A:20,B:0/-/-/x<==A+1;x>0").unwrap();
+        program.circuit = vec!["q<==A/B".to_string(), "q+1".to_string()];
+        let err = Circuit::from_program(&program).err().unwrap();
+        assert_eq!(err, ZkplexError::DivisionByZero("division by zero in expression 'A / B'".to_string()));
+
+        // Divisor depending on an unknown signal is not flagged
+        program.secret.shift_remove("B");
+        assert!(Circuit::from_program(&program).is_ok());
+    }
+
+    #[test]
+    fn test_from_program_inline_hash() {
+        use crate::api::Program;
+        use num_bigint::BigUint;
+
+        // Same hash through an explicit preprocess statement
+        let explicit = Circuit::from_program(&Program::from_zircon("1/secret:42/out:?/h<==sha256(secret{%d})/h").unwrap()).unwrap();
+        // The 32-byte hash is wide, so compare against its full value rather than the reduced one
+        let limb = |i: usize| BigUint::from_bytes_le(explicit.signals[&format!("h.limb{}", i)].to_repr().as_ref());
+        let target = ((limb(1) << 128usize) + limb(0)).to_string();
+
+        for (target, expected) in [(target.as_str(), 1u64), ("12345", 0)] {
+            let zircon = format!("1/secret:42/target:{},out:?/-/sha256(secret{{%d}})==target", target);
+            let circuit = Circuit::from_program(&Program::from_zircon(&zircon).unwrap()).unwrap();
+            assert_eq!(circuit.circuit_output, Some(Fp::from(expected)), "target={}", target);
+        }
+    }
+
+    #[test]
+    fn test_xor_truth_table() {
+        use crate::parser::parse_circuit;
+        use halo2_proofs::dev::MockProver;
+
+        // Non-zero values other than 1 are truthy, like AND/OR
+        for (a, b, expected) in [(0, 0, 0), (0, 7, 1), (5, 0, 1), (5, 7, 0)] {
+            let mut signals = HashMap::new();
+            signals.insert("A".to_string(), Fp::from(a));
+            signals.insert("B".to_string(), Fp::from(b));
+
+            let circuit = Circuit::new(parse_circuit("A XOR B").unwrap(), signals, vec![]);
+            assert_eq!(circuit.circuit_output, Some(Fp::from(expected)), "{} XOR {}", a, b);
+
+            let prover = MockProver::run(8, &CircuitBoolean(circuit), vec![vec![Fp::from(expected)]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "{} XOR {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_negated_boolean_truth_tables() {
+        use crate::parser::parse_circuit;
+        use halo2_proofs::dev::MockProver;
+
+        let cases = [
+            ("NAND", [1, 1, 1, 0]),
+            ("NOR", [1, 0, 0, 0]),
+            ("XNOR", [1, 0, 0, 1]),
+        ];
+
+        for (op, expected) in cases {
+            for ((a, b), expected) in [(0, 0), (0, 7), (5, 0), (5, 7)].into_iter().zip(expected) {
+                let mut signals = HashMap::new();
+                signals.insert("A".to_string(), Fp::from(a));
+                signals.insert("B".to_string(), Fp::from(b));
+
+                let expr = parse_circuit(&format!("A {} B", op)).unwrap();
+                let circuit = Circuit::new(expr, signals, vec![]);
+                assert_eq!(circuit.circuit_output, Some(Fp::from(expected)), "{} {} {}", a, op, b);
+
+                let prover = MockProver::run(8, &CircuitBoolean(circuit), vec![vec![Fp::from(expected)]]).unwrap();
+                assert_eq!(prover.verify(), Ok(()), "{} {} {}", a, op, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_and_is_square() {
+        use crate::parser::parse_circuit;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // (x, floor sqrt, is perfect square); 255 checks the 8-bit boundary
+        for (x, root, square) in [(0u64, 0u64, 1u64), (1, 1, 1), (49, 7, 1), (50, 7, 0), (255, 15, 0), (65536, 256, 1)] {
+            let mut signals = HashMap::new();
+            signals.insert("X".to_string(), Fp::from(x));
+
+            for (circuit_str, expected) in [("sqrt(X)", root), ("is_square(X)", square)] {
+                let circuit = Circuit::new(parse_circuit(circuit_str).unwrap(), signals.clone(), vec![]);
+                assert_eq!(circuit.circuit_output, Some(Fp::from(expected)), "{} for X={}", circuit_str, x);
+                assert!(circuit.uses_range_check_comparisons());
+
+                let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+                let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::from(expected)]]).unwrap();
+                assert_eq!(prover.verify(), Ok(()), "{} for X={}", circuit_str, x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sqrt_in_comparison() {
+        use crate::parser::parse_circuit;
+
+        let mut signals = HashMap::new();
+        signals.insert("X".to_string(), Fp::from(1000));
+
+        // floor(sqrt(1000)) = 31
+        let circuit = Circuit::new(parse_circuit("sqrt(X) == 31 AND NOT is_square(X)").unwrap(), signals, vec![]);
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+    }
+
+    #[test]
+    fn test_is_bool() {
+        use crate::parser::parse_circuit;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        for flag in [0u64, 1, 2] {
+            let mut signals = HashMap::new();
+            signals.insert("flag".to_string(), Fp::from(flag));
+
+            let circuit = Circuit::new(parse_circuit("is_bool(flag)").unwrap(), signals, vec![]);
+            assert!(!circuit.uses_range_check_comparisons());
+            assert_eq!(circuit.circuit_output, (flag < 2).then(|| Fp::from(flag)), "flag={}", flag);
+
+            // A prover claiming flag=2 anyway violates x * x == x
+            let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+            let prover = MockProver::run(k, &CircuitBoolean(circuit), vec![vec![Fp::from(flag)]]).unwrap();
+            assert_eq!(prover.verify().is_ok(), flag < 2, "flag={}", flag);
+        }
+    }
+
+    #[test]
+    fn test_circuit_builder() {
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // (A + B) > C
+        let build = |c: u64| CircuitBuilder::new()
+            .add_secret("A", Fp::from(10))
+            .add_secret("B", Fp::from(20))
+            .add_public("C", Fp::from(c))
+            .statement(Expression::compare(
+                ComparisonOperator::Greater,
+                Expression::add(Expression::var("A"), Expression::var("B")),
+                Expression::var("C"),
+            ))
+            .build()
+            .unwrap();
+
+        let circuit = build(25);
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+        assert_eq!(circuit.public_signal_names, vec!["C".to_string()]);
+        assert_eq!(build(40).circuit_output, Some(Fp::zero()));
+
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::from(25), Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // Assignments are evaluated for later statements
+        let circuit = CircuitBuilder::new()
+            .add_secret("A", Fp::from(6))
+            .assign("square", Expression::mul(Expression::var("A"), Expression::var("A")))
+            .statement(Expression::add(Expression::var("square"), Expression::constant("1")))
+            .build()
+            .unwrap();
+        assert_eq!(circuit.signals["square"], Fp::from(36));
+        assert_eq!(circuit.circuit_output, Some(Fp::from(37)));
+
+        let duplicate = CircuitBuilder::new()
+            .add_secret("A", Fp::from(1))
+            .add_public("A", Fp::from(2))
+            .statement(Expression::var("A"))
+            .build();
+        assert!(matches!(duplicate, Err(ZkplexError::Invalid(_))));
+        assert!(CircuitBuilder::new().build().is_err());
+    }
+
+    #[test]
+    fn test_popcount() {
+        use crate::parser::parse_circuit;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        let circuit = Circuit::new(parse_circuit("popcount(0b1011) == 3").unwrap(), HashMap::new(), vec![]);
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_popcount_threshold() {
+        use crate::parser::parse_circuit;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // At least 3 permission bits set
+        for (mask, expected) in [(0b10101u64, 1u64), (0b101, 0), (0xff, 1), (0, 0)] {
+            let mut signals = HashMap::new();
+            signals.insert("mask".to_string(), Fp::from(mask));
+
+            let circuit = Circuit::new(parse_circuit("popcount(mask) >= 3").unwrap(), signals, vec![]);
+            assert_eq!(circuit.circuit_output, Some(Fp::from(expected)), "mask={:#b}", mask);
+
+            let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+            let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::from(expected)]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "mask={:#b}", mask);
+        }
+    }
+
+    #[test]
+    fn test_popcount_wide_argument_sizes_range_table() {
+        use crate::parser::parse_circuit;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // 40-bit mask next to an 8-bit comparison
+        let mut signals = HashMap::new();
+        signals.insert("mask".to_string(), Fp::from(0xff_fff0_0000u64));
+        signals.insert("age".to_string(), Fp::from(30));
+
+        let circuit = Circuit::new(
+            parse_circuit("popcount(mask) == 20 AND age > 18").unwrap(),
+            signals,
+            vec![],
+        );
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+        assert_eq!(circuit.max_range_check_bits(), Some(64));
+
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_keccak256_known_answer() {
+        use crate::parser::parse_circuit;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // keccak256(abi.encode(uint256(1))), the published Solidity mapping slot vector
+        let mut signals = HashMap::new();
+        signals.insert("X".to_string(), Fp::from(1));
+        let circuit = Circuit::new(
+            parse_circuit("keccak256(X) == 0xb10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6").unwrap(),
+            signals,
+            vec![],
+        );
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+        assert!(!circuit.uses_range_check_comparisons());
+
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitBoolean(circuit), vec![vec![Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_abs_signed_difference() {
+        use crate::parser::parse_circuit;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // Positive, negative and zero differences
+        for (a, b, magnitude) in [(10u64, 3u64, 7u64), (3, 10, 7), (5, 5, 0)] {
+            let mut signals = HashMap::new();
+            signals.insert("A".to_string(), Fp::from(a));
+            signals.insert("B".to_string(), Fp::from(b));
+
+            let circuit = Circuit::new(parse_circuit("abs(A - B)").unwrap(), signals, vec![]);
+            assert_eq!(circuit.circuit_output, Some(Fp::from(magnitude)), "A={} B={}", a, b);
+            assert_eq!(circuit.max_range_check_bits(), Some(8));
+
+            let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+            let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::from(magnitude)]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "A={} B={}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_declared_signal_bits() {
+        use crate::api::Program;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        let program = |age: u64, bits: usize| Program::from_json(&format!(
+            r#"{{"version":1,"secret":{{"age":{{"value":"{}","bits":{}}}}},"public":{{}},"circuit":["age*2"]}}"#,
+            age, bits
+        )).unwrap();
+
+        // The declared width sizes the range table, not the witness
+        let circuit = Circuit::from_program(&program(5, 16)).unwrap();
+        assert!(circuit.uses_range_check_comparisons());
+        assert_eq!(circuit.max_range_check_bits(), Some(16));
+
+        let circuit = Circuit::from_program(&program(200, 8)).unwrap();
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::from(400)]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // A value past the declared width is rejected before proving
+        let err = Circuit::from_program(&program(300, 8)).err().unwrap();
+        assert_eq!(err.to_string(), "Signal 'age' exceeds its declared 8 bits (value needs 9 bits)");
+        assert!(Circuit::from_program(&program(1, 0)).is_err());
+
+        // A prover bypassing that check cannot satisfy the circuit with an over-width witness
+        let mut circuit = Circuit::from_program(&program(200, 8)).unwrap();
+        circuit.signals.insert("age".to_string(), Fp::from(300));
+        circuit.circuit_output = Some(Fp::from(600));
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::from(600)]]).unwrap();
+        assert!(prover.verify().is_err());
+
+        // Nor with a wrapped negative witness when the width is below the table size:
+        // 5 bits in the 8-bit table, and 8 bits in a table forced to 64 bits
+        for (bits, max_bits) in [(5, None), (8, Some(64))] {
+            let mut circuit = Circuit::from_program(&program(20, bits)).unwrap();
+            if let Some(max_bits) = max_bits {
+                circuit.force_max_bits(max_bits).unwrap();
+            }
+            assert!(circuit.max_range_check_bits().unwrap() > bits);
+            let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+
+            let minus_one = -Fp::one();
+            circuit.signals.insert("age".to_string(), minus_one);
+            circuit.circuit_output = Some(minus_one + minus_one);
+            let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![minus_one + minus_one]]).unwrap();
+            assert!(prover.verify().is_err(), "bits={}", bits);
+        }
+    }
+
+    #[test]
+    fn test_comparison_sized_for_arithmetic_growth() {
+        use crate::api::Program;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        let program = |a: u64, b: u64| Program::from_zircon(&format!("1/A:{},B:{}/C:5/-/A*B>C", a, b)).unwrap();
+
+        // The operands fit 32 bits, but their product needs the 64-bit table
+        let circuit = Circuit::from_program(&program(1 << 20, 1 << 20)).unwrap();
+        assert_eq!(circuit.max_range_check_bits(), Some(64));
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::from(5), Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        // A product past 64 bits cannot be range-checked at all
+        let err = Circuit::from_program(&program(1 << 40, 1 << 40)).err().unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Value exceeds 2^64 for ordering comparison: 'A * B' in 'A * B > C' needs 81 bits"
+        );
+
+        // Unknown operands (a verifier without the secrets) are not checked
+        let mut verifier = program(1 << 40, 1 << 40);
+        verifier.secret.clear();
+        assert!(Circuit::from_program(&verifier).is_ok());
+    }
+
+    #[test]
+    fn test_wide_signal_equality_is_exact() {
+        use crate::api::Program;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // p + 5 and 5: congruent modulo the Pallas base field prime, but not equal
+        let p_plus_5 = "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000006";
+        let five = "0x0000000000000000000000000000000000000000000000000000000000000005";
+
+        let program = |a: &str, b: &str, circuit: &str| Program::from_json(&format!(
+            r#"{{"version":1,"secret":{{"A":{{"value":"{}"}},"B":{{"value":"{}"}}}},"public":{{}},"circuit":["{}"]}}"#,
+            a, b, circuit
+        )).unwrap();
+
+        let circuit = Circuit::from_program(&program(p_plus_5, five, "A == B")).unwrap();
+        assert_eq!(circuit.signals["A"], circuit.signals["B"]);
+        assert_eq!(circuit.wide_signals.get("A"), Some(&2));
+        assert_eq!(circuit.circuit_output, Some(Fp::zero()));
+
+        let circuit = Circuit::from_program(&program(p_plus_5, five, "A != B")).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+
+        let circuit = Circuit::from_program(&program(p_plus_5, p_plus_5, "A == B")).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+
+        // Constants are split the same way
+        let circuit = Circuit::from_program(&program(five, five, "A == 5")).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+
+        let circuit = Circuit::from_program(&program(p_plus_5, five, "A == B")).unwrap();
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::zero()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_preprocessed_outputs_compared_exactly() {
+        use crate::api::Program;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        // h1 = 2^254 XOR 0x2246...06 = p + 5 and h2 = 5, both as 32-byte big-endian values:
+        // different digests that reduce to the same field element
+        let program = |circuit: &str| Program::from_json(&format!(
+            r#"{{"version":1,"secret":{{"A":{{"value":"0x4000000000000000000000000000000000000000000000000000000000000000"}},"B":{{"value":"0x224698fc094cf91b992d30ed00000006"}},"C":{{"value":"5"}}}},"public":{{}},"preprocess":["x<==xor_bytes(A, B)","h1<==reverse(x)","h2<==reverse(C)"],"circuit":["{}"]}}"#,
+            circuit
+        )).unwrap();
+
+        let circuit = Circuit::from_program(&program("h1 == h2")).unwrap();
+        assert_eq!(circuit.signals["h1"], circuit.signals["h2"]);
+        assert_eq!(circuit.wide_signals.get("h1"), Some(&2));
+        assert_eq!(circuit.wide_signals.get("h2"), Some(&2));
+        assert_eq!(circuit.circuit_output, Some(Fp::zero()));
+
+        let circuit = Circuit::from_program(&program("h1 != h2")).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+
+        let circuit = Circuit::from_program(&program("h1 == h1")).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+
+        let circuit = Circuit::from_program(&program("h1 == h2")).unwrap();
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::zero()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_wide_limbs_are_constrained() {
+        use crate::api::Program;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        let p_plus_5 = "0x40000000000000000000000000000000224698fc094cf91b992d30ed00000006";
+        let five = "0x0000000000000000000000000000000000000000000000000000000000000005";
+
+        let prove = |circuit: &Circuit| {
+            let mut instances: Vec<Fp> = circuit.public_signal_names.iter()
+                .flat_map(|name| circuit.public_values(name))
+                .collect();
+            instances.push(circuit.circuit_output.unwrap());
+            let k = estimate_circuit_requirements_with_strategy(circuit, None).k;
+            MockProver::run(k, &CircuitAuto(circuit.clone()), vec![instances]).unwrap().verify()
+        };
+        let forge = |circuit: &mut Circuit, name: &str, limbs: [Fp; 2]| {
+            for (i, limb) in limbs.into_iter().enumerate() {
+                circuit.signals.insert(limb_name(name, i), limb);
+            }
+            circuit.circuit_output = Some(Fp::one());
+        };
+
+        // A field element compared against a wide value: its limbs must recompose to it
+        // within the top limb bound, so it cannot take on the limbs of p + 5
+        let circuit = Circuit::from_program(&Program::from_json(&format!(
+            r#"{{"version":1,"secret":{{"A":{{"value":"5"}},"H":{{"value":"{}"}}}},"public":{{}},"circuit":["A == H"]}}"#,
+            p_plus_5
+        )).unwrap()).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::zero()));
+        assert!(prove(&circuit).is_ok());
+
+        let h_limbs = [circuit.signals["H.limb0"], circuit.signals["H.limb1"]];
+        let mut forged = circuit.clone();
+        forge(&mut forged, "A", h_limbs);
+        assert!(prove(&forged).is_err());
+
+        // A wide public signal is pinned limb by limb by the instance
+        let circuit = Circuit::from_program(&Program::from_json(&format!(
+            r#"{{"version":1,"secret":{{"B":{{"value":"{}"}}}},"public":{{"P":{{"value":"{}"}}}},"circuit":["B == P"]}}"#,
+            five, p_plus_5
+        )).unwrap()).unwrap();
+        assert_eq!(circuit.public_values("P"), vec![circuit.signals["P.limb0"], circuit.signals["P.limb1"]]);
+        assert_eq!(circuit.circuit_output, Some(Fp::zero()));
+        assert!(prove(&circuit).is_ok());
+
+        let mut instances: Vec<Fp> = circuit.public_values("P");
+        instances.push(Fp::one());
+        let b_limbs = [circuit.signals["B.limb0"], circuit.signals["B.limb1"]];
+        let mut forged = circuit.clone();
+        forge(&mut forged, "P", b_limbs);
+        let k = estimate_circuit_requirements_with_strategy(&forged, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(forged), vec![instances]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+
+    #[test]
+    fn test_pub_assignment_prefix() {
+        use crate::api::Program;
+
+        assert_eq!(split_public_assignment("pub total<==A+B"), (true, "total<==A+B"));
+        assert_eq!(split_public_assignment("  pub\thash <== A"), (true, "hash <== A"));
+        assert_eq!(split_public_assignment("public<==A"), (false, "public<==A"));
+        assert_eq!(split_public_assignment("pub > 3"), (false, "pub > 3"));
+
+        // Inline hashes are hoisted out of a `pub` assignment too
+        let program = Program::from_zircon("1/secret:42/out:?/-/pub hash<==sha256(secret);hash != 0").unwrap();
+        let circuit = Circuit::from_program(&program).unwrap();
+        assert_eq!(circuit.public_signal_names, ["hash"]);
+        assert!(circuit.signals.contains_key("hash"));
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+    }
+
+    #[test]
+    fn test_array_sum_and_product() {
+        use crate::api::Program;
+        use crate::circuit::estimate_circuit_requirements_with_strategy;
+        use halo2_proofs::dev::MockProver;
+
+        let program = Program::from_zircon("1/votes:[1,0,1,1],w:5/-/-/sum(votes) == 3").unwrap();
+        let circuit = Circuit::from_program(&program).unwrap();
+        assert_eq!(circuit.array_lengths.get("votes"), Some(&4));
+        assert_eq!(circuit.signals["votes_3"], Fp::one());
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+
+        // sum(votes) is expanded into a balanced tree of additions over the elements
+        let Statement::Expression(expr) = &circuit.statements[0] else { panic!("expected expression") };
+        assert_eq!(expr.to_string(), "votes_0 + votes_1 + (votes_2 + votes_3) == 3");
+
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        let prover = MockProver::run(k, &CircuitAuto(circuit), vec![vec![Fp::one()]]).unwrap();
+        assert_eq!(prover.verify(), Ok(()));
+
+        let program = Program::from_zircon("1/xs:[2,3,7]/-/-/product(xs) + sum(xs)").unwrap();
+        let circuit = Circuit::from_program(&program).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::from(42 + 12)));
+        assert_eq!(evaluate_expression(&crate::parser::parse_circuit("product(xs)").unwrap(), &circuit.signals), Ok(Fp::from(42)));
+
+        // Long arrays stay far below the expression depth limit
+        let values = vec!["1"; 1000].join(",");
+        let program = Program::from_zircon(&format!("1/xs:[{}]/-/-/sum(xs) == 1000", values)).unwrap();
+        let circuit = Circuit::from_program(&program).unwrap();
+        assert_eq!(circuit.circuit_output, Some(Fp::one()));
+        let Statement::Expression(expr) = &circuit.statements[0] else { panic!("expected expression") };
+        assert_eq!(expr.depth(), 12);
+
+        // Reductions need an array, and arrays need elements
+        let program = Program::from_zircon("1/x:5/-/-/sum(x) > 1").unwrap();
+        assert!(Circuit::from_program(&program).err().unwrap().to_string().contains("single value"));
+        let program = Program::from_zircon("1/xs:[]/-/-/sum(xs) > 1").unwrap();
+        assert!(Circuit::from_program(&program).err().unwrap().to_string().contains("empty"));
+    }
+
+    #[test]
+    fn test_not_in_blocklist() {
+        use crate::parser::parse_circuit;
+        use halo2_proofs::dev::MockProver;
+
+        // 1 when addr matches no blocked value, 0 when it is one of them
+        for (addr, expected) in [(7u64, 1u64), (0, 1), (42, 0), (1000, 0)] {
+            let mut signals = HashMap::new();
+            signals.insert("addr".to_string(), Fp::from(addr));
+            signals.insert("blocked".to_string(), Fp::from(1000));
+
+            let expr = parse_circuit("not_in(addr, [13, 42, blocked])").unwrap();
+            assert_eq!(evaluate_expression(&expr, &signals), Ok(Fp::from(expected)), "addr={}", addr);
+
+            let circuit = Circuit::new(expr, signals, vec![]);
+            assert!(!circuit.uses_range_check_comparisons());
+
+            let prover = MockProver::run(8, &CircuitBoolean(circuit), vec![vec![Fp::from(expected)]]).unwrap();
+            assert_eq!(prover.verify(), Ok(()), "addr={}", addr);
+        }
+    }
+
+    #[test]
+    fn test_approx_eq_boundary() {
+        use crate::parser::parse_circuit;
+
+        // |A - B| == tol is within tolerance, one more is not, in either direction
+        for (a, b, expected) in [(100u64, 97u64, 1u64), (97, 100, 1), (100, 96, 0), (96, 100, 0)] {
+            let mut signals = HashMap::new();
+            signals.insert("A".to_string(), Fp::from(a));
+            signals.insert("B".to_string(), Fp::from(b));
+            signals.insert("tol".to_string(), Fp::from(3));
+
+            let expr = parse_circuit("approx_eq(A, B, tol)").unwrap();
+            assert_eq!(evaluate_expression(&expr, &signals), Ok(Fp::from(expected)), "A={} B={}", a, b);
+
+            let circuit = Circuit::new(expr, signals, vec![]);
+            assert!(circuit.uses_range_check_comparisons());
+            assert_eq!(circuit.circuit_output, Some(Fp::from(expected)));
+        }
+
+        // Bit sizing covers the tolerance, not just the operands
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), Fp::from(5));
+        signals.insert("B".to_string(), Fp::from(3));
+        signals.insert("tol".to_string(), Fp::from(1 << 40));
+        let circuit = Circuit::new(parse_circuit("approx_eq(A, B, tol)").unwrap(), signals, vec![]);
+        assert!(circuit.max_range_check_bits().unwrap() > 40);
+    }
+
+    #[test]
+    fn test_abs_tolerance_check() {
+        use crate::parser::parse_circuit;
+
+        for (a, b, within) in [(100u64, 98u64, 1u64), (98, 100, 1), (100, 97, 0), (97, 100, 0)] {
+            let mut signals = HashMap::new();
+            signals.insert("A".to_string(), Fp::from(a));
+            signals.insert("B".to_string(), Fp::from(b));
+
+            let circuit = Circuit::new(parse_circuit("abs(A - B) <= 2").unwrap(), signals, vec![]);
+            assert_eq!(circuit.circuit_output, Some(Fp::from(within)), "A={} B={}", a, b);
+        }
+    }
+}
@@ -3,9 +3,10 @@
 //! Provides hardware-independent metrics for circuit requirements.
 
 use crate::api::EstimateResponse;
-use crate::circuit::Circuit;
+use crate::circuit::{Circuit, Statement, KECCAK_ROWS};
 use crate::circuit::strategy::Strategy;
-use crate::parser::Expression;
+use crate::parser::{BinaryOperator, BuiltinFunction, Expression};
+use std::collections::HashMap;
 
 /// Estimate circuit requirements with optional strategy
 ///
@@ -26,14 +27,31 @@ pub fn estimate_circuit_requirements_with_strategy(
     let mut cheap_comparison_count = 0;  // ==, != (use is_zero gadget)
     let mut expensive_comparison_count = 0;  // >, <, >=, <= (use range checks)
 
-    // Count operations in the main expression
-    if let Some(expr) = &circuit.expression {
+    let mut popcount_count = 0;
+    let mut keccak_count = 0;
+
+    // Count operations in the main expression and every circuit statement
+    let statement_exprs = circuit.statements.iter().map(|stmt| match stmt {
+        Statement::Assignment { expression, .. } => expression,
+        Statement::Expression(expression) => expression,
+    });
+    for expr in circuit.expression.iter().chain(statement_exprs) {
         let (ops, cheap_comps, expensive_comps) = count_operations(expr);
         operation_count += ops;
         cheap_comparison_count += cheap_comps;
         expensive_comparison_count += expensive_comps;
+
+        popcount_count += count_calls(expr, BuiltinFunction::Popcount);
+        keccak_count += count_calls(expr, BuiltinFunction::Keccak256);
     }
 
+    // Each declared signal width is enforced with range-checked comparisons: one
+    // against 2^bits - 1, plus one against the table size when the width is smaller
+    let table_bits = circuit.max_range_check_bits().unwrap_or(64);
+    expensive_comparison_count += circuit.signal_bits.values()
+        .map(|&bits| if bits < table_bits { 2 } else { 1 })
+        .sum::<u32>();
+
     let preprocess_count = circuit.statements.len() as u32;
     let total_comparisons = cheap_comparison_count + expensive_comparison_count;
 
@@ -137,8 +155,20 @@ pub fn estimate_circuit_requirements_with_strategy(
         Strategy::Auto => expensive_comparison_count * 25,  // Auto: use balanced estimate
     };
 
+    // popcount decomposes its argument into max_bits bits (64 when unknown):
+    // per bit one witness, one booleanity mul, two recompose adds and one count add
+    let popcount_rows = popcount_count * max_bits.unwrap_or(64) as u32 * 5;
+
+    // keccak256 runs the full Keccak-f[1600] permutation bit by bit
+    let keccak_rows = keccak_count * KECCAK_ROWS;
+
+    // Limbs of wide values are range-checked by bit decomposition: per bit
+    // one witness, one booleanity mul and two recompose adds
+    let limb_rows = circuit.limb_bits() as u32 * 4;
+
     // Add 25% safety margin to estimated rows
-    let estimated_rows_raw = base_overhead + op_rows + cheap_comparison_rows + expensive_comparison_rows;
+    let estimated_rows_raw = base_overhead + op_rows + cheap_comparison_rows + expensive_comparison_rows
+        + popcount_rows + keccak_rows + limb_rows;
     let estimated_rows = (estimated_rows_raw * 5) / 4;  // +25% safety margin
 
     // Find minimum k where 2^k >= estimated_rows
@@ -199,10 +229,55 @@ pub fn estimate_circuit_requirements_with_strategy(
         params_size_bytes,
         proof_size_bytes,
         vk_size_bytes,
+        critical_path_depth: critical_path_depth(circuit),
         complexity,
     }
 }
 
+/// Longest chain of operations that must be computed one after another
+///
+/// Each operator, comparison and function call on the path counts as one
+/// step; signals and constants count as none. A signal assigned by an earlier
+/// statement carries the depth of its assignment, so chains are followed
+/// across statements.
+///
+/// `((A + B) + C) + D` has depth 3, the balanced `(A + B) + (C + D)` depth 2.
+pub fn critical_path_depth(circuit: &Circuit) -> u32 {
+    let mut assigned: HashMap<&str, u32> = HashMap::new();
+    let mut depth = 0;
+
+    for stmt in &circuit.statements {
+        match stmt {
+            Statement::Assignment { name, expression } => {
+                let assignment_depth = operation_depth(expression, &assigned);
+                depth = depth.max(assignment_depth);
+                assigned.insert(name, assignment_depth);
+            }
+            Statement::Expression(expression) => depth = depth.max(operation_depth(expression, &assigned)),
+        }
+    }
+
+    if let Some(expr) = &circuit.expression {
+        depth = depth.max(operation_depth(expr, &assigned));
+    }
+
+    depth
+}
+
+fn operation_depth(expr: &Expression, assigned: &HashMap<&str, u32>) -> u32 {
+    match expr {
+        Expression::Variable(name) => assigned.get(name.as_str()).copied().unwrap_or(0),
+        Expression::Constant(_) | Expression::Boolean(_) => 0,
+        Expression::UnaryOp { operand, .. } => 1 + operation_depth(operand, assigned),
+        Expression::Call { argument, .. } => 1 + operation_depth(argument, assigned),
+        Expression::BinaryOp { left, right, .. }
+        | Expression::Comparison { left, right, .. }
+        | Expression::BooleanOp { left, right, .. } => {
+            1 + operation_depth(left, assigned).max(operation_depth(right, assigned))
+        }
+    }
+}
+
 /// Count operations in an expression tree
 ///
 /// Returns (total_operations, cheap_comparisons, expensive_comparisons)
@@ -215,13 +290,17 @@ fn count_operations(expr: &Expression) -> (u32, u32, u32) {
     match expr {
         Expression::Constant(_) | Expression::Variable(_) | Expression::Boolean(_) => (1, 0, 0),
 
-        Expression::BinaryOp { left, right, .. } => {
+        Expression::BinaryOp { op, left, right } => {
             let (left_ops, left_cheap, left_expensive) = count_operations(left);
             let (right_ops, right_cheap, right_expensive) = count_operations(right);
+
+            // Integer division range-checks q and r themselves, r < b and q <= a
+            let new_expensive = if *op == BinaryOperator::IntDiv { 4 } else { 0 };
+
             (
                 2 + left_ops + right_ops,
                 left_cheap + right_cheap,
-                left_expensive + right_expensive
+                left_expensive + right_expensive + new_expensive
             )
         }
 
@@ -260,6 +339,42 @@ fn count_operations(expr: &Expression) -> (u32, u32, u32) {
             let (ops, cheap, expensive) = count_operations(operand);
             (1 + ops, cheap, expensive)
         }
+
+        Expression::Call { function, argument } => {
+            let (ops, cheap, expensive) = count_operations(argument);
+
+            match function {
+                // Square root range-checks r <= x, r*r <= x and x - r*r <= 2r;
+                // is_square adds an is_zero equality on top
+                BuiltinFunction::Sqrt => (4 + ops, cheap, expensive + 3),
+                BuiltinFunction::IsSquare => (4 + ops, cheap + 1, expensive + 3),
+                // One multiplication gate plus the row copying x * x onto x
+                BuiltinFunction::IsBool => (2 + ops, cheap, expensive),
+                // Bit decomposition rows depend on max_bits, see count_calls
+                BuiltinFunction::Popcount => (2 + ops, cheap, expensive),
+                // Permutation rows are counted separately, see count_calls
+                BuiltinFunction::Keccak256 => (1 + ops, cheap, expensive),
+                // Sign mux plus one range check on the magnitude
+                BuiltinFunction::Abs => (4 + ops, cheap, expensive + 1),
+                // Expanded into add/mul gates when the circuit is built
+                BuiltinFunction::Sum | BuiltinFunction::Product => (ops, cheap, expensive),
+            }
+        }
+    }
+}
+
+/// Count calls to a built-in function in an expression tree
+fn count_calls(expr: &Expression, target: BuiltinFunction) -> u32 {
+    match expr {
+        Expression::Constant(_) | Expression::Variable(_) | Expression::Boolean(_) => 0,
+        Expression::BinaryOp { left, right, .. }
+        | Expression::Comparison { left, right, .. }
+        | Expression::BooleanOp { left, right, .. } => count_calls(left, target) + count_calls(right, target),
+        Expression::UnaryOp { operand, .. } => count_calls(operand, target),
+        Expression::Call { function, argument } => {
+            let own = if *function == target { 1 } else { 0 };
+            own + count_calls(argument, target)
+        }
     }
 }
 
@@ -285,6 +400,27 @@ mod tests {
         assert_eq!(estimate.complexity, "Very Simple");
     }
 
+    #[test]
+    fn test_statements_are_counted() {
+        use crate::circuit::CircuitBuilder;
+        use crate::parser::parse_circuit;
+        use halo2_proofs::pasta::Fp;
+
+        let mut builder = CircuitBuilder::new()
+            .add_secret("A", Fp::from(3))
+            .add_secret("B", Fp::from(4));
+        for i in 0..10 {
+            builder = builder.assign(format!("x{}", i), parse_circuit("A * B + A * B").unwrap());
+        }
+        let circuit = builder.statement(parse_circuit("x9 == 24").unwrap()).build().unwrap();
+
+        // Counting only the (absent) main expression gave the 48-row base and k=8;
+        // the 104 operations and one equality across the statements need k=10
+        let estimate = estimate_circuit_requirements_with_strategy(&circuit, None);
+        assert_eq!(estimate.operation_count, 104);
+        assert_eq!(estimate.k, 10);
+    }
+
     #[test]
     fn test_comparison_circuit_estimate() {
         use crate::parser::parse_circuit;
@@ -356,4 +492,81 @@ mod tests {
         assert!(est2.k >= est1.k);
         assert!(est2.operation_count > est1.operation_count);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_large_equality_does_not_raise_k() {
+        use crate::parser::parse_circuit;
+        use halo2_proofs::pasta::Fp;
+
+        let key = Fp::from_raw([u64::MAX, u64::MAX, u64::MAX, 0x0fff_ffff_ffff_ffff]);
+        let mut signals = HashMap::new();
+        signals.insert("key256".to_string(), key);
+        signals.insert("t".to_string(), key);
+        signals.insert("age".to_string(), Fp::from(25));
+
+        let mixed = Circuit::new(
+            parse_circuit("(key256 == t) AND (age > 18)").unwrap(),
+            signals.clone(),
+            vec![],
+        );
+        let ordering_only = Circuit::new(parse_circuit("age > 18").unwrap(), signals, vec![]);
+
+        let mixed_estimate = estimate_circuit_requirements_with_strategy(&mixed, None);
+        let ordering_estimate = estimate_circuit_requirements_with_strategy(&ordering_only, None);
+
+        assert_eq!(mixed_estimate.k, ordering_estimate.k);
+    }
+
+    #[test]
+    fn test_critical_path_depth() {
+        use crate::parser::parse_circuit;
+
+        let depth = |circuit: &str| {
+            critical_path_depth(&Circuit::new(parse_circuit(circuit).unwrap(), HashMap::new(), vec![]))
+        };
+
+        assert_eq!(depth("A"), 0);
+        assert_eq!(depth("((((((A + B) + C) + D) + E) + F) + G) + H"), 7);
+        assert_eq!(depth("((A + B) + (C + D)) + ((E + F) + (G + H))"), 3);
+
+        // Same operation count, different depth
+        let chain = estimate_circuit_requirements_with_strategy(
+            &Circuit::new(parse_circuit("(((A + B) + C) + D) > E").unwrap(), HashMap::new(), vec![]),
+            None,
+        );
+        let balanced = estimate_circuit_requirements_with_strategy(
+            &Circuit::new(parse_circuit("((A + B) + (C + D)) > E").unwrap(), HashMap::new(), vec![]),
+            None,
+        );
+        assert_eq!(chain.operation_count, balanced.operation_count);
+        assert_eq!(chain.critical_path_depth, 4);
+        assert_eq!(balanced.critical_path_depth, 3);
+    }
+
+    #[test]
+    fn test_critical_path_follows_assignments() {
+        use crate::api::Program;
+
+        let program = Program::from_zircon("1/A:1,B:2,C:3/out:?/-/s<==A+B;t<==s*C;t>B").unwrap();
+        let circuit = Circuit::from_program(&program).unwrap();
+
+        // (A + B), then * C, then > B
+        assert_eq!(critical_path_depth(&circuit), 3);
+    }
+
+    #[test]
+    fn test_identity_operations_are_not_counted() {
+        use crate::api::Program;
+
+        // from_program simplifies `(A + 0) * 1` to `A` before estimating
+        let estimate = |circuit: &str| {
+            let program = Program::from_zircon(&format!("1/A:10,B:5/out:?/-/{}", circuit)).unwrap();
+            estimate_circuit_requirements_with_strategy(&Circuit::from_program(&program).unwrap(), None)
+        };
+
+        let padded = estimate("(A + 0) * 1 > B");
+        let plain = estimate("A > B");
+        assert_eq!(padded.operation_count, plain.operation_count);
+        assert_eq!(padded.k, plain.k);
+    }
+}
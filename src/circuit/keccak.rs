@@ -0,0 +1,418 @@
+//! Keccak-256 gadget
+//!
+//! Proves `keccak256(x)` inside the circuit, so the hash is bound by the proof
+//! instead of being trusted preprocessing output.
+//!
+//! # Encoding
+//!
+//! - Input: `x` as a 32-byte big-endian integer, the same bytes Solidity hashes
+//!   for `keccak256(abi.encode(uint256(x)))`. `x` must be below 2^254 so its
+//!   bit decomposition is unique in the field.
+//! - Output: the 32-byte digest read as a big-endian integer and reduced modulo
+//!   the field, the same conversion hash preprocessing outputs go through. A
+//!   commitment given as a hex signal therefore compares directly with `==`.
+//!
+//! # Cost
+//!
+//! Keccak-f[1600] runs 24 rounds over 1600 state bits, one row per bit
+//! operation (XOR, AND-NOT, NOT). Bits that are still constants (padding,
+//! round constants) are folded at synthesis time, but the full permutation
+//! still needs about [`KECCAK_ROWS`] rows per call, i.e. k=18 on its own.
+
+use halo2_proofs::{
+    circuit::{AssignedCell, Layouter, Value},
+    pasta::Fp,
+    plonk::{Advice, Column, ConstraintSystem, Error, Expression, Selector},
+    poly::Rotation,
+};
+use ff::PrimeField;
+use sha3::{Digest, Keccak256};
+use super::CircuitConfig;
+
+/// Approximate rows used by one `keccak256` call
+///
+/// 24 rounds x ~6400 bitwise gates, plus input decomposition and digest recomposition.
+pub const KECCAK_ROWS: u32 = 155_000;
+
+/// Bits of the input that may be set (x < 2^254)
+const INPUT_BITS: usize = 254;
+
+/// Rate of Keccak-256 in bytes (1088 bits)
+const RATE_BYTES: usize = 136;
+
+/// Round constants for the iota step
+const ROUND_CONSTANTS: [u64; 24] = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+];
+
+/// Rotation offsets for the rho step, indexed [x][y]
+const ROTATION_OFFSETS: [[usize; 5]; 5] = [
+    [0, 36, 3, 41, 18],
+    [1, 44, 10, 45, 2],
+    [62, 6, 43, 15, 61],
+    [28, 55, 25, 21, 56],
+    [27, 20, 39, 8, 14],
+];
+
+/// Selectors for the one-row bitwise gates
+///
+/// All gates use advice columns [a, b, output] like the add and mul gates,
+/// and assume a and b are bits.
+#[derive(Debug, Clone)]
+pub struct BitwiseConfig {
+    /// a XOR b = a + b - 2ab
+    pub s_xor: Selector,
+    /// (NOT a) AND b = (1 - a) * b
+    pub s_andn: Selector,
+    /// NOT a = 1 - a
+    pub s_not: Selector,
+}
+
+impl BitwiseConfig {
+    /// Create the bitwise gates over the given advice columns
+    pub fn configure(meta: &mut ConstraintSystem<Fp>, advice: &[Column<Advice>]) -> Self {
+        let s_xor = meta.selector();
+        let s_andn = meta.selector();
+        let s_not = meta.selector();
+
+        meta.create_gate("xor_gate", |meta| {
+            let s = meta.query_selector(s_xor);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            // Enforce: a + b - 2ab = c
+            vec![s * (a.clone() + b.clone() - Expression::Constant(Fp::from(2)) * a * b - c)]
+        });
+
+        meta.create_gate("andn_gate", |meta| {
+            let s = meta.query_selector(s_andn);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let b = meta.query_advice(advice[1], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            // Enforce: (1 - a) * b = c
+            vec![s * ((Expression::Constant(Fp::one()) - a) * b - c)]
+        });
+
+        meta.create_gate("not_gate", |meta| {
+            let s = meta.query_selector(s_not);
+            let a = meta.query_advice(advice[0], Rotation::cur());
+            let c = meta.query_advice(advice[2], Rotation::cur());
+
+            // Enforce: a + c = 1
+            vec![s * (a + c - Expression::Constant(Fp::one()))]
+        });
+
+        Self { s_xor, s_andn, s_not }
+    }
+}
+
+/// Compute `keccak256(x)` natively with the gadget's encoding
+///
+/// Used for witness generation and `evaluate_expression`.
+pub fn keccak256_field(x: &Fp) -> Result<Fp, String> {
+    let repr = x.to_repr();
+    if repr.as_ref()[31] & 0xc0 != 0 {
+        return Err("keccak256 argument must be below 2^254".to_string());
+    }
+
+    // Field repr is little-endian, the preimage is big-endian
+    let mut preimage = repr.as_ref().to_vec();
+    preimage.reverse();
+
+    let digest = Keccak256::digest(&preimage);
+    Ok(digest.iter().fold(Fp::zero(), |acc, byte| acc * Fp::from(256) + Fp::from(*byte as u64)))
+}
+
+/// Keccak state bit: a constant known at synthesis time, or an assigned cell
+#[derive(Clone)]
+enum Bit {
+    Const(bool),
+    Cell(AssignedCell<Fp, Fp>),
+}
+
+/// Chip that lays out the Keccak-256 gadget
+pub(super) struct KeccakChip<'a> {
+    config: &'a CircuitConfig,
+}
+
+impl<'a> KeccakChip<'a> {
+    pub(super) fn new(config: &'a CircuitConfig) -> Self {
+        Self { config }
+    }
+
+    /// Constrain `keccak256(x)` and return the digest as a field element
+    pub(super) fn keccak256(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        let input_bits = self.decompose(layouter.namespace(|| "decompose_input"), x)?;
+
+        // Absorb the single padded block: 32 message bytes, then 0x01 ... 0x80
+        let mut state = vec![Bit::Const(false); 1600];
+        for (integer_bit, bit) in input_bits.into_iter().enumerate() {
+            // Big-endian byte i holds integer bits 8*(31-i)..8*(31-i)+8
+            let byte = 31 - integer_bit / 8;
+            state[8 * byte + integer_bit % 8] = bit;
+        }
+        state[8 * 32] = Bit::Const(true);
+        state[8 * RATE_BYTES - 1] = Bit::Const(true);
+
+        for (round, round_constant) in ROUND_CONSTANTS.iter().enumerate() {
+            state = self.round(layouter.namespace(|| format!("round_{}", round)), state, *round_constant)?;
+        }
+
+        // Digest bytes are the first 32 state bytes, read as a big-endian integer
+        let mut acc: Option<AssignedCell<Fp, Fp>> = None;
+        for integer_bit in (0..256).rev() {
+            let byte = 31 - integer_bit / 8;
+            let bit = match &state[8 * byte + integer_bit % 8] {
+                Bit::Cell(cell) => cell.clone(),
+                // Unreachable: after the first two rounds every state bit depends on the input
+                Bit::Const(_) => return Err(Error::Synthesis),
+            };
+
+            acc = Some(match acc {
+                None => bit,
+                Some(acc) => {
+                    let doubled = self.add(layouter.namespace(|| format!("digest_double_{}", integer_bit)), &acc, &acc)?;
+                    self.add(layouter.namespace(|| format!("digest_bit_{}", integer_bit)), &doubled, &bit)?
+                }
+            });
+        }
+
+        acc.ok_or(Error::Synthesis)
+    }
+
+    /// Decompose x into 256 bits (least significant first), the top two fixed to 0
+    ///
+    /// Each witnessed bit is constrained with b * b == b, and the bits are
+    /// recomposed (acc = 2 * acc + b) and constrained equal to x.
+    fn decompose(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        x: &AssignedCell<Fp, Fp>,
+    ) -> Result<Vec<Bit>, Error> {
+        let advice = &self.config.advice;
+
+        let mut cells = Vec::with_capacity(INPUT_BITS);
+        for i in 0..INPUT_BITS {
+            let value = x.value().map(|v| Fp::from(((v.to_repr().as_ref()[i / 8] >> (i % 8)) & 1) as u64));
+            let cell = layouter.assign_region(
+                || format!("input_bit_{}", i),
+                |mut region| {
+                    // b * b == b  <=>  b is 0 or 1
+                    self.config.s_mul.enable(&mut region, 0)?;
+                    let bit = region.assign_advice(|| "bit", advice[0], 0, || value)?;
+                    bit.copy_advice(|| "bit", &mut region, advice[1], 0)?;
+                    bit.copy_advice(|| "bit", &mut region, advice[2], 0)?;
+                    Ok(bit)
+                },
+            )?;
+            cells.push(cell);
+        }
+
+        let mut acc = cells[INPUT_BITS - 1].clone();
+        for (i, bit) in cells.iter().enumerate().rev().skip(1) {
+            let doubled = self.add(layouter.namespace(|| format!("input_double_{}", i)), &acc, &acc)?;
+            acc = self.add(layouter.namespace(|| format!("input_bit_{}", i)), &doubled, bit)?;
+        }
+        layouter.assign_region(
+            || "input_recompose",
+            |mut region| region.constrain_equal(acc.cell(), x.cell()),
+        )?;
+
+        let mut bits: Vec<Bit> = cells.into_iter().map(Bit::Cell).collect();
+        bits.resize(256, Bit::Const(false));
+        Ok(bits)
+    }
+
+    /// One Keccak-f[1600] round (theta, rho, pi, chi, iota)
+    ///
+    /// State bit (x, y, z) is at index 64 * (x + 5 * y) + z.
+    fn round(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        state: Vec<Bit>,
+        round_constant: u64,
+    ) -> Result<Vec<Bit>, Error> {
+        let idx = |x: usize, y: usize, z: usize| 64 * (x + 5 * y) + z;
+
+        // theta: column parities C[x][z], then D[x][z] = C[x-1][z] ^ C[x+1][z-1]
+        let mut parity = Vec::with_capacity(320);
+        for x in 0..5 {
+            for z in 0..64 {
+                let mut c = state[idx(x, 0, z)].clone();
+                for y in 1..5 {
+                    c = self.xor(layouter.namespace(|| "theta_parity"), &c, &state[idx(x, y, z)])?;
+                }
+                parity.push(c);
+            }
+        }
+
+        let mut theta = state;
+        for x in 0..5 {
+            for z in 0..64 {
+                let d = self.xor(
+                    layouter.namespace(|| "theta_d"),
+                    &parity[64 * ((x + 4) % 5) + z],
+                    &parity[64 * ((x + 1) % 5) + (z + 63) % 64],
+                )?;
+                for y in 0..5 {
+                    theta[idx(x, y, z)] = self.xor(layouter.namespace(|| "theta_apply"), &theta[idx(x, y, z)], &d)?;
+                }
+            }
+        }
+
+        // rho and pi: B[y][2x+3y] = rotate_left(A[x][y], r[x][y]) (wiring only)
+        let mut permuted = vec![Bit::Const(false); 1600];
+        for x in 0..5 {
+            for y in 0..5 {
+                let rotation = ROTATION_OFFSETS[x][y];
+                for z in 0..64 {
+                    permuted[idx(y, (2 * x + 3 * y) % 5, (z + rotation) % 64)] = theta[idx(x, y, z)].clone();
+                }
+            }
+        }
+
+        // chi: A[x][y] = B[x][y] ^ (!B[x+1][y] & B[x+2][y])
+        let mut chi = Vec::with_capacity(1600);
+        for y in 0..5 {
+            for x in 0..5 {
+                for z in 0..64 {
+                    let masked = self.andn(
+                        layouter.namespace(|| "chi_andn"),
+                        &permuted[idx((x + 1) % 5, y, z)],
+                        &permuted[idx((x + 2) % 5, y, z)],
+                    )?;
+                    chi.push(self.xor(layouter.namespace(|| "chi_xor"), &permuted[idx(x, y, z)], &masked)?);
+                }
+            }
+        }
+
+        // iota: flip the bits of lane (0, 0) set in the round constant
+        for (z, bit) in chi.iter_mut().take(64).enumerate() {
+            if (round_constant >> z) & 1 == 1 {
+                *bit = self.not(layouter.namespace(|| "iota"), bit)?;
+            }
+        }
+
+        Ok(chi)
+    }
+
+    /// a XOR b, folding constants
+    fn xor(&self, layouter: impl Layouter<Fp>, a: &Bit, b: &Bit) -> Result<Bit, Error> {
+        match (a, b) {
+            (Bit::Const(p), Bit::Const(q)) => Ok(Bit::Const(p ^ q)),
+            (Bit::Const(false), other) | (other, Bit::Const(false)) => Ok(other.clone()),
+            (Bit::Const(true), other) | (other, Bit::Const(true)) => self.not(layouter, other),
+            (Bit::Cell(a), Bit::Cell(b)) => {
+                let value = a.value().zip(b.value()).map(|(a, b)| *a + *b - (*a * *b).double());
+                self.bitwise_gate(layouter, "xor", self.config.bitwise.s_xor, a, b, value)
+            }
+        }
+    }
+
+    /// (NOT a) AND b, folding constants
+    fn andn(&self, layouter: impl Layouter<Fp>, a: &Bit, b: &Bit) -> Result<Bit, Error> {
+        match (a, b) {
+            (Bit::Const(true), _) | (_, Bit::Const(false)) => Ok(Bit::Const(false)),
+            (Bit::Const(false), other) => Ok(other.clone()),
+            (other, Bit::Const(true)) => self.not(layouter, other),
+            (Bit::Cell(a), Bit::Cell(b)) => {
+                let value = a.value().zip(b.value()).map(|(a, b)| (Fp::one() - a) * b);
+                self.bitwise_gate(layouter, "andn", self.config.bitwise.s_andn, a, b, value)
+            }
+        }
+    }
+
+    /// NOT a, folding constants
+    fn not(&self, mut layouter: impl Layouter<Fp>, a: &Bit) -> Result<Bit, Error> {
+        match a {
+            Bit::Const(p) => Ok(Bit::Const(!p)),
+            Bit::Cell(a) => {
+                let cell = layouter.assign_region(
+                    || "not",
+                    |mut region| {
+                        self.config.bitwise.s_not.enable(&mut region, 0)?;
+                        let a_val = a.copy_advice(|| "operand", &mut region, self.config.advice[0], 0)?;
+                        region.assign_advice(|| "output", self.config.advice[2], 0, || a_val.value().map(|a| Fp::one() - a))
+                    },
+                )?;
+                Ok(Bit::Cell(cell))
+            }
+        }
+    }
+
+    /// Lay out a two-input bitwise gate: [a, b, output]
+    fn bitwise_gate(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        name: &'static str,
+        selector: Selector,
+        a: &AssignedCell<Fp, Fp>,
+        b: &AssignedCell<Fp, Fp>,
+        value: Value<Fp>,
+    ) -> Result<Bit, Error> {
+        let cell = layouter.assign_region(
+            || name,
+            |mut region| {
+                selector.enable(&mut region, 0)?;
+                a.copy_advice(|| "lhs", &mut region, self.config.advice[0], 0)?;
+                b.copy_advice(|| "rhs", &mut region, self.config.advice[1], 0)?;
+                region.assign_advice(|| "output", self.config.advice[2], 0, || value)
+            },
+        )?;
+        Ok(Bit::Cell(cell))
+    }
+
+    /// a + b with the add gate
+    fn add(
+        &self,
+        mut layouter: impl Layouter<Fp>,
+        a: &AssignedCell<Fp, Fp>,
+        b: &AssignedCell<Fp, Fp>,
+    ) -> Result<AssignedCell<Fp, Fp>, Error> {
+        layouter.assign_region(
+            || "add",
+            |mut region| {
+                self.config.s_add.enable(&mut region, 0)?;
+                let a_val = a.copy_advice(|| "lhs", &mut region, self.config.advice[0], 0)?;
+                let b_val = b.copy_advice(|| "rhs", &mut region, self.config.advice[1], 0)?;
+                region.assign_advice(|| "output", self.config.advice[2], 0, || a_val.value().copied() + b_val.value().copied())
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keccak256_field_known_answers() {
+        // keccak256(abi.encode(uint256(0))) and keccak256(abi.encode(uint256(1)))
+        for (x, digest) in [
+            (0u64, "290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"),
+            (1, "b10e2d527612073b26eecdfd717e6a320cf44b4afac2b0732d9fcbe2b7fa0cf6"),
+        ] {
+            let expected = hex::decode(digest).unwrap()
+                .iter()
+                .fold(Fp::zero(), |acc, byte| acc * Fp::from(256) + Fp::from(*byte as u64));
+            assert_eq!(keccak256_field(&Fp::from(x)).unwrap(), expected, "x={}", x);
+        }
+    }
+
+    #[test]
+    fn test_keccak256_field_rejects_wide_input() {
+        let wide = -Fp::one();
+        assert!(keccak256_field(&wide).is_err());
+    }
+}
@@ -0,0 +1,396 @@
+//! Circuit linter
+//!
+//! Detects sub-expressions that are always true or always false. They still
+//! cost rows in the circuit and usually indicate a mistake in the program.
+//!
+//! Contradictions are found with a heuristic, not a solver: only comparisons
+//! joined directly by `AND` are checked against each other, and only when they
+//! compare the same two operands or the same operand with constants.
+
+use crate::circuit::{Circuit, Statement};
+use crate::parser::{BooleanOperator, ComparisonOperator, Expression};
+use num_bigint::BigUint;
+use num_traits::One;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Category of a lint warning
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintKind {
+    /// Comparison between two constant-only operands (e.g. `5 > 3`)
+    ConstantComparison,
+    /// Comparison of an expression with itself (e.g. `A == A`)
+    SelfComparison,
+    /// Boolean operation with a constant operand (e.g. `A > 5 AND true`)
+    ConstantBooleanOperand,
+    /// Conjunction of comparisons that cannot all hold (e.g. `A > B AND A < B`)
+    Contradiction,
+}
+
+/// A single lint finding with the offending sub-expression
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintWarning {
+    /// Lint category
+    pub kind: LintKind,
+    /// The offending sub-expression, rendered as text
+    pub expression: String,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.message, self.expression)
+    }
+}
+
+/// Lint an expression for always-true/always-false sub-expressions
+///
+/// # Example
+///
+/// ```ignore
+/// let expr = parse_circuit("A == A AND B > 5")?;
+/// let warnings = lint(&expr);
+/// assert_eq!(warnings[0].kind, LintKind::SelfComparison);
+/// ```
+pub fn lint(expr: &Expression) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    lint_expr(expr, &mut warnings);
+    lint_contradictions(expr, &mut warnings);
+    warnings
+}
+
+/// Lint the main expression and every statement of a circuit
+pub fn lint_circuit(circuit: &Circuit) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    if let Some(expr) = &circuit.expression {
+        lint_expr(expr, &mut warnings);
+        lint_contradictions(expr, &mut warnings);
+    }
+
+    for stmt in &circuit.statements {
+        let expr = match stmt {
+            Statement::Assignment { expression, .. } => expression,
+            Statement::Expression(expression) => expression,
+        };
+        lint_expr(expr, &mut warnings);
+        lint_contradictions(expr, &mut warnings);
+    }
+
+    warnings
+}
+
+fn lint_expr(expr: &Expression, warnings: &mut Vec<LintWarning>) {
+    match expr {
+        Expression::Comparison { left, right, .. } => {
+            if is_constant(left) && is_constant(right) {
+                warnings.push(LintWarning {
+                    kind: LintKind::ConstantComparison,
+                    expression: expr.to_string(),
+                    message: "Comparison of constants is always true or always false".to_string(),
+                });
+            } else if left == right {
+                warnings.push(LintWarning {
+                    kind: LintKind::SelfComparison,
+                    expression: expr.to_string(),
+                    message: "Expression compared with itself is always true or always false".to_string(),
+                });
+            }
+
+            lint_expr(left, warnings);
+            lint_expr(right, warnings);
+        }
+
+        Expression::BooleanOp { left, right, .. } => {
+            if is_constant(left) || is_constant(right) {
+                warnings.push(LintWarning {
+                    kind: LintKind::ConstantBooleanOperand,
+                    expression: expr.to_string(),
+                    message: "Boolean operation has a constant operand".to_string(),
+                });
+            }
+
+            lint_expr(left, warnings);
+            lint_expr(right, warnings);
+        }
+
+        Expression::BinaryOp { left, right, .. } => {
+            lint_expr(left, warnings);
+            lint_expr(right, warnings);
+        }
+
+        Expression::UnaryOp { operand, .. } => lint_expr(operand, warnings),
+        Expression::Call { argument, .. } => lint_expr(argument, warnings),
+
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => {}
+    }
+}
+
+/// An expression is constant if it references no signals
+fn is_constant(expr: &Expression) -> bool {
+    expr.variables().is_empty()
+}
+
+/// Check every `AND` chain in `expr` for comparisons that cannot all hold
+fn lint_contradictions(expr: &Expression, warnings: &mut Vec<LintWarning>) {
+    match expr {
+        Expression::BooleanOp { op: BooleanOperator::And, .. } => {
+            let mut conjuncts = Vec::new();
+            collect_conjuncts(expr, &mut conjuncts);
+
+            if let Some(conflicting) = find_contradiction(&conjuncts) {
+                warnings.push(LintWarning {
+                    kind: LintKind::Contradiction,
+                    expression: conflicting.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" AND "),
+                    message: "Conjunction of contradictory comparisons is always false".to_string(),
+                });
+            }
+
+            for conjunct in conjuncts {
+                lint_contradictions(conjunct, warnings);
+            }
+        }
+
+        Expression::Comparison { left, right, .. }
+        | Expression::BooleanOp { left, right, .. }
+        | Expression::BinaryOp { left, right, .. } => {
+            lint_contradictions(left, warnings);
+            lint_contradictions(right, warnings);
+        }
+
+        Expression::UnaryOp { operand, .. } => lint_contradictions(operand, warnings),
+        Expression::Call { argument, .. } => lint_contradictions(argument, warnings),
+
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => {}
+    }
+}
+
+/// Flatten `a AND (b AND c)` into `[a, b, c]`
+fn collect_conjuncts<'a>(expr: &'a Expression, conjuncts: &mut Vec<&'a Expression>) {
+    match expr {
+        Expression::BooleanOp { op: BooleanOperator::And, left, right } => {
+            collect_conjuncts(left, conjuncts);
+            collect_conjuncts(right, conjuncts);
+        }
+        _ => conjuncts.push(expr),
+    }
+}
+
+// Orderings a comparison `left op right` allows, as a bit set
+const LESS: u8 = 0b001;
+const EQUAL: u8 = 0b010;
+const GREATER: u8 = 0b100;
+
+fn allowed_orderings(op: ComparisonOperator) -> u8 {
+    match op {
+        ComparisonOperator::Less => LESS,
+        ComparisonOperator::LessEqual => LESS | EQUAL,
+        ComparisonOperator::Equal => EQUAL,
+        ComparisonOperator::NotEqual => LESS | GREATER,
+        ComparisonOperator::GreaterEqual => GREATER | EQUAL,
+        ComparisonOperator::Greater => GREATER,
+    }
+}
+
+/// Orderings of `right op left`, given those of `left op right`
+fn swap_orderings(orderings: u8) -> u8 {
+    (orderings & EQUAL) | ((orderings & LESS) << 2) | ((orderings & GREATER) >> 2)
+}
+
+/// Values an operand may take, from its comparisons with constants
+struct ValueRange<'a> {
+    /// Inclusive lower bound
+    min: BigUint,
+    /// Inclusive upper bound, `None` if unbounded
+    max: Option<BigUint>,
+    /// Values excluded by `!=`
+    excluded: Vec<BigUint>,
+    /// Set by `x < 0`, which no value satisfies
+    empty: bool,
+    conjuncts: Vec<&'a Expression>,
+}
+
+impl ValueRange<'_> {
+    fn is_empty(&self) -> bool {
+        if self.empty || self.max.as_ref().is_some_and(|max| *max < self.min) {
+            return true;
+        }
+        // A single remaining value that is excluded
+        self.max.as_ref() == Some(&self.min) && self.excluded.contains(&self.min)
+    }
+
+    fn restrict(&mut self, op: ComparisonOperator, value: BigUint) {
+        let tighten_max = |max: &mut Option<BigUint>, bound: BigUint| {
+            if max.as_ref().is_none_or(|current| bound < *current) {
+                *max = Some(bound);
+            }
+        };
+
+        match op {
+            ComparisonOperator::Greater => self.min = self.min.clone().max(value + 1u32),
+            ComparisonOperator::GreaterEqual => self.min = self.min.clone().max(value),
+            ComparisonOperator::Less if value == BigUint::default() => self.empty = true,
+            ComparisonOperator::Less => tighten_max(&mut self.max, value - BigUint::one()),
+            ComparisonOperator::LessEqual => tighten_max(&mut self.max, value),
+            ComparisonOperator::Equal => {
+                self.min = self.min.clone().max(value.clone());
+                tighten_max(&mut self.max, value);
+            }
+            ComparisonOperator::NotEqual => self.excluded.push(value),
+        }
+    }
+}
+
+/// Find comparisons among `conjuncts` that cannot hold together
+///
+/// Comparisons of the same two operands are checked for a common ordering
+/// (`A > B AND A <= B`), and comparisons of an operand with constants for a
+/// common value (`x > 10 AND x < 5`, `x == 1 AND x == 2`). Values are read as
+/// unsigned integers, like the range checks behind comparisons.
+fn find_contradiction<'a>(conjuncts: &[&'a Expression]) -> Option<Vec<&'a Expression>> {
+    let mut pairs: HashMap<(&Expression, &Expression), (u8, Vec<&Expression>)> = HashMap::new();
+    let mut ranges: HashMap<&Expression, ValueRange> = HashMap::new();
+
+    for &conjunct in conjuncts {
+        let Expression::Comparison { op, left, right } = conjunct else {
+            continue;
+        };
+        let (left, right) = (left.as_ref(), right.as_ref());
+
+        // Same operand pair, in a canonical order
+        let (key, orderings) = if left.to_string() <= right.to_string() {
+            ((left, right), allowed_orderings(*op))
+        } else {
+            ((right, left), swap_orderings(allowed_orderings(*op)))
+        };
+        let (allowed, seen) = pairs.entry(key).or_insert((LESS | EQUAL | GREATER, Vec::new()));
+        *allowed &= orderings;
+        seen.push(conjunct);
+        if *allowed == 0 {
+            return Some(seen.clone());
+        }
+
+        // Operand compared with a literal constant
+        let (subject, op, value) = match (constant_value(left), constant_value(right)) {
+            (None, Some(value)) if !is_constant(left) => (left, *op, value),
+            (Some(value), None) if !is_constant(right) => (right, mirror(*op), value),
+            _ => continue,
+        };
+        let range = ranges.entry(subject).or_insert_with(|| ValueRange {
+            min: BigUint::default(),
+            max: None,
+            excluded: Vec::new(),
+            empty: false,
+            conjuncts: Vec::new(),
+        });
+        range.restrict(op, value);
+        range.conjuncts.push(conjunct);
+        if range.is_empty() {
+            return Some(range.conjuncts.clone());
+        }
+    }
+
+    None
+}
+
+/// Value of a literal constant
+fn constant_value(expr: &Expression) -> Option<BigUint> {
+    match expr {
+        Expression::Constant(value) => BigUint::parse_bytes(value.as_bytes(), 10),
+        _ => None,
+    }
+}
+
+/// Operator of `b op' a` equivalent to `a op b`
+fn mirror(op: ComparisonOperator) -> ComparisonOperator {
+    match op {
+        ComparisonOperator::Greater => ComparisonOperator::Less,
+        ComparisonOperator::Less => ComparisonOperator::Greater,
+        ComparisonOperator::GreaterEqual => ComparisonOperator::LessEqual,
+        ComparisonOperator::LessEqual => ComparisonOperator::GreaterEqual,
+        ComparisonOperator::Equal | ComparisonOperator::NotEqual => op,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_circuit;
+
+    #[test]
+    fn test_constant_comparison() {
+        let warnings = lint(&parse_circuit("5 > 3").unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::ConstantComparison);
+        assert_eq!(warnings[0].expression, "5 > 3");
+    }
+
+    #[test]
+    fn test_self_comparison() {
+        let warnings = lint(&parse_circuit("A + 1 == A + 1").unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::SelfComparison);
+    }
+
+    #[test]
+    fn test_constant_boolean_operand() {
+        let warnings = lint(&parse_circuit("A > 5 AND true").unwrap());
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, LintKind::ConstantBooleanOperand);
+    }
+
+    #[test]
+    fn test_nested_findings() {
+        // Constant comparison is also a constant operand of the AND
+        let warnings = lint(&parse_circuit("(2 == 2) AND (B == B)").unwrap());
+        let kinds: Vec<LintKind> = warnings.iter().map(|w| w.kind).collect();
+        assert!(kinds.contains(&LintKind::ConstantBooleanOperand));
+        assert!(kinds.contains(&LintKind::ConstantComparison));
+        assert!(kinds.contains(&LintKind::SelfComparison));
+    }
+
+    #[test]
+    fn test_contradictory_conjunctions() {
+        for circuit in [
+            "A > B AND A < B",
+            "A >= B AND B > A",
+            "x == 1 AND x == 2",
+            "x > 10 AND y > 0 AND 5 > x",
+            "(x >= 3 AND x <= 3) AND x != 3",
+        ] {
+            let warnings = lint(&parse_circuit(circuit).unwrap());
+            assert_eq!(warnings.len(), 1, "{}: {:?}", circuit, warnings);
+            assert_eq!(warnings[0].kind, LintKind::Contradiction, "{}", circuit);
+        }
+
+        // Only the conflicting comparisons are reported
+        let warnings = lint(&parse_circuit("x > 10 AND y > 0 AND 5 > x").unwrap());
+        assert_eq!(warnings[0].expression, "x > 10 AND 5 > x");
+
+        // Also inside a disjunction, where the AND is always false
+        let warnings = lint(&parse_circuit("(A > B AND A < B) OR C > 1").unwrap());
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_satisfiable_conjunctions() {
+        for circuit in [
+            "A >= B AND A <= B",
+            "x > 10 AND x < 12",
+            "x == 1 OR x == 2",
+            "x != 3 AND x >= 3",
+            "A > B AND B > C",
+        ] {
+            assert!(lint(&parse_circuit(circuit).unwrap()).is_empty(), "{}", circuit);
+        }
+    }
+
+    #[test]
+    fn test_clean_circuit() {
+        let warnings = lint(&parse_circuit("(A + B) * C > D AND E != F").unwrap());
+        assert!(warnings.is_empty());
+    }
+}
@@ -4,8 +4,16 @@
 
 mod builder;
 mod estimator;
+mod keccak;
+mod lint;
+mod optimize;
+mod registry;
 mod strategy;
 
 pub use builder::*;
 pub use estimator::*;
+pub use keccak::*;
+pub use lint::*;
+pub use optimize::*;
+pub use registry::*;
 pub use strategy::*;
\ No newline at end of file
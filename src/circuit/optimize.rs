@@ -0,0 +1,300 @@
+//! Algebraic simplifier
+//!
+//! Removes identity operations that would otherwise cost rows in the circuit:
+//!
+//! - `A + 0`, `0 + A`, `A - 0` → `A`
+//! - `A * 1`, `1 * A` → `A`
+//! - `A * 0`, `0 * A` → `0`
+//! - `NOT NOT A` → `A` if `A` is already boolean, `A != 0` otherwise
+//!
+//! The simplified expression evaluates to the same value for every input.
+//! `A * 0` is only folded when `A` is plain arithmetic (`+`, `-`, `*` over
+//! signals and constants). Anything else may constrain the witness (`is_bool`,
+//! range checks behind comparisons, `abs`, `sqrt`, `//`) or fail to evaluate
+//! (division by zero, `popcount` above 64 bits), and must not be optimized away.
+//!
+//! [`balance_chains`] is a separate, opt-in pass: it changes the circuit
+//! layout and therefore the verifying key.
+
+use crate::parser::{BinaryOperator, BooleanOperator, ComparisonOperator, Expression, UnaryOperator};
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+
+/// Simplify identity operations in an expression, bottom-up
+///
+/// # Examples
+///
+/// ```ignore
+/// let expr = parse_circuit("(A + 0) * 1 > B * 0")?;
+/// assert_eq!(simplify(&expr).to_string(), "A > 0");
+/// ```
+pub fn simplify(expr: &Expression) -> Expression {
+    match expr {
+        Expression::BinaryOp { op, left, right } => {
+            let left = simplify(left);
+            let right = simplify(right);
+
+            match op {
+                BinaryOperator::Add if is_zero(&right) => left,
+                BinaryOperator::Add if is_zero(&left) => right,
+                BinaryOperator::Sub if is_zero(&right) => left,
+                BinaryOperator::Mul if is_one(&right) => left,
+                BinaryOperator::Mul if is_one(&left) => right,
+                BinaryOperator::Mul if (is_zero(&right) && is_droppable(&left))
+                    || (is_zero(&left) && is_droppable(&right)) => Expression::constant("0"),
+                _ => Expression::BinaryOp { op: *op, left: Box::new(left), right: Box::new(right) },
+            }
+        }
+
+        Expression::UnaryOp { op: UnaryOperator::Not, operand } => {
+            match simplify(operand) {
+                Expression::UnaryOp { op: UnaryOperator::Not, operand: inner } => {
+                    if is_boolean(&inner) {
+                        *inner
+                    } else {
+                        Expression::compare(ComparisonOperator::NotEqual, *inner, Expression::constant("0"))
+                    }
+                }
+                operand => Expression::not(operand),
+            }
+        }
+
+        Expression::UnaryOp { op, operand } => {
+            Expression::UnaryOp { op: *op, operand: Box::new(simplify(operand)) }
+        }
+        Expression::Comparison { op, left, right } => {
+            Expression::Comparison { op: *op, left: Box::new(simplify(left)), right: Box::new(simplify(right)) }
+        }
+        Expression::BooleanOp { op, left, right } => {
+            Expression::BooleanOp { op: *op, left: Box::new(simplify(left)), right: Box::new(simplify(right)) }
+        }
+        Expression::Call { function, argument } => {
+            Expression::Call { function: *function, argument: Box::new(simplify(argument)) }
+        }
+
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => expr.clone(),
+    }
+}
+
+/// Re-associate chains of `+`, `*`, `AND` and `OR` into balanced trees
+///
+/// The parser builds `A + B + C + D` as `((A + B) + C) + D`, so every
+/// addition waits for the one before it. Balancing gives `(A + B) + (C + D)`:
+/// same operands in the same order, same result (the operators are
+/// associative in the field and on truth values), but a critical path of
+/// about log2(n) instead of n - 1 operations.
+///
+/// # Examples
+///
+/// ```ignore
+/// let expr = parse_circuit("A + B + C + D > E")?;
+/// assert_eq!(balance_chains(&expr).to_string(), "A + B + (C + D) > E");
+/// ```
+pub fn balance_chains(expr: &Expression) -> Expression {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Mul, .. }
+        | Expression::BooleanOp { op: BooleanOperator::And | BooleanOperator::Or, .. } => {
+            let mut operands = Vec::new();
+            collect_chain(expr, expr, &mut operands);
+            let operands: Vec<Expression> = operands.into_iter().map(balance_chains).collect();
+            build_balanced(expr, &operands)
+        }
+
+        Expression::BinaryOp { op, left, right } => {
+            Expression::BinaryOp { op: *op, left: Box::new(balance_chains(left)), right: Box::new(balance_chains(right)) }
+        }
+        Expression::BooleanOp { op, left, right } => {
+            Expression::BooleanOp { op: *op, left: Box::new(balance_chains(left)), right: Box::new(balance_chains(right)) }
+        }
+        Expression::Comparison { op, left, right } => {
+            Expression::Comparison { op: *op, left: Box::new(balance_chains(left)), right: Box::new(balance_chains(right)) }
+        }
+        Expression::UnaryOp { op, operand } => {
+            Expression::UnaryOp { op: *op, operand: Box::new(balance_chains(operand)) }
+        }
+        Expression::Call { function, argument } => {
+            Expression::Call { function: *function, argument: Box::new(balance_chains(argument)) }
+        }
+
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => expr.clone(),
+    }
+}
+
+/// Operands of the chain of `chain`'s operator rooted at `expr`, left to right
+fn collect_chain<'a>(expr: &'a Expression, chain: &Expression, operands: &mut Vec<&'a Expression>) {
+    let (left, right) = match (expr, chain) {
+        (Expression::BinaryOp { op, left, right }, Expression::BinaryOp { op: chain_op, .. }) if op == chain_op => (left, right),
+        (Expression::BooleanOp { op, left, right }, Expression::BooleanOp { op: chain_op, .. }) if op == chain_op => (left, right),
+        _ => {
+            operands.push(expr);
+            return;
+        }
+    };
+    collect_chain(left, chain, operands);
+    collect_chain(right, chain, operands);
+}
+
+/// Combine operands with `chain`'s operator into a tree of minimal depth, keeping their order
+fn build_balanced(chain: &Expression, operands: &[Expression]) -> Expression {
+    if operands.len() == 1 {
+        return operands[0].clone();
+    }
+
+    let (left, right) = operands.split_at(operands.len() / 2);
+    let (left, right) = (Box::new(build_balanced(chain, left)), Box::new(build_balanced(chain, right)));
+    match chain {
+        Expression::BinaryOp { op, .. } => Expression::BinaryOp { op: *op, left, right },
+        Expression::BooleanOp { op, .. } => Expression::BooleanOp { op: *op, left, right },
+        _ => unreachable!("only operator chains are balanced"),
+    }
+}
+
+/// Constant with the given value
+fn is_constant_value(expr: &Expression, check: impl Fn(&BigUint) -> bool) -> bool {
+    match expr {
+        Expression::Constant(value) => BigUint::parse_bytes(value.as_bytes(), 10).is_some_and(|v| check(&v)),
+        _ => false,
+    }
+}
+
+fn is_zero(expr: &Expression) -> bool {
+    is_constant_value(expr, BigUint::is_zero)
+}
+
+fn is_one(expr: &Expression) -> bool {
+    is_constant_value(expr, BigUint::is_one)
+}
+
+/// Expressions that always evaluate to 0 or 1
+fn is_boolean(expr: &Expression) -> bool {
+    matches!(
+        expr,
+        Expression::Comparison { .. }
+            | Expression::BooleanOp { .. }
+            | Expression::Boolean(_)
+            | Expression::UnaryOp { op: UnaryOperator::Not, .. }
+    )
+}
+
+/// Whether the expression can be removed without losing a constraint or an error
+///
+/// Only `+`, `-`, `*` and negation over signals and constants qualify: their
+/// gates hold for every witness and they always evaluate.
+fn is_droppable(expr: &Expression) -> bool {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Sub | BinaryOperator::Mul, left, right } => {
+            is_droppable(left) && is_droppable(right)
+        }
+        Expression::UnaryOp { op: UnaryOperator::Neg, operand } => is_droppable(operand),
+        Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => true,
+        Expression::BinaryOp { .. }
+        | Expression::Comparison { .. }
+        | Expression::BooleanOp { .. }
+        | Expression::UnaryOp { .. }
+        | Expression::Call { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit::evaluate_expression;
+    use crate::parser::parse_circuit;
+    use halo2_proofs::pasta::Fp;
+    use std::collections::HashMap;
+
+    /// Simplify `input`, check the result and that both evaluate alike
+    fn check(input: &str, expected: &str) {
+        let expr = parse_circuit(input).unwrap();
+        let simplified = simplify(&expr);
+        assert_eq!(simplified, parse_circuit(expected).unwrap(), "{}", input);
+
+        for a in [0u64, 1, 7] {
+            let mut signals = HashMap::new();
+            signals.insert("A".to_string(), Fp::from(a));
+            signals.insert("B".to_string(), Fp::from(3));
+            assert_eq!(
+                evaluate_expression(&expr, &signals),
+                evaluate_expression(&simplified, &signals),
+                "{} with A={}", input, a
+            );
+        }
+    }
+
+    #[test]
+    fn test_additive_identity() {
+        check("A + 0", "A");
+        check("0 + A", "A");
+        check("A - 0", "A");
+    }
+
+    #[test]
+    fn test_multiplicative_identity() {
+        check("A * 1", "A");
+        check("1 * A", "A");
+    }
+
+    #[test]
+    fn test_multiplication_by_zero() {
+        check("A * 0", "0");
+        check("0 * (A + B)", "0");
+
+        // A division could fail, so it is kept
+        check("(B / A) * 0", "(B / A) * 0");
+
+        // So are built-ins and comparisons, which constrain their arguments
+        check("is_bool(A) * 0", "is_bool(A) * 0");
+        check("0 * popcount(A)", "0 * popcount(A)");
+        check("(A > B) * 0", "(A > B) * 0");
+    }
+
+    #[test]
+    fn test_double_negation() {
+        check("NOT NOT A", "A != 0");
+        check("NOT NOT (A > B)", "A > B");
+        check("NOT A", "NOT A");
+    }
+
+    #[test]
+    fn test_balance_chains() {
+        use crate::circuit::{critical_path_depth, Circuit};
+
+        let depth = |expr: &Expression| critical_path_depth(&Circuit::new(expr.clone(), HashMap::new(), vec![]));
+
+        for input in [
+            "A + B + C + D + E + F + G + H",
+            "A * B * C * D * E",
+            "(A > 1) AND (B > 1) AND (C > 1) AND (D > 1) OR A == B",
+            "(A + B + C + D) * (A - B - C)",
+        ] {
+            let expr = parse_circuit(input).unwrap();
+            let balanced = balance_chains(&expr);
+            assert!(depth(&balanced) < depth(&expr), "{} -> {}", input, balanced);
+
+            // Same value for every input, and the printed form reparses to the same tree
+            for (a, b) in [(0u64, 3u64), (1, 1), (7, 2), (12, 9)] {
+                let signals: HashMap<String, Fp> = [("A", a), ("B", b), ("C", a + 1), ("D", 5), ("E", 2), ("F", 3), ("G", b), ("H", 4)]
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), Fp::from(value)))
+                    .collect();
+                assert_eq!(evaluate_expression(&expr, &signals), evaluate_expression(&balanced, &signals), "{}", input);
+            }
+            assert_eq!(parse_circuit(&balanced.to_string()).unwrap(), balanced);
+        }
+
+        assert_eq!(
+            balance_chains(&parse_circuit("A + B + C + D").unwrap()),
+            parse_circuit("(A + B) + (C + D)").unwrap()
+        );
+
+        // Non-associative operators are left alone
+        let expr = parse_circuit("A - B - C - D").unwrap();
+        assert_eq!(balance_chains(&expr), expr);
+    }
+
+    #[test]
+    fn test_nested_identities() {
+        check("(A + 0) * 1 > B * 0", "A > 0");
+        check("A - B", "A - B");
+    }
+}
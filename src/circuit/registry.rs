@@ -0,0 +1,385 @@
+//! Circuit registry
+//!
+//! Caches params and proving keys per circuit shape, so services that prove
+//! the same circuits over and over only pay for key generation once. The
+//! cache is bounded: the least recently used shape is evicted when it is full.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut registry = CircuitRegistry::new();
+//! let first = prove_with_registry(&mut registry, request_a)?;  // generates keys
+//! let second = prove_with_registry(&mut registry, request_b)?; // reuses keys
+//! assert_eq!(registry.keygen_count(), 1);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use halo2_proofs::pasta::{Fp, EqAffine};
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::plonk::{Circuit as PlonkCircuit, ProvingKey, keygen_vk, keygen_pk};
+use sha2::{Digest, Sha256};
+use crate::api::Program;
+use super::{Circuit, Statement, Strategy, estimate_circuit_requirements_with_strategy};
+
+/// Compute the canonical fingerprint of a circuit shape
+///
+/// The fingerprint covers everything the params and proving key depend on:
+/// circuit statements, public signal layout, declared signal widths, strategy,
+/// `k`, and max bits.
+/// Signal values are NOT included, so different witnesses for the same
+/// circuit produce the same fingerprint.
+///
+/// Statements are rendered from the parsed AST, so formatting differences
+/// in the source (e.g. whitespace) don't produce different fingerprints.
+pub fn circuit_fingerprint(circuit: &Circuit, strategy: Strategy, k: u32) -> String {
+    let max_bits = circuit.cached_max_bits
+        .map(|bits| bits.to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    let mut parts = vec![
+        format!("k={}", k),
+        format!("strategy={}", strategy),
+        format!("max_bits={}", max_bits),
+        format!("public={}", circuit.public_signal_names.join(",")),
+    ];
+
+    // Declared widths add range checks, which change the verifying key
+    for (name, bits) in &circuit.signal_bits {
+        parts.push(format!("bits={}:{}", name, bits));
+    }
+
+    if let Some(expr) = &circuit.expression {
+        parts.push(format!("expr={}", expr));
+    }
+
+    for stmt in &circuit.statements {
+        parts.push(match stmt {
+            Statement::Assignment { name, expression } => format!("{}<=={}", name, expression),
+            Statement::Expression(expression) => expression.to_string(),
+        });
+    }
+
+    parts.join(";")
+}
+
+/// Compute a stable hash of everything in a program that affects its verifying key
+///
+/// Builds the circuit, picks `k` the same way proving does, and hashes
+/// (SHA-256, hex) the [`circuit_fingerprint`] together with the preprocess
+/// statements. Two programs that differ only in signal values of the same bit
+/// width share a fingerprint, so front-ends can use it as a cache key for
+/// downloaded params or to deduplicate proofs.
+pub fn program_fingerprint(program: &Program, strategy: Strategy) -> Result<String, String> {
+    let circuit = Circuit::from_program(program)?;
+    let k = estimate_circuit_requirements_with_strategy(&circuit, Some(strategy)).k;
+
+    let mut canonical = circuit_fingerprint(&circuit, strategy, k);
+    for stmt in &program.preprocess {
+        canonical.push_str(";preprocess=");
+        canonical.push_str(stmt.trim());
+    }
+
+    Ok(hex::encode(Sha256::digest(canonical.as_bytes())))
+}
+
+/// Params and proving key generated for one circuit shape
+///
+/// Shared behind an `Arc`, so a proof can be created from it without holding
+/// the registry's lock.
+pub struct RegisteredCircuit {
+    /// Circuit size parameter
+    pub k: u32,
+    /// Universal parameters for the circuit size
+    pub params: Params<EqAffine>,
+    /// Proving key for the circuit shape
+    pub pk: ProvingKey<EqAffine>,
+}
+
+/// Number of circuit shapes a registry keeps by default
+pub const DEFAULT_REGISTRY_CAPACITY: usize = 16;
+
+/// Cache of params and proving keys keyed by circuit fingerprint
+///
+/// See [`circuit_fingerprint`] for what identifies a circuit shape. The registry
+/// can be shared between threads: its lock is only held to look up and insert
+/// entries, never during key generation or proving. When it holds `capacity`
+/// shapes, the least recently used one is evicted to make room.
+pub struct CircuitRegistry {
+    state: Mutex<RegistryState>,
+    capacity: usize,
+}
+
+#[derive(Default)]
+struct RegistryState {
+    /// Entries with the tick they were last used at
+    circuits: HashMap<String, (Arc<RegisteredCircuit>, u64)>,
+    params: HashMap<u32, Params<EqAffine>>,
+    tick: u64,
+    keygen_count: usize,
+    last_keygen: Option<(Duration, Duration)>,
+}
+
+impl Default for CircuitRegistry {
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_REGISTRY_CAPACITY)
+    }
+}
+
+impl CircuitRegistry {
+    /// Create an empty registry holding up to [`DEFAULT_REGISTRY_CAPACITY`] shapes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty registry holding up to `capacity` shapes (at least one)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(RegistryState::default()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Lock the registry state
+    ///
+    /// Entries are inserted whole, so a panic elsewhere cannot leave the state
+    /// half-updated and a poisoned lock is recovered rather than propagated.
+    fn state(&self) -> MutexGuard<'_, RegistryState> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Maximum number of circuit shapes kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of circuit shapes currently cached
+    pub fn len(&self) -> usize {
+        self.state().circuits.len()
+    }
+
+    /// Check whether the registry has no cached circuits
+    pub fn is_empty(&self) -> bool {
+        self.state().circuits.is_empty()
+    }
+
+    /// Check whether a circuit shape is cached
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.state().circuits.contains_key(fingerprint)
+    }
+
+    /// Number of times params and keys were generated (i.e. cache misses)
+    pub fn keygen_count(&self) -> usize {
+        self.state().keygen_count
+    }
+
+    /// Durations of the most recent key generation: (VK, PK)
+    ///
+    /// `None` until the first cache miss.
+    pub fn last_keygen_durations(&self) -> Option<(Duration, Duration)> {
+        self.state().last_keygen
+    }
+
+    /// Drop all cached params and keys
+    pub fn clear(&self) {
+        let mut state = self.state();
+        state.circuits.clear();
+        state.params.clear();
+    }
+
+    /// Params used for circuits of size `k`, if any were supplied or generated
+    pub fn params(&self, k: u32) -> Option<Params<EqAffine>> {
+        let state = self.state();
+        state.params.get(&k).cloned().or_else(|| {
+            state.circuits.values()
+                .find(|(registered, _)| registered.k == k)
+                .map(|(registered, _)| registered.params.clone())
+        })
+    }
+
+    /// Use `params` for circuits of size `k` instead of generating them
+    ///
+    /// Applies to circuit shapes set up from now on; cached keys are kept.
+    pub fn insert_params(&self, k: u32, params: Params<EqAffine>) {
+        self.state().params.insert(k, params);
+    }
+
+    /// Get params and proving key for a circuit, generating them on first use
+    ///
+    /// Also returns the (VK, PK) generation durations when this call generated
+    /// them. Two threads missing the same shape at once may both generate keys;
+    /// either result is valid and one of them is kept.
+    ///
+    /// # Arguments
+    /// * `fingerprint` - Circuit shape fingerprint from [`circuit_fingerprint`]
+    /// * `circuit` - Circuit wrapper used for key generation (witnesses are ignored)
+    /// * `k` - Circuit size parameter
+    pub fn setup<C>(
+        &self,
+        fingerprint: &str,
+        circuit: &C,
+        k: u32,
+    ) -> Result<(Arc<RegisteredCircuit>, Option<(Duration, Duration)>), String>
+    where
+        C: PlonkCircuit<Fp>,
+    {
+        let params = {
+            let mut state = self.state();
+            state.tick += 1;
+            let tick = state.tick;
+            if let Some((registered, last_used)) = state.circuits.get_mut(fingerprint) {
+                *last_used = tick;
+                return Ok((Arc::clone(registered), None));
+            }
+            state.params.get(&k).cloned()
+        };
+
+        // Universal parameters for the circuit size, unless supplied
+        let params = params.unwrap_or_else(|| Params::new(k));
+
+        let empty_wrapped = circuit.without_witnesses();
+
+        // Generate VK
+        let vk_start = Instant::now();
+        let vk = keygen_vk(&params, &empty_wrapped)
+            .map_err(|e| format!("Failed to generate VK: {:?}", e))?;
+        let vk_time = vk_start.elapsed();
+
+        // Generate PK
+        let pk_start = Instant::now();
+        let pk = keygen_pk(&params, vk, &empty_wrapped)
+            .map_err(|e| format!("Failed to generate PK: {:?}", e))?;
+        let pk_time = pk_start.elapsed();
+
+        let registered = Arc::new(RegisteredCircuit { k, params, pk });
+
+        let mut state = self.state();
+        if !state.circuits.contains_key(fingerprint) && state.circuits.len() >= self.capacity {
+            let least_recent = state.circuits.iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(fingerprint, _)| fingerprint.clone());
+            if let Some(least_recent) = least_recent {
+                state.circuits.remove(&least_recent);
+            }
+        }
+        state.tick += 1;
+        let tick = state.tick;
+        state.circuits.insert(fingerprint.to_string(), (Arc::clone(&registered), tick));
+        state.keygen_count += 1;
+        state.last_keygen = Some((vk_time, pk_time));
+
+        Ok((registered, Some((vk_time, pk_time))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn circuit_for(zircon: &str) -> Circuit {
+        let program = Program::from_zircon(zircon).unwrap();
+        Circuit::from_program(&program).unwrap()
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_witness_values() {
+        let a = circuit_for("1/A:10,B:20/out:?/-/A+B>25");
+        let b = circuit_for("1/A:3,B:4/out:?/-/A + B > 25");
+
+        assert_eq!(
+            circuit_fingerprint(&a, Strategy::Auto, 8),
+            circuit_fingerprint(&b, Strategy::Auto, 8),
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_distinguishes_shape() {
+        let a = circuit_for("1/A:10,B:20/out:?/-/A+B>25");
+        let b = circuit_for("1/A:10,B:20/out:?/-/A*B>25");
+
+        let base = circuit_fingerprint(&a, Strategy::Auto, 8);
+        assert_ne!(base, circuit_fingerprint(&b, Strategy::Auto, 8));
+        assert_ne!(base, circuit_fingerprint(&a, Strategy::Lookup, 8));
+        assert_ne!(base, circuit_fingerprint(&a, Strategy::Auto, 9));
+    }
+
+    #[test]
+    fn test_fingerprint_includes_declared_bits() {
+        let program = |bits: usize| Program::from_json(&format!(
+            r#"{{"version":1,"secret":{{"A":{{"value":"5","bits":{}}}}},"public":{{"out":{{}}}},"circuit":["A*2"]}}"#,
+            bits
+        )).unwrap();
+        let a = Circuit::from_program(&program(8)).unwrap();
+        let b = Circuit::from_program(&program(16)).unwrap();
+        let mut c = b.clone();
+        c.cached_max_bits = a.cached_max_bits;
+
+        // Same statements and table size, but different range checks
+        assert_ne!(circuit_fingerprint(&a, Strategy::Auto, 8), circuit_fingerprint(&c, Strategy::Auto, 8));
+    }
+
+    #[test]
+    fn test_registry_evicts_least_recently_used() {
+        use crate::circuit::CircuitAuto;
+
+        let registry = CircuitRegistry::with_capacity(2);
+        let setup = |zircon: &str| {
+            let circuit = circuit_for(zircon);
+            let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+            let fingerprint = circuit_fingerprint(&circuit, Strategy::Auto, k);
+            let (_, keygen) = registry.setup(&fingerprint, &CircuitAuto(circuit), k).unwrap();
+            (fingerprint, keygen.is_some())
+        };
+
+        let (add, generated) = setup("1/A:1,B:2/out:?/-/A+B");
+        assert!(generated);
+        let (mul, _) = setup("1/A:1,B:2/out:?/-/A*B");
+        assert!(!setup("1/A:5,B:6/out:?/-/A+B").1, "cached shape is reused");
+
+        // `mul` is now the least recently used entry
+        let (sub, _) = setup("1/A:1,B:2/out:?/-/A-B");
+        assert_eq!(registry.len(), 2);
+        assert!(registry.contains(&add));
+        assert!(registry.contains(&sub));
+        assert!(!registry.contains(&mul));
+        assert_eq!(registry.keygen_count(), 3);
+    }
+
+    #[test]
+    fn test_registry_returns_params_it_used() {
+        use crate::circuit::CircuitAuto;
+
+        let registry = CircuitRegistry::new();
+        let circuit = circuit_for("1/A:1,B:2/out:?/-/A+B");
+        let k = estimate_circuit_requirements_with_strategy(&circuit, None).k;
+        assert!(registry.params(k).is_none());
+
+        let fingerprint = circuit_fingerprint(&circuit, Strategy::Auto, k);
+        registry.setup(&fingerprint, &CircuitAuto(circuit), k).unwrap();
+        let params = registry.params(k).unwrap();
+        assert_eq!(crate::api::params_to_bytes(&params), crate::api::generate_params(k));
+        assert!(registry.params(k + 1).is_none());
+    }
+
+    #[test]
+    fn test_program_fingerprint() {
+        let fingerprint = |zircon: &str| {
+            program_fingerprint(&Program::from_zircon(zircon).unwrap(), Strategy::Auto).unwrap()
+        };
+
+        let base = fingerprint("1/A:10,B:20/out:?/h<==sha256(A{%d})/A+B>25");
+        assert_eq!(base.len(), 64);
+
+        // Only secret values differ
+        assert_eq!(base, fingerprint("1/A:3,B:4/out:?/h<==sha256(A{%d})/A+B>25"));
+
+        // Different circuit or preprocessing
+        assert_ne!(base, fingerprint("1/A:10,B:20/out:?/h<==sha256(A{%d})/A*B>25"));
+        assert_ne!(base, fingerprint("1/A:10,B:20/out:?/h<==sha256(B{%d})/A+B>25"));
+        assert_ne!(
+            base,
+            program_fingerprint(&Program::from_zircon("1/A:10,B:20/out:?/h<==sha256(A{%d})/A+B>25").unwrap(), Strategy::Lookup).unwrap()
+        );
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::circuit::Circuit;
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::fmt;
 use std::str::FromStr;
 
@@ -12,12 +13,12 @@ use std::str::FromStr;
 ///
 /// Strategies control how circuit constraints are implemented, balancing between
 /// proof size, proving time, and circuit size.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum Strategy {
     /// Adaptive strategy that automatically selects optimal strategy based on circuit
     Auto,
-    /// Base strategy: arithmetic (+, -, *, /), equality (==, !=), and boolean (AND, OR, NOT) operations only
+    /// Base strategy: arithmetic (+, -, *, /), equality (==, !=), and boolean (AND, OR, XOR, NAND, NOR, XNOR, NOT) operations only
     Boolean,
     /// Full comparison support using lookup tables for fast proving
     Lookup,
@@ -37,13 +38,18 @@ impl Strategy {
         }
     }
 
+    /// Returns true for the default adaptive strategy
+    pub fn is_auto(&self) -> bool {
+        matches!(self, Strategy::Auto)
+    }
+
     /// Returns the supported operations for this strategy
     pub fn operations(&self) -> &'static str {
         match self {
             Strategy::Auto => "All operations (adaptive selection)",
-            Strategy::Boolean => "+, -, *, /, ==, !=, AND, OR, NOT",
-            Strategy::Lookup => "+, -, *, /, ==, !=, AND, OR, NOT, >, <, >=, <=",
-            Strategy::BitD => "+, -, *, /, ==, !=, AND, OR, NOT, >, <, >=, <=",
+            Strategy::Boolean => "+, -, *, /, ==, !=, AND, OR, XOR, NAND, NOR, XNOR, NOT, is_bool, keccak256",
+            Strategy::Lookup => "+, -, *, /, //, ==, !=, AND, OR, XOR, NAND, NOR, XNOR, NOT, >, <, >=, <=, sqrt, is_square, is_bool, popcount, abs, keccak256",
+            Strategy::BitD => "+, -, *, /, //, ==, !=, AND, OR, XOR, NAND, NOR, XNOR, NOT, >, <, >=, <=, sqrt, is_square, is_bool, popcount, abs, keccak256",
         }
     }
 
@@ -133,11 +139,11 @@ pub fn validate_strategy_compatibility(
             // Boolean strategy is the base strategy supporting:
             // - Arithmetic: +, -, *, /
             // - Equality: ==, != (including implicit constrain_instance)
-            // - Boolean: AND, OR, NOT
-            // BUT NOT range comparisons (>, <, >=, <=)
+            // - Boolean: AND, OR, XOR, NAND, NOR, XNOR, NOT
+            // BUT NOT range comparisons (>, <, >=, <=), integer division (//), or sqrt/is_square/popcount/abs
             if circuit.uses_range_check_comparisons() {
                 return Err(format!(
-                    "Strategy '{}' does not support range comparison operations (>, <, >=, <=).\n\
+                    "Strategy '{}' does not support range-checked operations (>, <, >=, <=, //, sqrt, is_square, popcount, abs).\n\
                      \n\
                      The '{}' strategy only supports: {}\n\
                      \n\
@@ -5,6 +5,7 @@
 //! - Hexadecimal: "0x1a2b" or "1a2b" (any size)
 //! - Base58: "5HpH..." (Solana/Bitcoin addresses - 32 bytes)
 //! - Base64: "SGVsbG8=" (universal encoding)
+//! - Base64url: "SGVsbG8" (URL-safe, no padding; JWTs and web APIs)
 //!
 //! # Important Notes
 //!
@@ -13,6 +14,7 @@
 //! - Use equality operators for comparing large values like Solana addresses (32 bytes)
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use thiserror::Error;
 use num_bigint::BigUint;
 use num_traits::Num;
@@ -32,6 +34,9 @@ pub enum ValueEncodingError {
     #[error("Invalid base64: {0}")]
     InvalidBase64(String),
 
+    #[error("Invalid base64url: {0}")]
+    InvalidBase64Url(String),
+
     #[error("Invalid base85: {0}")]
     InvalidBase85(String),
 
@@ -43,7 +48,7 @@ pub enum ValueEncodingError {
 }
 
 /// Value encoding format
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ValueEncoding {
     /// Decimal string: "12345"
@@ -58,6 +63,9 @@ pub enum ValueEncoding {
     /// Base64 encoding: "SGVsbG8="
     Base64,
 
+    /// URL-safe Base64 without padding (JWT segments): "SGVsbG8"
+    Base64Url,
+
     /// Base85 encoding (Ascii85): More compact than Base64
     Base85,
 
@@ -71,6 +79,67 @@ impl Default for ValueEncoding {
     }
 }
 
+impl ValueEncoding {
+    /// Every encoding, in the order they are listed to users
+    pub const ALL: [ValueEncoding; 7] = [
+        ValueEncoding::Decimal,
+        ValueEncoding::Hex,
+        ValueEncoding::Base58,
+        ValueEncoding::Base64,
+        ValueEncoding::Base64Url,
+        ValueEncoding::Base85,
+        ValueEncoding::Text,
+    ];
+
+    /// Canonical name, as used in Zircon and JSON
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValueEncoding::Decimal => "decimal",
+            ValueEncoding::Hex => "hex",
+            ValueEncoding::Base58 => "base58",
+            ValueEncoding::Base64 => "base64",
+            ValueEncoding::Base64Url => "base64url",
+            ValueEncoding::Base85 => "base85",
+            ValueEncoding::Text => "text",
+        }
+    }
+
+    /// Short aliases accepted besides the canonical name
+    pub fn aliases(&self) -> &'static [&'static str] {
+        match self {
+            ValueEncoding::Decimal | ValueEncoding::Hex => &[],
+            ValueEncoding::Base58 => &["b58"],
+            ValueEncoding::Base64 => &["b64"],
+            ValueEncoding::Base64Url => &["b64url"],
+            ValueEncoding::Base85 => &["b85"],
+            ValueEncoding::Text => &["txt", "string", "str"],
+        }
+    }
+}
+
+impl std::fmt::Display for ValueEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Parse an encoding name or alias (case-insensitive)
+///
+/// The single mapping used by the CLI (`name:value:encoding`) and the Zircon parser.
+impl std::str::FromStr for ValueEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        ValueEncoding::ALL.into_iter()
+            .find(|encoding| encoding.as_str() == name || encoding.aliases().contains(&name.as_str()))
+            .ok_or_else(|| format!(
+                "Unknown encoding: {}. Supported: decimal, hex, base58/b58, base64/b64, base64url/b64url, base85/b85, text/txt/string/str",
+                s
+            ))
+    }
+}
+
 /// Parse a value string according to the specified encoding
 ///
 /// # Examples
@@ -88,6 +157,9 @@ impl Default for ValueEncoding {
 ///
 /// // Base64
 /// let val = parse_value("SGVsbG8=", ValueEncoding::Base64)?;
+///
+/// // Base64url (JWT segment)
+/// let val = parse_value("eyJhbGciOiJIUzI1NiJ9", ValueEncoding::Base64Url)?;
 /// ```
 pub fn parse_value(value: &str, encoding: ValueEncoding) -> Result<Vec<u8>, ValueEncodingError> {
     match encoding {
@@ -95,6 +167,7 @@ pub fn parse_value(value: &str, encoding: ValueEncoding) -> Result<Vec<u8>, Valu
         ValueEncoding::Hex => parse_hex(value),
         ValueEncoding::Base58 => parse_base58(value),
         ValueEncoding::Base64 => parse_base64(value),
+        ValueEncoding::Base64Url => parse_base64url(value),
         ValueEncoding::Base85 => parse_base85(value),
         ValueEncoding::Text => Ok(value.as_bytes().to_vec()),
     }
@@ -107,37 +180,108 @@ pub fn parse_value(value: &str, encoding: ValueEncoding) -> Result<Vec<u8>, Valu
 /// - All digits -> Decimal
 /// - Contains only base58 chars -> Base58
 /// - Contains base64 chars (including +/=) -> Base64
+/// - Contains - or _ and no = -> Base64url
 /// - Everything else -> Text (UTF-8 string)
+///
+/// Hex without the `0x` prefix is not detected: `deadbeef` only uses base58
+/// letters and reads as base58. Give such values an explicit `hex` encoding, or
+/// opt in with [`AutoDetectOptions::prefer_bare_hex`].
 pub fn parse_value_auto(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
+    parse_value(value, auto_encoding(value))
+}
+
+/// Same as [`parse_value_auto`], with detection adjusted by `options`
+pub fn parse_value_auto_with(value: &str, options: AutoDetectOptions) -> Result<Vec<u8>, ValueEncodingError> {
+    parse_value(value, auto_encoding_with(value, options))
+}
+
+/// Opt-in changes to encoding auto-detection
+///
+/// The defaults are the rules of [`parse_value_auto`]; each option trades a
+/// value that used to be read one way for another, so none is on by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AutoDetectOptions {
+    /// Read an even number of hex digits without `0x` (e.g. `deadbeef`) as hex
+    ///
+    /// All-digit strings stay decimal. Values that base58 would otherwise
+    /// accept, such as `cafe`, change meaning.
+    pub prefer_bare_hex: bool,
+}
+
+/// The encoding [`parse_value_auto`] reads a value string with
+///
+/// Hex and decimal are chosen by their shape alone (and may then fail to parse);
+/// the other encodings only when the value decodes under them.
+pub fn auto_encoding(value: &str) -> ValueEncoding {
+    auto_encoding_with(value, AutoDetectOptions::default())
+}
+
+/// The encoding [`parse_value_auto_with`] reads a value string with
+pub fn auto_encoding_with(value: &str, options: AutoDetectOptions) -> ValueEncoding {
     // Try hex first (most specific)
     if value.starts_with("0x") || value.starts_with("0X") {
-        return parse_hex(value);
+        return ValueEncoding::Hex;
     }
 
     // Try decimal (simple and common)
     if value.chars().all(|c| c.is_ascii_digit()) {
-        return parse_decimal(value);
+        return ValueEncoding::Decimal;
+    }
+
+    // Bare hex, when asked for (not all digits, so not decimal)
+    if options.prefer_bare_hex && value.len() % 2 == 0 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        return ValueEncoding::Hex;
     }
 
     // Try base64 (contains +, /, =)
-    if value.contains('+') || value.contains('/') || value.contains('=') {
-        if let Ok(result) = parse_base64(value) {
-            return Ok(result);
-        }
+    if (value.contains('+') || value.contains('/') || value.contains('=')) && parse_base64(value).is_ok() {
+        return ValueEncoding::Base64;
+    }
+
+    // Try base64url (contains - or _, never padded)
+    if (value.contains('-') || value.contains('_')) && !value.contains('=') && parse_base64url(value).is_ok() {
+        return ValueEncoding::Base64Url;
     }
 
     // Try base58 (no 0, O, I, l characters)
     if value.chars().all(|c| {
         c.is_ascii_alphanumeric() && c != '0' && c != 'O' && c != 'I' && c != 'l'
-    }) {
-        if let Ok(result) = parse_base58(value) {
-            return Ok(result);
-        }
+    }) && parse_base58(value).is_ok() {
+        return ValueEncoding::Base58;
     }
 
     // Default to plain text (UTF-8 bytes)
     // This allows arbitrary strings to be used in preprocessing
-    Ok(value.as_bytes().to_vec())
+    ValueEncoding::Text
+}
+
+/// Report which encodings a value string is valid under
+///
+/// Candidates are listed in auto-detection order. More than one `true` means the
+/// value is ambiguous and [`parse_value_auto`] may not pick the encoding you meant;
+/// `Text` always accepts the string.
+///
+/// # Examples
+///
+/// ```ignore
+/// // All digits: decimal wins auto-detection, but hex and base58 decode too
+/// for (encoding, valid) in detect_encoding("12345678") {
+///     println!("{:?}: {}", encoding, valid);
+/// }
+/// ```
+pub fn detect_encoding(value: &str) -> Vec<(ValueEncoding, bool)> {
+    [
+        ValueEncoding::Hex,
+        ValueEncoding::Decimal,
+        ValueEncoding::Base64,
+        ValueEncoding::Base64Url,
+        ValueEncoding::Base58,
+        ValueEncoding::Base85,
+        ValueEncoding::Text,
+    ]
+    .into_iter()
+    .map(|encoding| (encoding, parse_value(value, encoding).is_ok()))
+    .collect()
 }
 
 fn parse_decimal(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
@@ -148,8 +292,12 @@ fn parse_decimal(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
 
     // Parse as BigUint (supports arbitrary precision)
     // This correctly handles any decimal number, including very large ones
-    let num = BigUint::from_str_radix(value, 10)
-        .map_err(|_| ValueEncodingError::InvalidDecimal(value.to_string()))?;
+    let num = BigUint::from_str_radix(value, 10).map_err(|_| {
+        ValueEncodingError::InvalidDecimal(match value.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            Some((index, c)) => invalid_character(c, index, "is not a decimal digit"),
+            None => value.to_string(),
+        })
+    })?;
 
     // Convert to big-endian bytes
     let bytes = num.to_bytes_be();
@@ -168,26 +316,96 @@ fn parse_hex(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
         .or_else(|| value.strip_prefix("0X"))
         .unwrap_or(value);
 
-    hex::decode(hex_str)
-        .map_err(|_| ValueEncodingError::InvalidHex(value.to_string()))
+    // Positions are reported in the original value, including any 0x prefix
+    let prefix_len = value.len() - hex_str.len();
+    hex::decode(hex_str).map_err(|e| ValueEncodingError::InvalidHex(match e {
+        hex::FromHexError::InvalidHexCharacter { c, index } => {
+            invalid_character(c, prefix_len + index, "is not a hex digit")
+        }
+        hex::FromHexError::OddLength => format!("'{}' has an odd number of digits", value),
+        hex::FromHexError::InvalidStringLength => value.to_string(),
+    }))
 }
 
 fn parse_base58(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
     bs58::decode(value)
         .into_vec()
-        .map_err(|_| ValueEncodingError::InvalidBase58(value.to_string()))
+        .map_err(|e| ValueEncodingError::InvalidBase58(match e {
+            bs58::decode::Error::InvalidCharacter { character, index } => {
+                invalid_character(character, index, "is not in the base58 alphabet")
+            }
+            bs58::decode::Error::NonAsciiCharacter { index } => {
+                let c = value[index..].chars().next().unwrap_or('?');
+                invalid_character(c, index, "is not in the base58 alphabet")
+            }
+            _ => value.to_string(),
+        }))
 }
 
 fn parse_base64(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
     general_purpose::STANDARD.decode(value)
-        .map_err(|_| ValueEncodingError::InvalidBase64(value.to_string()))
+        .map_err(|e| ValueEncodingError::InvalidBase64(base64_error_detail(value, e, "base64")))
+}
+
+fn parse_base64url(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
+    general_purpose::URL_SAFE_NO_PAD.decode(value)
+        .map_err(|e| ValueEncodingError::InvalidBase64Url(base64_error_detail(value, e, "base64url")))
 }
 
 fn parse_base85(value: &str) -> Result<Vec<u8>, ValueEncodingError> {
+    // Ascii85 uses '!' to 'u', plus 'z' for four zero bytes, optionally wrapped in <~ ~>
+    let body = value.strip_prefix("<~").and_then(|v| v.strip_suffix("~>"));
+    let offset = if body.is_some() { 2 } else { 0 };
+    let invalid = body.unwrap_or(value).char_indices()
+        .find(|(_, c)| !matches!(c, '!'..='u' | 'z' | ' ' | '\t' | '\n' | '\r'));
+    if let Some((index, c)) = invalid {
+        return Err(ValueEncodingError::InvalidBase85(invalid_character(c, offset + index, "is not in the base85 alphabet")));
+    }
+
     ascii85::decode(value)
         .map_err(|_| ValueEncodingError::InvalidBase85(value.to_string()))
 }
 
+/// Describe an offending character and its (byte) index in the value
+fn invalid_character(c: char, index: usize, reason: &str) -> String {
+    format!("character '{}' at index {} {}", c.escape_default(), index, reason)
+}
+
+/// Describe a base64/base64url decoding error
+fn base64_error_detail(value: &str, error: base64::DecodeError, alphabet: &str) -> String {
+    match error {
+        base64::DecodeError::InvalidByte(index, b'=') => {
+            format!("padding '=' at index {} is not at the end", index)
+        }
+        base64::DecodeError::InvalidByte(index, byte) => {
+            let c = value[index..].chars().next().unwrap_or(byte as char);
+            invalid_character(c, index, &format!("is not in the {} alphabet", alphabet))
+        }
+        base64::DecodeError::InvalidLastSymbol(index, byte) => {
+            invalid_character(byte as char, index, "ends the value with leftover bits (truncated or corrupted)")
+        }
+        base64::DecodeError::InvalidLength => format!("'{}' has an invalid length", value),
+        base64::DecodeError::InvalidPadding => format!("'{}' has invalid padding", value),
+    }
+}
+
+/// Split an array signal value `[a,b,c]` into its trimmed elements
+///
+/// Returns `None` for a scalar value. Elements are separated by commas, so
+/// each one must be in an encoding without commas; `[]` yields one empty
+/// element, which callers reject.
+///
+/// # Examples
+///
+/// ```ignore
+/// assert_eq!(split_array_value("[1, 0, 1]"), Some(vec!["1", "0", "1"]));
+/// assert_eq!(split_array_value("42"), None);
+/// ```
+pub fn split_array_value(value: &str) -> Option<Vec<&str>> {
+    let inner = value.trim().strip_prefix('[')?.strip_suffix(']')?;
+    Some(inner.split(',').map(str::trim).collect())
+}
+
 /// Convert bytes to decimal string representation
 pub fn bytes_to_decimal(bytes: &[u8]) -> String {
     // Use BigUint for arbitrary precision
@@ -210,15 +428,61 @@ pub fn bytes_to_base64(bytes: &[u8]) -> String {
     general_purpose::STANDARD.encode(bytes)
 }
 
+/// Convert bytes to base64url string (URL-safe alphabet, no padding)
+pub fn bytes_to_base64url(bytes: &[u8]) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+}
+
 /// Convert bytes to base85 string (Adobe ASCII85)
 pub fn bytes_to_base85(bytes: &[u8]) -> String {
     ascii85::encode(bytes)
 }
 
+/// Convert bytes to a string in the given encoding, the inverse of `parse_value`
+///
+/// Text that is not valid UTF-8 is converted lossily.
+pub fn encode_bytes(bytes: &[u8], encoding: ValueEncoding) -> String {
+    match encoding {
+        ValueEncoding::Decimal => bytes_to_decimal(bytes),
+        ValueEncoding::Hex => bytes_to_hex(bytes),
+        ValueEncoding::Base58 => bytes_to_base58(bytes),
+        ValueEncoding::Base64 => bytes_to_base64(bytes),
+        ValueEncoding::Base64Url => bytes_to_base64url(bytes),
+        ValueEncoding::Base85 => bytes_to_base85(bytes),
+        ValueEncoding::Text => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_encoding_names_and_aliases() {
+        for encoding in ValueEncoding::ALL {
+            assert_eq!(encoding.as_str().parse::<ValueEncoding>().unwrap(), encoding);
+            assert_eq!(encoding.to_string().to_uppercase().parse::<ValueEncoding>().unwrap(), encoding);
+            for alias in encoding.aliases() {
+                assert_eq!(alias.parse::<ValueEncoding>().unwrap(), encoding, "{}", alias);
+            }
+            // Same names as serde
+            assert_eq!(serde_json::to_string(&encoding).unwrap(), format!("\"{}\"", encoding.as_str()));
+        }
+
+        assert_eq!("str".parse::<ValueEncoding>().unwrap(), ValueEncoding::Text);
+        assert_eq!("B64URL".parse::<ValueEncoding>().unwrap(), ValueEncoding::Base64Url);
+        assert!("base62".parse::<ValueEncoding>().unwrap_err().starts_with("Unknown encoding: base62."));
+    }
+
+    #[test]
+    fn test_split_array_value() {
+        assert_eq!(split_array_value("[1,0,1,1]"), Some(vec!["1", "0", "1", "1"]));
+        assert_eq!(split_array_value(" [ 0x01 , 2 ] "), Some(vec!["0x01", "2"]));
+        assert_eq!(split_array_value("[]"), Some(vec![""]));
+        assert_eq!(split_array_value("12345"), None);
+        assert_eq!(split_array_value("[1,2"), None);
+    }
+
     #[test]
     fn test_parse_decimal() {
         let result = parse_value("12345", ValueEncoding::Decimal).unwrap();
@@ -245,6 +509,22 @@ mod tests {
         assert_eq!(decoded, original);
     }
 
+    #[test]
+    fn test_parse_base64url_jwt_segment() {
+        // Payload and signature of the jwt.io HS256 example token
+        let payload = "eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ";
+        let decoded = parse_value(payload, ValueEncoding::Base64Url).unwrap();
+        assert_eq!(decoded, br#"{"sub":"1234567890","name":"John Doe","iat":1516239022}"#);
+        assert!(parse_value(payload, ValueEncoding::Base64).is_err());
+
+        // The signature uses '_', so auto-detection picks base64url
+        let signature = "SflKxwRJSMeKKF2QT4fwpMeJf36POk6yJV_adQssw5c";
+        let decoded = parse_value(signature, ValueEncoding::Base64Url).unwrap();
+        assert_eq!(decoded.len(), 32);
+        assert_eq!(parse_value_auto(signature).unwrap(), decoded);
+        assert_eq!(bytes_to_base64url(&decoded), signature);
+    }
+
     #[test]
     fn test_parse_base58() {
         let original = b"Hello, World!";
@@ -253,6 +533,29 @@ mod tests {
         assert_eq!(decoded, original);
     }
 
+    #[test]
+    fn test_invalid_character_errors() {
+        let err = parse_value("9aE40sH9", ValueEncoding::Base58).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid base58: character '0' at index 4 is not in the base58 alphabet");
+
+        // Hex positions count the 0x prefix
+        let err = parse_value("0x12g4", ValueEncoding::Hex).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid hexadecimal: character 'g' at index 4 is not a hex digit");
+        let err = parse_value("abc", ValueEncoding::Hex).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid hexadecimal: 'abc' has an odd number of digits");
+
+        let err = parse_value("12a4", ValueEncoding::Decimal).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid decimal number: character 'a' at index 2 is not a decimal digit");
+
+        let err = parse_value("SGV$bG8=", ValueEncoding::Base64).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid base64: character '$' at index 3 is not in the base64 alphabet");
+        let err = parse_value("SGVs+G8", ValueEncoding::Base64Url).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid base64url: character '+' at index 4 is not in the base64url alphabet");
+
+        let err = parse_value("87cU{", ValueEncoding::Base85).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid base85: character '{' at index 4 is not in the base85 alphabet");
+    }
+
     #[test]
     fn test_auto_detect_hex() {
         let result = parse_value_auto("0x1a2b").unwrap();
@@ -265,6 +568,24 @@ mod tests {
         assert_eq!(bytes_to_decimal(&result), "12345");
     }
 
+    #[test]
+    fn test_auto_detect_bare_hex() {
+        let bare_hex = AutoDetectOptions { prefer_bare_hex: true };
+
+        // Default rules: bare hex digits that avoid 0 are valid base58
+        assert_eq!(auto_encoding("deadbeef"), ValueEncoding::Base58);
+        assert_eq!(auto_encoding("12345"), ValueEncoding::Decimal);
+        assert_eq!(auto_encoding("0xff"), ValueEncoding::Hex);
+
+        assert_eq!(auto_encoding_with("deadbeef", bare_hex), ValueEncoding::Hex);
+        assert_eq!(parse_value_auto_with("deadbeef", bare_hex).unwrap(), vec![0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(auto_encoding_with("12345", bare_hex), ValueEncoding::Decimal);
+        assert_eq!(auto_encoding_with("0xff", bare_hex), ValueEncoding::Hex);
+
+        // Odd length is not hex
+        assert_eq!(auto_encoding_with("abc", bare_hex), ValueEncoding::Base58);
+    }
+
     #[test]
     fn test_roundtrip_hex() {
         let original = vec![0xde, 0xad, 0xbe, 0xef];
@@ -357,4 +678,41 @@ mod tests {
         let decoded = parse_value(&encoded, ValueEncoding::Base85).unwrap();
         assert_eq!(decoded, original);
     }
+
+    #[test]
+    fn test_roundtrip_encode_bytes() {
+        let original = b"zkplex";
+        for encoding in ValueEncoding::ALL {
+            let encoded = encode_bytes(original, encoding);
+            assert_eq!(parse_value(&encoded, encoding).unwrap(), original, "{}", encoding);
+        }
+        assert_eq!(encode_bytes(&[0x01, 0x00], ValueEncoding::Decimal), "256");
+    }
+
+    #[test]
+    fn test_detect_encoding_ambiguous_digits() {
+        let report = detect_encoding("12345678");
+        let valid = |encoding| report.iter().any(|&(e, ok)| e == encoding && ok);
+
+        // Auto-detection reads this as decimal, but it is also valid hex and base58
+        assert!(valid(ValueEncoding::Decimal));
+        assert!(valid(ValueEncoding::Hex));
+        assert!(valid(ValueEncoding::Base58));
+        assert!(valid(ValueEncoding::Text));
+        assert_ne!(
+            parse_value("12345678", ValueEncoding::Decimal).unwrap(),
+            parse_value("12345678", ValueEncoding::Base58).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_detect_encoding_prefixed_hex() {
+        let report = detect_encoding("0xdeadbeef");
+        let valid = |encoding| report.iter().any(|&(e, ok)| e == encoding && ok);
+
+        assert_eq!(report[0], (ValueEncoding::Hex, true));
+        assert!(!valid(ValueEncoding::Decimal));
+        assert!(!valid(ValueEncoding::Base58)); // '0' is not in the base58 alphabet
+        assert!(!valid(ValueEncoding::Base64));
+    }
 }
\ No newline at end of file
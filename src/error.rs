@@ -0,0 +1,79 @@
+//! Error type of the proving API
+//!
+//! `core::prove()`, `core::verify()` and `Circuit::from_program()` return a
+//! [`ZkplexError`], so callers can match on the kind of failure instead of the
+//! message. The message itself (`Display`) is unchanged from when these
+//! functions returned `String` errors.
+//!
+//! ```ignore
+//! match prove(request) {
+//!     Err(ZkplexError::CircuitTooLarge { k, .. }) => eprintln!("needs 2^{} rows", k),
+//!     Err(e) => eprintln!("{}", e),
+//!     Ok(response) => { /* ... */ }
+//! }
+//! ```
+
+/// Why proving, verifying or building a circuit failed
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ZkplexError {
+    /// A circuit statement does not parse
+    #[error("{0}")]
+    Parse(String),
+
+    /// A signal value cannot be decoded with its encoding
+    #[error("{0}")]
+    Encoding(String),
+
+    /// Halo2 failed to generate keys or create the proof, or the witness
+    /// does not produce a circuit output
+    #[error("{0}")]
+    Synthesis(String),
+
+    /// A divisor evaluates to zero
+    #[error("{0}")]
+    DivisionByZero(String),
+
+    /// The circuit uses an operation its strategy does not support
+    #[error("{0}")]
+    UnsupportedOperation(String),
+
+    /// The circuit needs more rows than `max_k` allows
+    #[error("circuit requires k={k} which exceeds max_k={max_k}")]
+    CircuitTooLarge { k: u32, max_k: u32 },
+
+    /// Any other invalid input: signals, requests, verification contexts
+    #[error("{0}")]
+    Invalid(String),
+}
+
+impl ZkplexError {
+    /// Prefix the message with `context`, keeping the variant
+    ///
+    /// `CircuitTooLarge` has a fixed message and is returned unchanged.
+    pub fn context(self, context: &str) -> Self {
+        let prefix = |message: String| format!("{}: {}", context, message);
+        match self {
+            ZkplexError::Parse(m) => ZkplexError::Parse(prefix(m)),
+            ZkplexError::Encoding(m) => ZkplexError::Encoding(prefix(m)),
+            ZkplexError::Synthesis(m) => ZkplexError::Synthesis(prefix(m)),
+            ZkplexError::DivisionByZero(m) => ZkplexError::DivisionByZero(prefix(m)),
+            ZkplexError::UnsupportedOperation(m) => ZkplexError::UnsupportedOperation(prefix(m)),
+            ZkplexError::Invalid(m) => ZkplexError::Invalid(prefix(m)),
+            e @ ZkplexError::CircuitTooLarge { .. } => e,
+        }
+    }
+}
+
+/// Errors of helpers that still return `String` are invalid input
+impl From<String> for ZkplexError {
+    fn from(message: String) -> Self {
+        ZkplexError::Invalid(message)
+    }
+}
+
+/// Lets `?` pass a `ZkplexError` on from functions returning `String` errors
+impl From<ZkplexError> for String {
+    fn from(error: ZkplexError) -> Self {
+        error.to_string()
+    }
+}
@@ -44,6 +44,7 @@
 pub mod api;
 pub mod circuit;
 pub mod encoding;
+pub mod error;
 pub mod layout;
 pub mod parser;
 pub mod preprocess;
@@ -52,7 +53,8 @@ pub mod wasm;
 // Re-export commonly used types
 pub use api::{ProveRequest, ProveResponse, VerifyRequest, VerifyResponse, Signal};
 pub use encoding::{ValueEncoding, parse_value, parse_value_auto};
-pub use parser::{Expression, BinaryOperator, ComparisonOperator, BooleanOperator, UnaryOperator, parse_circuit, ParseError};
+pub use error::ZkplexError;
+pub use parser::{Expression, BinaryOperator, ComparisonOperator, BooleanOperator, UnaryOperator, BuiltinFunction, parse_circuit, ParseError};
 
 #[cfg(test)]
 mod tests {
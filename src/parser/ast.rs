@@ -41,12 +41,30 @@
 //! - `NOT 123` → 0
 //!
 //! ## Precedence
-//! Parentheses can be used to control operation order
+//! From loosest to tightest binding:
+//! 1. `OR`, `||`, `NOR`
+//! 2. `XOR`, `^^`, `XNOR`
+//! 3. `AND`, `&&`, `NAND`
+//! 4. `>`, `<`, `>=`, `<=`, `==`, `!=` (non-associative: `A < B < C` is rejected)
+//! 5. `+`, `-`
+//! 6. `*`, `/`, `//`
+//! 7. `NOT`, `!`, unary `-`
+//!
+//! Binary operators are left-associative: `A - B - C` is `(A - B) - C`.
+//! Parentheses can be used to control operation order.
+//!
+//! `Display` emits the canonical form: only the parentheses this precedence
+//! requires, so `parse(expr.to_string()) == expr`.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 /// Expression in the circuit AST
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Equality and hashing are structural. The parser stores constants as canonical
+/// decimal strings, so `A > 010` and `A > 10` parse to equal expressions; a
+/// hand-built `Expression::Constant("010")` is compared as written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Expression {
     /// Variable (can be public or secret)
     Variable(String),
@@ -83,26 +101,33 @@ pub enum Expression {
 
     /// Boolean constant
     Boolean(bool),
+
+    /// Built-in function call, e.g. `sqrt(x)`
+    Call {
+        function: BuiltinFunction,
+        argument: Box<Expression>,
+    },
 }
 
 /// Binary arithmetic operators
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BinaryOperator {
     Add,      // +
     Sub,      // -
     Mul,      // *
-    Div,      // /
+    Div,      // / (field division: a * b^-1)
+    IntDiv,   // // (integer division: floor(a / b))
 }
 
 /// Unary operators
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UnaryOperator {
     Neg,      // -x (negation)
     Not,      // NOT x (boolean not)
 }
 
 /// Comparison operators (require range checks)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ComparisonOperator {
     Greater,        // >
     Less,           // <
@@ -113,10 +138,29 @@ pub enum ComparisonOperator {
 }
 
 /// Boolean operators
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BooleanOperator {
     And,    // AND
     Or,     // OR
+    Xor,    // XOR
+    Nand,   // NAND
+    Nor,    // NOR
+    Xnor,   // XNOR
+}
+
+/// Built-in functions (single argument, written `name(x)`)
+///
+/// Arguments are read as unsigned integers (canonical representatives), like `//`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BuiltinFunction {
+    Sqrt,       // sqrt(x): floor integer square root
+    IsSquare,   // is_square(x): 1 if x is a perfect square, else 0
+    IsBool,     // is_bool(x): x, constrained to be 0 or 1
+    Popcount,   // popcount(x): number of set bits
+    Keccak256,  // keccak256(x): in-circuit Keccak-256 of x as 32 big-endian bytes
+    Abs,        // abs(x): magnitude of x read as a signed value
+    Sum,        // sum(array): sum of the elements of an array signal
+    Product,    // product(array): product of the elements of an array signal
 }
 
 impl Expression {
@@ -166,6 +210,15 @@ impl Expression {
         }
     }
 
+    /// Helper to create an integer division expression
+    pub fn int_div(left: Expression, right: Expression) -> Self {
+        Expression::BinaryOp {
+            op: BinaryOperator::IntDiv,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
     /// Helper to create a comparison expression
     pub fn compare(op: ComparisonOperator, left: Expression, right: Expression) -> Self {
         Expression::Comparison {
@@ -193,6 +246,42 @@ impl Expression {
         }
     }
 
+    /// Helper to create a boolean XOR expression
+    pub fn xor(left: Expression, right: Expression) -> Self {
+        Expression::BooleanOp {
+            op: BooleanOperator::Xor,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Helper to create a boolean NAND expression
+    pub fn nand(left: Expression, right: Expression) -> Self {
+        Expression::BooleanOp {
+            op: BooleanOperator::Nand,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Helper to create a boolean NOR expression
+    pub fn nor(left: Expression, right: Expression) -> Self {
+        Expression::BooleanOp {
+            op: BooleanOperator::Nor,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
+    /// Helper to create a boolean XNOR expression
+    pub fn xnor(left: Expression, right: Expression) -> Self {
+        Expression::BooleanOp {
+            op: BooleanOperator::Xnor,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+
     /// Helper to create a NOT expression
     pub fn not(operand: Expression) -> Self {
         Expression::UnaryOp {
@@ -201,6 +290,14 @@ impl Expression {
         }
     }
 
+    /// Helper to create a built-in function call
+    pub fn call(function: BuiltinFunction, argument: Expression) -> Self {
+        Expression::Call {
+            function,
+            argument: Box::new(argument),
+        }
+    }
+
     /// Get all variable names used in this expression
     pub fn variables(&self) -> Vec<String> {
         let mut vars = Vec::new();
@@ -210,6 +307,79 @@ impl Expression {
         vars
     }
 
+    /// Get all variable names used in this expression, deduplicated and sorted
+    pub fn variables_sorted(&self) -> Vec<String> {
+        self.variable_set().into_iter().collect()
+    }
+
+    /// Get the set of variable names used in this expression
+    ///
+    /// Handy for checking that every referenced variable has a signal:
+    /// `expr.variable_set().difference(&signal_names)`.
+    pub fn variable_set(&self) -> BTreeSet<String> {
+        let mut vars = Vec::new();
+        self.collect_variables(&mut vars);
+        vars.into_iter().collect()
+    }
+
+    /// Number of nodes on the longest path from this node to a leaf
+    ///
+    /// A single variable or constant has depth 1; `A + B * C` has depth 3.
+    pub fn depth(&self) -> usize {
+        1 + match self {
+            Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => 0,
+            Expression::UnaryOp { operand, .. } => operand.depth(),
+            Expression::Call { argument, .. } => argument.depth(),
+            Expression::BinaryOp { left, right, .. }
+            | Expression::Comparison { left, right, .. }
+            | Expression::BooleanOp { left, right, .. } => left.depth().max(right.depth()),
+        }
+    }
+
+    /// Render the expression as an indented tree, one node per line
+    ///
+    /// Makes operator precedence visible: `A + B * C > D` becomes
+    ///
+    /// ```text
+    /// >
+    /// ├── +
+    /// │   ├── A
+    /// │   └── *
+    /// │       ├── B
+    /// │       └── C
+    /// └── D
+    /// ```
+    pub fn to_tree_string(&self) -> String {
+        let mut out = String::new();
+        self.write_tree(&mut out, "", "");
+        out
+    }
+
+    fn write_tree(&self, out: &mut String, prefix: &str, child_prefix: &str) {
+        let (label, children): (String, Vec<&Expression>) = match self {
+            Expression::Variable(name) => (name.clone(), vec![]),
+            Expression::Constant(value) => (value.clone(), vec![]),
+            Expression::Boolean(b) => (b.to_string(), vec![]),
+            Expression::BinaryOp { op, left, right } => (op.to_string(), vec![left, right]),
+            Expression::UnaryOp { op, operand } => (op.to_string().trim().to_string(), vec![operand]),
+            Expression::Call { function, argument } => (function.to_string(), vec![argument]),
+            Expression::Comparison { op, left, right } => (op.to_string(), vec![left, right]),
+            Expression::BooleanOp { op, left, right } => (op.to_string(), vec![left, right]),
+        };
+
+        out.push_str(prefix);
+        out.push_str(&label);
+        out.push('\n');
+
+        for (i, child) in children.iter().enumerate() {
+            if i + 1 == children.len() {
+                child.write_tree(out, &format!("{}└── ", child_prefix), &format!("{}    ", child_prefix));
+            } else {
+                child.write_tree(out, &format!("{}├── ", child_prefix), &format!("{}│   ", child_prefix));
+            }
+        }
+    }
+
     fn collect_variables(&self, vars: &mut Vec<String>) {
         match self {
             Expression::Variable(name) => vars.push(name.clone()),
@@ -221,6 +391,9 @@ impl Expression {
             Expression::UnaryOp { operand, .. } => {
                 operand.collect_variables(vars);
             }
+            Expression::Call { argument, .. } => {
+                argument.collect_variables(vars);
+            }
             Expression::Comparison { left, right, .. } => {
                 left.collect_variables(vars);
                 right.collect_variables(vars);
@@ -233,23 +406,67 @@ impl Expression {
     }
 }
 
+impl Expression {
+    /// Binding strength of the top-level operator (higher binds tighter)
+    ///
+    /// Mirrors the grammar levels in `circuit.pest`.
+    fn precedence(&self) -> u8 {
+        match self {
+            Expression::BooleanOp { op: BooleanOperator::Or | BooleanOperator::Nor, .. } => 1,
+            Expression::BooleanOp { op: BooleanOperator::Xor | BooleanOperator::Xnor, .. } => 2,
+            Expression::BooleanOp { op: BooleanOperator::And | BooleanOperator::Nand, .. } => 3,
+            Expression::Comparison { .. } => 4,
+            Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Sub, .. } => 5,
+            Expression::BinaryOp { .. } => 6,
+            Expression::UnaryOp { .. } => 7,
+            Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) |
+            Expression::Call { .. } => 8,
+        }
+    }
+
+    /// Write `self`, parenthesized if it binds looser than `min_precedence`
+    fn fmt_operand(&self, f: &mut std::fmt::Formatter<'_>, min_precedence: u8) -> std::fmt::Result {
+        if self.precedence() < min_precedence {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
+/// Canonical form with minimal parentheses
+///
+/// Left-associative operators only parenthesize a right operand of the same level
+/// (`A - (B - C)`), and comparisons parenthesize any comparison operand since they
+/// don't chain.
 impl std::fmt::Display for Expression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let prec = self.precedence();
         match self {
             Expression::Variable(name) => write!(f, "{}", name),
             Expression::Constant(value) => write!(f, "{}", value),
             Expression::Boolean(b) => write!(f, "{}", b),
             Expression::BinaryOp { op, left, right } => {
-                write!(f, "({} {} {})", left, op, right)
+                left.fmt_operand(f, prec)?;
+                write!(f, " {} ", op)?;
+                right.fmt_operand(f, prec + 1)
             }
             Expression::UnaryOp { op, operand } => {
-                write!(f, "({}{})", op, operand)
+                write!(f, "{}", op)?;
+                operand.fmt_operand(f, prec)
+            }
+            Expression::Call { function, argument } => {
+                write!(f, "{}({})", function, argument)
             }
             Expression::Comparison { op, left, right } => {
-                write!(f, "({} {} {})", left, op, right)
+                left.fmt_operand(f, prec + 1)?;
+                write!(f, " {} ", op)?;
+                right.fmt_operand(f, prec + 1)
             }
             Expression::BooleanOp { op, left, right } => {
-                write!(f, "({} {} {})", left, op, right)
+                left.fmt_operand(f, prec)?;
+                write!(f, " {} ", op)?;
+                right.fmt_operand(f, prec + 1)
             }
         }
     }
@@ -262,6 +479,22 @@ impl std::fmt::Display for BinaryOperator {
             BinaryOperator::Sub => write!(f, "-"),
             BinaryOperator::Mul => write!(f, "*"),
             BinaryOperator::Div => write!(f, "/"),
+            BinaryOperator::IntDiv => write!(f, "//"),
+        }
+    }
+}
+
+impl std::fmt::Display for BuiltinFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuiltinFunction::Sqrt => write!(f, "sqrt"),
+            BuiltinFunction::IsSquare => write!(f, "is_square"),
+            BuiltinFunction::IsBool => write!(f, "is_bool"),
+            BuiltinFunction::Popcount => write!(f, "popcount"),
+            BuiltinFunction::Keccak256 => write!(f, "keccak256"),
+            BuiltinFunction::Abs => write!(f, "abs"),
+            BuiltinFunction::Sum => write!(f, "sum"),
+            BuiltinFunction::Product => write!(f, "product"),
         }
     }
 }
@@ -293,6 +526,10 @@ impl std::fmt::Display for BooleanOperator {
         match self {
             BooleanOperator::And => write!(f, "AND"),
             BooleanOperator::Or => write!(f, "OR"),
+            BooleanOperator::Xor => write!(f, "XOR"),
+            BooleanOperator::Nand => write!(f, "NAND"),
+            BooleanOperator::Nor => write!(f, "NOR"),
+            BooleanOperator::Xnor => write!(f, "XNOR"),
         }
     }
 }
@@ -362,6 +599,77 @@ mod tests {
             Expression::var("C"),
         );
 
-        assert_eq!(expr.to_string(), "((A + B) * C)");
+        assert_eq!(expr.to_string(), "(A + B) * C");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_to_tree_string_shows_precedence() {
+        let expr = crate::parser::parse_circuit("A + B * C > D").unwrap();
+
+        assert_eq!(
+            expr.to_tree_string(),
+            ">\n\
+             ├── +\n\
+             │   ├── A\n\
+             │   └── *\n\
+             │       ├── B\n\
+             │       └── C\n\
+             └── D\n"
+        );
+    }
+
+    #[test]
+    fn test_structural_equality_finds_duplicate_subtree() {
+        use std::collections::HashSet;
+
+        fn visit(expr: &Expression, seen: &mut HashSet<Expression>, duplicates: &mut Vec<String>) {
+            if !seen.insert(expr.clone()) {
+                duplicates.push(expr.to_string());
+            }
+            match expr {
+                Expression::BinaryOp { left, right, .. }
+                | Expression::Comparison { left, right, .. }
+                | Expression::BooleanOp { left, right, .. } => {
+                    visit(left, seen, duplicates);
+                    visit(right, seen, duplicates);
+                }
+                Expression::UnaryOp { operand, .. } => visit(operand, seen, duplicates),
+                Expression::Call { argument, .. } => visit(argument, seen, duplicates),
+                Expression::Variable(_) | Expression::Constant(_) | Expression::Boolean(_) => {}
+            }
+        }
+
+        let expr = crate::parser::parse_circuit("(A + B) * C > (A + B)").unwrap();
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+        visit(&expr, &mut seen, &mut duplicates);
+
+        // The second A + B and its leaves are repeats
+        assert_eq!(duplicates, vec!["A + B", "A", "B"]);
+
+        // Constants are canonical, whatever the literal form
+        let parse = |s| crate::parser::parse_circuit(s).unwrap();
+        assert_eq!(parse("A > 010"), parse("A > 10"));
+        assert_eq!(parse("A > 0xa"), parse("A > 10"));
+        assert_ne!(parse("A > 10"), parse("A >= 10"));
+    }
+
+    #[test]
+    fn test_variable_set_is_deduplicated() {
+        // A + B * A
+        let expr = Expression::add(
+            Expression::var("A"),
+            Expression::mul(Expression::var("B"), Expression::var("A")),
+        );
+
+        let expected: BTreeSet<String> = ["A", "B"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(expr.variable_set(), expected);
+        assert_eq!(expr.variables_sorted(), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_to_tree_string_unary() {
+        let expr = Expression::not(Expression::var("A"));
+        assert_eq!(expr.to_tree_string(), "NOT\n└── A\n");
+    }
+}
@@ -6,6 +6,8 @@ use pest::Parser;
 use pest::iterators::Pair;
 use pest_derive::Parser;
 use super::ast::*;
+use num_bigint::BigUint;
+use num_traits::Num;
 use thiserror::Error;
 
 #[derive(Parser)]
@@ -22,12 +24,45 @@ pub enum ParseError {
 
     #[error("Unknown operator: {0}")]
     UnknownOperator(String),
+
+    #[error("Invalid number literal: {0}")]
+    InvalidNumber(String),
+
+    #[error("Chained comparison '{expression}' is not supported: comparisons don't chain. Write '{suggestion}' instead")]
+    ChainedComparison { expression: String, suggestion: String },
+
+    #[error("{function}() takes the name of an array signal, got '{argument}'")]
+    ArrayArgument { function: String, argument: String },
+
+    #[error("Expression is nested more than {depth} levels deep; split it into intermediate signals with '<=='")]
+    TooDeep { depth: usize },
 }
 
 pub type ParseResult<T> = Result<T, ParseError>;
 
+/// Deepest expression nesting `parse_circuit` accepts
+///
+/// The parser, evaluation and synthesis all recurse over the expression, so
+/// deeper input could overflow the stack.
+pub const MAX_EXPRESSION_DEPTH: usize = 256;
+
 /// Parse a circuit string into an Expression AST
+///
+/// Rejects expressions nested deeper than [`MAX_EXPRESSION_DEPTH`].
 pub fn parse_circuit(input: &str) -> ParseResult<Expression> {
+    parse_circuit_with_max_depth(input, MAX_EXPRESSION_DEPTH)
+}
+
+/// Parse a circuit string, rejecting expressions nested deeper than `max_depth`
+///
+/// Nesting is checked on the raw text before parsing, so thousands of
+/// parentheses fail cleanly instead of overflowing the parser's stack, and
+/// again on the resulting tree, which catches long operator chains.
+pub fn parse_circuit_with_max_depth(input: &str, max_depth: usize) -> ParseResult<Expression> {
+    if nesting_depth(input) > max_depth {
+        return Err(ParseError::TooDeep { depth: max_depth });
+    }
+
     let pairs = CircuitParser::parse(Rule::circuit, input)?;
 
     for pair in pairs {
@@ -35,7 +70,11 @@ pub fn parse_circuit(input: &str) -> ParseResult<Expression> {
             Rule::circuit => {
                 // Get the expression inside
                 if let Some(expr_pair) = pair.into_inner().next() {
-                    return parse_expression(expr_pair);
+                    let expr = parse_expression(expr_pair)?;
+                    if expr.depth() > max_depth {
+                        return Err(ParseError::TooDeep { depth: max_depth });
+                    }
+                    return Ok(expr);
                 }
             }
             _ => {}
@@ -45,6 +84,66 @@ pub fn parse_circuit(input: &str) -> ParseResult<Expression> {
     Err(ParseError::InvalidStructure)
 }
 
+/// Nesting of brackets and prefix operators (`NOT`, `!`, unary `-`) in the raw text
+///
+/// Each open bracket counts one level, plus one for every prefix operator
+/// applied to it, e.g. `NOT (-(A))` is 4 levels deep.
+fn nesting_depth(input: &str) -> usize {
+    let bytes = input.as_bytes();
+    // Levels added by each open bracket, to drop them when it closes
+    let mut open = Vec::new();
+    let mut depth = 0;
+    // Prefix operators waiting for their operand
+    let mut prefixes = 0;
+    let mut after_operand = false;
+    let mut max = 0;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' | b'[' => {
+                open.push(prefixes + 1);
+                depth += prefixes + 1;
+                prefixes = 0;
+                after_operand = false;
+            }
+            b')' | b']' => {
+                depth -= open.pop().unwrap_or(0);
+                prefixes = 0;
+                after_operand = true;
+            }
+            b'!' if bytes.get(i + 1) != Some(&b'=') => prefixes += 1,
+            b'-' if !after_operand => prefixes += 1,
+            c if c.is_ascii_alphanumeric() || c == b'_' => {
+                let start = i;
+                while bytes.get(i + 1).is_some_and(|c| c.is_ascii_alphanumeric() || *c == b'_') {
+                    i += 1;
+                }
+                match &input[start..=i] {
+                    "NOT" => prefixes += 1,
+                    "AND" | "OR" | "XOR" | "NAND" | "NOR" | "XNOR" => after_operand = false,
+                    _ => {
+                        prefixes = 0;
+                        after_operand = true;
+                    }
+                }
+            }
+            // Comments run to the end of the line
+            b'#' => {
+                while bytes.get(i + 1).is_some_and(|c| *c != b'\n') {
+                    i += 1;
+                }
+            }
+            c if c.is_ascii_whitespace() => {}
+            _ => after_operand = false,
+        }
+        max = max.max(depth + prefixes);
+        i += 1;
+    }
+
+    max
+}
+
 fn parse_expression(pair: Pair<Rule>) -> ParseResult<Expression> {
     match pair.as_rule() {
         Rule::expression => {
@@ -57,17 +156,44 @@ fn parse_expression(pair: Pair<Rule>) -> ParseResult<Expression> {
 
 fn parse_boolean_or(pair: Pair<Rule>) -> ParseResult<Expression> {
     let mut inner = pair.into_inner();
-    let mut left = parse_boolean_and(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+    let mut left = parse_boolean_xor(inner.next().ok_or(ParseError::InvalidStructure)?)?;
 
     while let Some(op_or_right) = inner.next() {
         match op_or_right.as_rule() {
             Rule::or_op => {
-                let right = parse_boolean_and(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+                let right = parse_boolean_xor(inner.next().ok_or(ParseError::InvalidStructure)?)?;
                 left = Expression::or(left, right);
             }
+            Rule::nor_op => {
+                let right = parse_boolean_xor(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+                left = Expression::nor(left, right);
+            }
             _ => {
                 // If it's not an operator, it must be the right side of a previous operation
-                left = Expression::or(left, parse_boolean_and(op_or_right)?);
+                left = Expression::or(left, parse_boolean_xor(op_or_right)?);
+            }
+        }
+    }
+
+    Ok(left)
+}
+
+fn parse_boolean_xor(pair: Pair<Rule>) -> ParseResult<Expression> {
+    let mut inner = pair.into_inner();
+    let mut left = parse_boolean_and(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+
+    while let Some(op_or_right) = inner.next() {
+        match op_or_right.as_rule() {
+            Rule::xor_op => {
+                let right = parse_boolean_and(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+                left = Expression::xor(left, right);
+            }
+            Rule::xnor_op => {
+                let right = parse_boolean_and(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+                left = Expression::xnor(left, right);
+            }
+            _ => {
+                left = Expression::xor(left, parse_boolean_and(op_or_right)?);
             }
         }
     }
@@ -85,6 +211,10 @@ fn parse_boolean_and(pair: Pair<Rule>) -> ParseResult<Expression> {
                 let right = parse_comparison(inner.next().ok_or(ParseError::InvalidStructure)?)?;
                 left = Expression::and(left, right);
             }
+            Rule::nand_op => {
+                let right = parse_comparison(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+                left = Expression::nand(left, right);
+            }
             _ => {
                 left = Expression::and(left, parse_comparison(op_or_right)?);
             }
@@ -95,6 +225,14 @@ fn parse_boolean_and(pair: Pair<Rule>) -> ParseResult<Expression> {
 }
 
 fn parse_comparison(pair: Pair<Rule>) -> ParseResult<Expression> {
+    let source = pair.as_str().trim().to_string();
+    let parts: Vec<Pair<Rule>> = pair.clone().into_inner().collect();
+
+    // additive (op additive)* - more than one operator is a chain like `A < B < C`
+    if parts.len() > 3 {
+        return Err(chained_comparison_error(source, &parts));
+    }
+
     let mut inner = pair.into_inner();
     let left = parse_additive(inner.next().ok_or(ParseError::InvalidStructure)?)?;
 
@@ -118,6 +256,20 @@ fn parse_comparison(pair: Pair<Rule>) -> ParseResult<Expression> {
     Ok(left)
 }
 
+/// Build the error for `A < B < C`, suggesting `(A < B) AND (B < C)`
+fn chained_comparison_error(expression: String, parts: &[Pair<Rule>]) -> ParseError {
+    let operands: Vec<&str> = parts.iter().step_by(2).map(|p| p.as_str().trim()).collect();
+    let operators: Vec<&str> = parts.iter().skip(1).step_by(2).map(|p| p.as_str()).collect();
+
+    let suggestion = operators.iter()
+        .enumerate()
+        .map(|(i, op)| format!("({} {} {})", operands[i], op, operands[i + 1]))
+        .collect::<Vec<_>>()
+        .join(" AND ");
+
+    ParseError::ChainedComparison { expression, suggestion }
+}
+
 fn parse_additive(pair: Pair<Rule>) -> ParseResult<Expression> {
     let mut inner = pair.into_inner();
     let mut left = parse_multiplicative(inner.next().ok_or(ParseError::InvalidStructure)?)?;
@@ -148,6 +300,7 @@ fn parse_multiplicative(pair: Pair<Rule>) -> ParseResult<Expression> {
         let op = match op_pair.as_rule() {
             Rule::mul_op => BinaryOperator::Mul,
             Rule::div_op => BinaryOperator::Div,
+            Rule::int_div_op => BinaryOperator::IntDiv,
             _ => return Err(ParseError::InvalidStructure),
         };
 
@@ -187,17 +340,118 @@ fn parse_primary(pair: Pair<Rule>) -> ParseResult<Expression> {
     let inner = pair.into_inner().next().ok_or(ParseError::InvalidStructure)?;
 
     match inner.as_rule() {
-        Rule::number => Ok(Expression::Constant(inner.as_str().to_string())),
-        Rule::variable => Ok(Expression::Variable(inner.as_str().to_string())),
+        Rule::number => parse_number(inner.as_str()),
+        Rule::variable => parse_variable(inner.as_str()),
         Rule::boolean => {
             let value = matches!(inner.as_str(), "true" | "TRUE");
             Ok(Expression::Boolean(value))
         }
         Rule::expression => parse_expression(inner),
+        Rule::call => parse_call(inner),
+        Rule::approx_eq_call => parse_approx_eq(inner),
+        Rule::not_in_call => parse_not_in(inner),
         _ => Err(ParseError::InvalidStructure),
     }
 }
 
+/// Desugar `approx_eq(a, b, tol)` into `abs(a - b) <= tol`
+fn parse_approx_eq(pair: Pair<Rule>) -> ParseResult<Expression> {
+    let mut inner = pair.into_inner();
+    let mut next = || parse_expression(inner.next().ok_or(ParseError::InvalidStructure)?);
+    let (a, b, tol) = (next()?, next()?, next()?);
+
+    Ok(Expression::compare(
+        ComparisonOperator::LessEqual,
+        Expression::call(BuiltinFunction::Abs, Expression::sub(a, b)),
+        tol,
+    ))
+}
+
+/// Desugar `not_in(x, [a, b, ...])` into `(x != a) AND (x != b) AND ...`
+///
+/// This is `NOT (x == a OR x == b OR ...)`: 1 when `x` matches no member.
+fn parse_not_in(pair: Pair<Rule>) -> ParseResult<Expression> {
+    let mut inner = pair.into_inner();
+    let x = parse_expression(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+
+    inner
+        .map(|member| Ok(Expression::compare(ComparisonOperator::NotEqual, x.clone(), parse_expression(member)?)))
+        .reduce(|acc, next| Ok(Expression::and(acc?, next?)))
+        .ok_or(ParseError::InvalidStructure)?
+}
+
+fn parse_call(pair: Pair<Rule>) -> ParseResult<Expression> {
+    let mut inner = pair.into_inner();
+    let name = inner.next().ok_or(ParseError::InvalidStructure)?;
+
+    let function = match name.as_str() {
+        "sqrt" => BuiltinFunction::Sqrt,
+        "is_square" => BuiltinFunction::IsSquare,
+        "is_bool" => BuiltinFunction::IsBool,
+        "popcount" => BuiltinFunction::Popcount,
+        "keccak256" => BuiltinFunction::Keccak256,
+        "abs" => BuiltinFunction::Abs,
+        "sum" => BuiltinFunction::Sum,
+        "product" => BuiltinFunction::Product,
+        other => return Err(ParseError::UnknownOperator(other.to_string())),
+    };
+
+    let argument = parse_expression(inner.next().ok_or(ParseError::InvalidStructure)?)?;
+
+    // Reductions are expanded over the elements of a named array signal
+    if matches!(function, BuiltinFunction::Sum | BuiltinFunction::Product)
+        && !matches!(argument, Expression::Variable(_))
+    {
+        return Err(ParseError::ArrayArgument { function: function.to_string(), argument: argument.to_string() });
+    }
+    Ok(Expression::call(function, argument))
+}
+
+/// Parse a signal name
+///
+/// A name made of an underscore followed by digits (`_100`) is a misplaced
+/// digit separator rather than a signal, and is rejected as a number literal.
+fn parse_variable(name: &str) -> ParseResult<Expression> {
+    if name.starts_with('_')
+        && name.chars().any(|c| c.is_ascii_digit())
+        && name.chars().all(|c| c == '_' || c.is_ascii_digit())
+    {
+        return Err(ParseError::InvalidNumber(name.to_string()));
+    }
+    Ok(Expression::Variable(name.to_string()))
+}
+
+/// Parse an integer literal into a decimal `Expression::Constant`
+///
+/// Supports `0x` (hex), `0o` (octal), `0b` (binary) prefixes and plain decimal.
+/// Digits may be grouped with single underscores between them (`1_000_000`).
+/// The constant is always stored as a canonical decimal string (no leading zeros),
+/// so downstream code (circuit builder, evaluation) only ever deals with radix 10
+/// and `010`, `10` and `0xa` parse to equal expressions.
+fn parse_number(literal: &str) -> ParseResult<Expression> {
+    let lower = literal.to_ascii_lowercase();
+    let (digits, radix) = if let Some(rest) = lower.strip_prefix("0x") {
+        (rest, 16)
+    } else if let Some(rest) = lower.strip_prefix("0o") {
+        (rest, 8)
+    } else if let Some(rest) = lower.strip_prefix("0b") {
+        (rest, 2)
+    } else {
+        (lower.as_str(), 10)
+    };
+
+    // Underscores only separate digits: not leading, trailing or doubled
+    if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+        return Err(ParseError::InvalidNumber(literal.to_string()));
+    }
+    let digits = digits.replace('_', "");
+
+    let value = BigUint::from_str_radix(&digits, radix)
+        .map_err(|_| ParseError::InvalidNumber(literal.to_string()))?;
+
+    Ok(Expression::Constant(value.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,6 +502,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_xor() {
+        for input in ["A XOR B", "A ^^ B"] {
+            match parse_circuit(input).unwrap() {
+                Expression::BooleanOp { op, .. } => assert_eq!(op, BooleanOperator::Xor),
+                _ => panic!("Expected XOR operation"),
+            }
+        }
+
+        // XOR binds tighter than OR and looser than AND
+        match parse_circuit("A OR B XOR C AND D").unwrap() {
+            Expression::BooleanOp { op: BooleanOperator::Or, right, .. } => match *right {
+                Expression::BooleanOp { op: BooleanOperator::Xor, right, .. } => {
+                    assert!(matches!(*right, Expression::BooleanOp { op: BooleanOperator::And, .. }));
+                }
+                _ => panic!("Expected XOR on the right of OR"),
+            },
+            _ => panic!("Expected OR at the top"),
+        }
+    }
+
+    #[test]
+    fn test_parse_negated_boolean_ops() {
+        for (input, expected) in [
+            ("A NAND B", BooleanOperator::Nand),
+            ("A NOR B", BooleanOperator::Nor),
+            ("A XNOR B", BooleanOperator::Xnor),
+        ] {
+            match parse_circuit(input).unwrap() {
+                Expression::BooleanOp { op, .. } => assert_eq!(op, expected, "{}", input),
+                _ => panic!("Expected boolean operation for {}", input),
+            }
+        }
+    }
+
     #[test]
     fn test_parse_not() {
         let expr = parse_circuit("NOT (A > B)").unwrap();
@@ -257,6 +546,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_bang_matches_not() {
+        let cases = [
+            ("!A", "NOT A"),
+            ("!(A > B)", "NOT (A > B)"),
+            ("!(A==B) AND C>D", "NOT(A==B) AND C>D"),
+            ("C > D OR !(A == B)", "C > D OR NOT (A == B)"),
+            ("!A > B", "NOT A > B"),
+            ("A == !B", "A == NOT B"),
+            ("A != !B", "A != NOT B"),
+            ("!!A", "NOT NOT A"),
+            ("!-A", "NOT -A"),
+            ("!is_bool(X)", "NOT is_bool(X)"),
+            ("sqrt(!A)", "sqrt(NOT A)"),
+            ("!not_in(X, [1, 2])", "NOT not_in(X, [1, 2])"),
+            ("A XOR !(B AND C)", "A XOR NOT (B AND C)"),
+        ];
+
+        for (bang, not) in cases {
+            let expected = parse_circuit(not).unwrap();
+            assert_eq!(parse_circuit(bang).unwrap(), expected, "'{}' vs '{}'", bang, not);
+        }
+
+        assert!(matches!(
+            parse_circuit("!(A==B) AND C>D").unwrap(),
+            Expression::BooleanOp { op: BooleanOperator::And, left, .. }
+                if matches!(*left, Expression::UnaryOp { op: UnaryOperator::Not, .. })
+        ));
+    }
+
     #[test]
     fn test_parse_precedence() {
         // Test that * has higher precedence than +
@@ -299,4 +618,265 @@ mod tests {
             _ => panic!("Expected multiplication at top level"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_hex_literal() {
+        let expr = parse_circuit("A == 0xff").unwrap();
+        match expr {
+            Expression::Comparison { right, .. } => {
+                assert_eq!(*right, Expression::Constant("255".to_string()));
+            }
+            _ => panic!("Expected comparison"),
+        }
+    }
+
+    #[test]
+    fn test_parse_octal_literal() {
+        let expr = parse_circuit("0o755").unwrap();
+        assert_eq!(expr, Expression::Constant("493".to_string()));
+    }
+
+    #[test]
+    fn test_parse_binary_literal() {
+        let expr = parse_circuit("0b0100").unwrap();
+        assert_eq!(expr, Expression::Constant("4".to_string()));
+
+        let expr = parse_circuit("0B11").unwrap();
+        assert_eq!(expr, Expression::Constant("3".to_string()));
+    }
+
+    #[test]
+    fn test_parse_invalid_radix_digits() {
+        assert!(parse_circuit("0b102").is_err());
+        assert!(parse_circuit("0o8").is_err());
+        assert!(parse_circuit("0xZZ").is_err());
+    }
+
+    #[test]
+    fn test_parse_digit_separators() {
+        assert_eq!(parse_circuit("1_000_000_000").unwrap(), Expression::Constant("1000000000".to_string()));
+        assert_eq!(parse_circuit("0xff_ff").unwrap(), Expression::Constant("65535".to_string()));
+        assert_eq!(parse_circuit("0b1010_0101").unwrap(), Expression::Constant("165".to_string()));
+        assert_eq!(parse_circuit("A > 1_000").unwrap(), parse_circuit("A > 1000").unwrap());
+
+        for literal in ["_100", "100_", "1__0", "0x_ff", "A > 1_000_"] {
+            assert!(
+                matches!(parse_circuit(literal), Err(ParseError::InvalidNumber(_))),
+                "{} should be rejected", literal
+            );
+        }
+        // Other names starting with an underscore are still signals
+        assert_eq!(parse_circuit("_a1").unwrap(), Expression::Variable("_a1".to_string()));
+    }
+
+    #[test]
+    fn test_parse_int_div() {
+        let expr = parse_circuit("A // B + 1").unwrap();
+        assert_eq!(
+            expr,
+            Expression::add(
+                Expression::int_div(Expression::var("A"), Expression::var("B")),
+                Expression::constant("1"),
+            )
+        );
+
+        // Single slash stays field division
+        let expr = parse_circuit("A / B").unwrap();
+        assert_eq!(expr, Expression::div(Expression::var("A"), Expression::var("B")));
+    }
+
+    #[test]
+    fn test_canonical_round_trip() {
+        let corpus = [
+            "A + B * C > D",
+            "(A + B) * C",
+            "A - B - C",
+            "A - (B - C)",
+            "A / B / C",
+            "A // (B * C)",
+            "-A * B",
+            "-(A * B)",
+            "A - -B",
+            "NOT A > B",
+            "NOT (A > B)",
+            "!!A",
+            "(A > B) == 1",
+            "A > B AND C < D OR E == F",
+            "A OR B AND C",
+            "(A OR B) AND C",
+            "A XOR B OR C XNOR D",
+            "A NAND (B NOR C)",
+            "A AND (B AND C)",
+            "((((A))))",
+            "0xff + 0b101 * true",
+            "sqrt(A - B) * 2 >= is_square(C)",
+        ];
+
+        for input in corpus {
+            let parsed = parse_circuit(input).unwrap();
+            let canonical = parsed.to_string();
+            let reparsed = parse_circuit(&canonical)
+                .unwrap_or_else(|e| panic!("canonical form '{}' of '{}' failed to parse: {}", canonical, input, e));
+            assert_eq!(reparsed, parsed, "'{}' -> '{}'", input, canonical);
+            // Canonical form is a fixed point
+            assert_eq!(reparsed.to_string(), canonical);
+        }
+    }
+
+    #[test]
+    fn test_canonical_and_or_precedence() {
+        // OR binds looser than AND, so no parentheses are needed here...
+        assert_eq!(parse_circuit("(A AND B) OR C").unwrap().to_string(), "A AND B OR C");
+        // ...but they are needed to group an OR under an AND
+        assert_eq!(parse_circuit("(A OR B) AND C").unwrap().to_string(), "(A OR B) AND C");
+        assert_eq!(parse_circuit("A OR B AND C").unwrap().to_string(), "A OR B AND C");
+    }
+
+    #[test]
+    fn test_canonical_chained_subtraction() {
+        // Left-associative: A - B - C is (A - B) - C
+        assert_eq!(parse_circuit("(A - B) - C").unwrap().to_string(), "A - B - C");
+        assert_eq!(parse_circuit("A - (B - C)").unwrap().to_string(), "A - (B - C)");
+        assert_ne!(parse_circuit("A - B - C").unwrap(), parse_circuit("A - (B - C)").unwrap());
+    }
+
+    #[test]
+    fn test_chained_comparison_error() {
+        let err = parse_circuit("A < B < C").unwrap_err();
+        assert!(matches!(err, ParseError::ChainedComparison { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Chained comparison 'A < B < C' is not supported: comparisons don't chain. \
+             Write '(A < B) AND (B < C)' instead"
+        );
+
+        match parse_circuit("A + 1 == B != C * 2").unwrap_err() {
+            ParseError::ChainedComparison { suggestion, .. } => {
+                assert_eq!(suggestion, "(A + 1 == B) AND (B != C * 2)");
+            }
+            e => panic!("Expected chained comparison error, got {}", e),
+        }
+
+        // Explicit grouping still compares the 0/1 result
+        assert!(parse_circuit("(A < B) < C").is_ok());
+    }
+
+    #[test]
+    fn test_parse_builtin_calls() {
+        assert_eq!(
+            parse_circuit("sqrt(A + 1) > 3").unwrap(),
+            Expression::compare(
+                ComparisonOperator::Greater,
+                Expression::call(
+                    BuiltinFunction::Sqrt,
+                    Expression::add(Expression::var("A"), Expression::constant("1")),
+                ),
+                Expression::constant("3"),
+            )
+        );
+        assert!(matches!(
+            parse_circuit("is_square(X)").unwrap(),
+            Expression::Call { function: BuiltinFunction::IsSquare, .. }
+        ));
+        assert!(matches!(
+            parse_circuit("is_bool(flag)").unwrap(),
+            Expression::Call { function: BuiltinFunction::IsBool, .. }
+        ));
+        assert!(matches!(
+            parse_circuit("popcount(flags)").unwrap(),
+            Expression::Call { function: BuiltinFunction::Popcount, .. }
+        ));
+        assert!(matches!(
+            parse_circuit("abs(A - B)").unwrap(),
+            Expression::Call { function: BuiltinFunction::Abs, .. }
+        ));
+
+        // A name that only starts with a function name is still a variable
+        assert_eq!(parse_circuit("sqrtX").unwrap(), Expression::var("sqrtX"));
+        assert_eq!(parse_circuit("sqrt(X)").unwrap().to_string(), "sqrt(X)");
+    }
+
+    #[test]
+    fn test_parse_array_reductions() {
+        assert_eq!(
+            parse_circuit("sum(votes) >= 3").unwrap(),
+            Expression::compare(
+                ComparisonOperator::GreaterEqual,
+                Expression::call(BuiltinFunction::Sum, Expression::var("votes")),
+                Expression::constant("3"),
+            )
+        );
+        assert_eq!(parse_circuit("product(xs)").unwrap().to_string(), "product(xs)");
+
+        // A variable named `sum` is still a variable
+        assert_eq!(parse_circuit("sum * 2").unwrap(), Expression::mul(Expression::var("sum"), Expression::constant("2")));
+
+        // Reductions only take an array signal name
+        assert!(matches!(
+            parse_circuit("sum(A + B)").unwrap_err(),
+            ParseError::ArrayArgument { .. }
+        ));
+    }
+
+    #[test]
+    fn test_parse_not_in() {
+        assert_eq!(
+            parse_circuit("not_in(addr, [1, B, 0x10])").unwrap(),
+            parse_circuit("(addr != 1) AND (addr != B) AND (addr != 16)").unwrap()
+        );
+        assert_eq!(parse_circuit("not_in(A + 1, [5])").unwrap(), parse_circuit("A + 1 != 5").unwrap());
+        assert_eq!(parse_circuit("not_inX").unwrap(), Expression::var("not_inX"));
+
+        // The member list is required and cannot be empty
+        assert!(parse_circuit("not_in(A, [])").is_err());
+        assert!(parse_circuit("not_in(A, 5)").is_err());
+    }
+
+    #[test]
+    fn test_parse_approx_eq() {
+        assert_eq!(
+            parse_circuit("approx_eq(A, B + 1, 2)").unwrap(),
+            parse_circuit("abs(A - (B + 1)) <= 2").unwrap()
+        );
+        assert_eq!(parse_circuit("approx_eqX").unwrap(), Expression::var("approx_eqX"));
+        assert!(parse_circuit("approx_eq(A, B)").is_err());
+    }
+
+    #[test]
+    fn test_parse_too_deep() {
+        let too_deep = |input: &str| matches!(parse_circuit(input), Err(ParseError::TooDeep { depth: MAX_EXPRESSION_DEPTH }));
+
+        // Nesting is rejected before the parser recurses into it
+        assert!(too_deep(&format!("{}A{}", "(".repeat(1000), ")".repeat(1000))));
+        assert!(too_deep(&format!("{}A", "NOT ".repeat(1000))));
+        assert!(too_deep(&format!("{}A", "-".repeat(1000))));
+        assert!(too_deep(&format!("{}A{}", "NOT (".repeat(500), ")".repeat(500))));
+
+        // Long chains parse without recursion but build a deep tree
+        assert!(too_deep(&format!("A{}", " + A".repeat(1000))));
+
+        // Binary minus and != are not prefix operators
+        let chain = format!("A{}", " - A".repeat(200));
+        assert!(parse_circuit(&chain).is_ok());
+        assert!(parse_circuit(&format!("{}A{} != 0", "(".repeat(20), ")".repeat(20))).is_ok());
+
+        let err = parse_circuit_with_max_depth("(A + B) * C", 2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Expression is nested more than 2 levels deep; split it into intermediate signals with '<=='"
+        );
+        assert!(parse_circuit_with_max_depth("(A + B) * C", 3).is_ok());
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        assert_eq!(parse_circuit("age >= 18 # adult check").unwrap(), parse_circuit("age >= 18").unwrap());
+        assert_eq!(
+            parse_circuit("# limits\n(A > 1) # lower\nAND (A < 9) # upper").unwrap(),
+            parse_circuit("(A > 1) AND (A < 9)").unwrap()
+        );
+        // Brackets inside a comment do not count towards the nesting depth
+        assert!(parse_circuit_with_max_depth("A + B # ((((((", 2).is_ok());
+        assert!(parse_circuit("# only a comment").is_err());
+    }
+}
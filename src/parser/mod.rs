@@ -4,6 +4,8 @@
 
 pub mod ast;
 mod circuit;
+mod operators;
 
 pub use ast::*;
-pub use circuit::*;
\ No newline at end of file
+pub use circuit::*;
+pub use operators::*;
\ No newline at end of file
@@ -0,0 +1,143 @@
+//! Operator and built-in function tables
+//!
+//! Lists what circuit expressions accept, for front-ends that offer
+//! autocomplete or operator help. The tables follow `circuit.pest`; the tests
+//! parse every symbol and check each precedence pair against the parser, so a
+//! grammar change that is not reflected here fails them.
+
+use serde::Serialize;
+
+/// Group an operator belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperatorCategory {
+    /// `+`, `-`, `*`, `/`, `//` and unary `-`
+    Arithmetic,
+    /// `>`, `<`, `>=`, `<=`, `==`, `!=` (0 or 1)
+    Comparison,
+    /// `AND`, `OR`, `XOR`, their negations and `NOT` (non-zero is true)
+    Boolean,
+}
+
+/// A circuit operator and the ways to write it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OperatorInfo {
+    /// Operator name, independent of spelling
+    pub name: &'static str,
+    /// Accepted spellings, e.g. `AND` and `&&`
+    pub symbols: &'static [&'static str],
+    pub category: OperatorCategory,
+    /// Binding strength, from 1 (`OR`, loosest) to 7 (unary operators, tightest)
+    pub precedence: u8,
+    /// 1 for prefix operators, 2 for infix ones
+    pub arity: usize,
+}
+
+/// A built-in function callable in circuit expressions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CircuitFunctionInfo {
+    pub name: &'static str,
+    /// Number of arguments (the list in `not_in(x, [a, b])` is one)
+    pub arity: usize,
+    /// What the call evaluates to
+    pub description: &'static str,
+}
+
+const OPERATORS: &[OperatorInfo] = &[
+    OperatorInfo { name: "or", symbols: &["OR", "||"], category: OperatorCategory::Boolean, precedence: 1, arity: 2 },
+    OperatorInfo { name: "nor", symbols: &["NOR"], category: OperatorCategory::Boolean, precedence: 1, arity: 2 },
+    OperatorInfo { name: "xor", symbols: &["XOR", "^^"], category: OperatorCategory::Boolean, precedence: 2, arity: 2 },
+    OperatorInfo { name: "xnor", symbols: &["XNOR"], category: OperatorCategory::Boolean, precedence: 2, arity: 2 },
+    OperatorInfo { name: "and", symbols: &["AND", "&&"], category: OperatorCategory::Boolean, precedence: 3, arity: 2 },
+    OperatorInfo { name: "nand", symbols: &["NAND"], category: OperatorCategory::Boolean, precedence: 3, arity: 2 },
+    OperatorInfo { name: "greater_equal", symbols: &[">="], category: OperatorCategory::Comparison, precedence: 4, arity: 2 },
+    OperatorInfo { name: "less_equal", symbols: &["<="], category: OperatorCategory::Comparison, precedence: 4, arity: 2 },
+    OperatorInfo { name: "equal", symbols: &["=="], category: OperatorCategory::Comparison, precedence: 4, arity: 2 },
+    OperatorInfo { name: "not_equal", symbols: &["!="], category: OperatorCategory::Comparison, precedence: 4, arity: 2 },
+    OperatorInfo { name: "greater", symbols: &[">"], category: OperatorCategory::Comparison, precedence: 4, arity: 2 },
+    OperatorInfo { name: "less", symbols: &["<"], category: OperatorCategory::Comparison, precedence: 4, arity: 2 },
+    OperatorInfo { name: "add", symbols: &["+"], category: OperatorCategory::Arithmetic, precedence: 5, arity: 2 },
+    OperatorInfo { name: "sub", symbols: &["-"], category: OperatorCategory::Arithmetic, precedence: 5, arity: 2 },
+    OperatorInfo { name: "mul", symbols: &["*"], category: OperatorCategory::Arithmetic, precedence: 6, arity: 2 },
+    OperatorInfo { name: "div", symbols: &["/"], category: OperatorCategory::Arithmetic, precedence: 6, arity: 2 },
+    OperatorInfo { name: "int_div", symbols: &["//"], category: OperatorCategory::Arithmetic, precedence: 6, arity: 2 },
+    OperatorInfo { name: "not", symbols: &["NOT", "!"], category: OperatorCategory::Boolean, precedence: 7, arity: 1 },
+    OperatorInfo { name: "neg", symbols: &["-"], category: OperatorCategory::Arithmetic, precedence: 7, arity: 1 },
+];
+
+const FUNCTIONS: &[CircuitFunctionInfo] = &[
+    CircuitFunctionInfo { name: "sqrt", arity: 1, description: "floor integer square root" },
+    CircuitFunctionInfo { name: "is_square", arity: 1, description: "1 if the argument is a perfect square, else 0" },
+    CircuitFunctionInfo { name: "is_bool", arity: 1, description: "the argument, constrained to be 0 or 1" },
+    CircuitFunctionInfo { name: "popcount", arity: 1, description: "number of set bits" },
+    CircuitFunctionInfo { name: "keccak256", arity: 1, description: "in-circuit Keccak-256 of the argument as 32 big-endian bytes" },
+    CircuitFunctionInfo { name: "abs", arity: 1, description: "magnitude of the argument read as a signed value" },
+    CircuitFunctionInfo { name: "sum", arity: 1, description: "sum of the elements of an array signal" },
+    CircuitFunctionInfo { name: "product", arity: 1, description: "product of the elements of an array signal" },
+    CircuitFunctionInfo { name: "approx_eq", arity: 3, description: "1 if |a - b| <= tol, else 0" },
+    CircuitFunctionInfo { name: "not_in", arity: 2, description: "1 if x differs from every element of the list, else 0" },
+];
+
+/// List the operators circuit expressions accept, loosest-binding first
+pub fn circuit_operators() -> Vec<OperatorInfo> {
+    OPERATORS.to_vec()
+}
+
+/// List the built-in functions circuit expressions accept
+pub fn circuit_functions() -> Vec<CircuitFunctionInfo> {
+    FUNCTIONS.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_circuit;
+
+    #[test]
+    fn test_and_symbols_share_operator() {
+        let operators = circuit_operators();
+        let find = |symbol: &str| operators.iter().find(|op| op.arity == 2 && op.symbols.contains(&symbol)).unwrap();
+
+        assert_eq!(find("AND"), find("&&"));
+        assert_eq!(find("&&").precedence, 3);
+        assert!(find("OR").precedence < find("AND").precedence);
+    }
+
+    #[test]
+    fn test_tables_match_parser() {
+        let operators = circuit_operators();
+
+        // Every spelling parses, and spellings of one operator parse alike
+        for op in &operators {
+            let parse = |symbol: &str| match op.arity {
+                1 => parse_circuit(&format!("{} a", symbol)),
+                _ => parse_circuit(&format!("a {} b", symbol)),
+            };
+            let first = parse(op.symbols[0]).unwrap_or_else(|e| panic!("{}: {}", op.name, e));
+            for symbol in op.symbols {
+                assert_eq!(parse(symbol).unwrap(), first, "{}", symbol);
+            }
+        }
+
+        // A tighter operator on the right binds first
+        let binary: Vec<_> = operators.iter().filter(|op| op.arity == 2).collect();
+        for loose in &binary {
+            for tight in binary.iter().filter(|op| op.precedence > loose.precedence) {
+                let (l, t) = (loose.symbols[0], tight.symbols[0]);
+                assert_eq!(
+                    parse_circuit(&format!("a {} b {} c", l, t)).unwrap(),
+                    parse_circuit(&format!("a {} (b {} c)", l, t)).unwrap(),
+                    "{} should bind tighter than {}", t, l
+                );
+            }
+        }
+
+        for function in circuit_functions() {
+            let call = match function.name {
+                "not_in" => "not_in(a, [b, c])".to_string(),
+                name => format!("{}({})", name, vec!["a"; function.arity].join(", ")),
+            };
+            assert!(parse_circuit(&call).is_ok(), "{}", call);
+        }
+    }
+}
@@ -53,97 +53,99 @@ pub enum HashAlgorithm {
 /// assert_eq!(hash.len(), 32); // SHA-256 produces 32 bytes
 /// ```
 pub fn hash(algorithm: HashAlgorithm, data: &[u8]) -> Result<Vec<u8>, String> {
-    match algorithm {
-        HashAlgorithm::SHA1 => Ok(hash_sha1(data)),
-        HashAlgorithm::SHA256 => Ok(hash_sha256(data)),
-        HashAlgorithm::SHA512 => Ok(hash_sha512(data)),
-        HashAlgorithm::SHA3_256 => Ok(hash_sha3_256(data)),
-        HashAlgorithm::SHA3_512 => Ok(hash_sha3_512(data)),
-        HashAlgorithm::MD5 => Ok(hash_md5(data)),
-        HashAlgorithm::CRC32 => Ok(hash_crc32(data)),
-        HashAlgorithm::BLAKE2b => Ok(hash_blake2b(data)),
-        HashAlgorithm::BLAKE3 => Ok(hash_blake3(data)),
-        HashAlgorithm::Keccak256 => Ok(hash_keccak256(data)),
-        HashAlgorithm::RIPEMD160 => Ok(hash_ripemd160(data)),
-    }
-}
-
-/// Compute SHA-1 hash (20 bytes)
-fn hash_sha1(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Sha1::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
-}
-
-/// Compute SHA-256 hash (32 bytes)
-fn hash_sha256(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Sha256::new();
+    let mut hasher = IncrementalHasher::new(algorithm);
     hasher.update(data);
-    hasher.finalize().to_vec()
+    Ok(hasher.finalize())
 }
 
-/// Compute SHA-512 hash (64 bytes)
-fn hash_sha512(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Sha512::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
-}
-
-/// Compute MD5 hash (16 bytes)
-fn hash_md5(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Md5::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
-}
-
-/// Compute CRC32 checksum (4 bytes)
-fn hash_crc32(data: &[u8]) -> Vec<u8> {
-    let checksum = crc32fast::hash(data);
-    checksum.to_be_bytes().to_vec()
-}
-
-/// Compute BLAKE2b hash (32 bytes, truncated from 64)
-fn hash_blake2b(data: &[u8]) -> Vec<u8> {
-    type Blake2b256 = Blake2b<U32>;
-
-    let mut hasher = Blake2b256::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
-}
-
-/// Compute SHA3-256 hash (32 bytes) - Standard SHA3
-fn hash_sha3_256(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Sha3_256::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
+/// Hasher that accepts input in chunks
+///
+/// Produces the same output as [`hash`] over the concatenation of all chunks,
+/// without the caller having to build that concatenation in memory.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut hasher = IncrementalHasher::new(HashAlgorithm::SHA256);
+/// hasher.update(b"hello ");
+/// hasher.update(b"world");
+/// assert_eq!(hasher.finalize(), hash(HashAlgorithm::SHA256, b"hello world")?);
+/// ```
+pub struct IncrementalHasher {
+    state: HasherState,
 }
 
-/// Compute SHA3-512 hash (64 bytes) - Standard SHA3
-fn hash_sha3_512(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Sha3_512::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
+/// Per-algorithm hasher state
+enum HasherState {
+    SHA1(Sha1),
+    SHA256(Sha256),
+    SHA512(Sha512),
+    SHA3_256(Sha3_256),
+    SHA3_512(Sha3_512),
+    MD5(Md5),
+    CRC32(crc32fast::Hasher),
+    BLAKE2b(Blake2b<U32>), // 32 bytes, truncated from 64
+    BLAKE3(Box<Blake3Hasher>),
+    Keccak256(Keccak256), // Ethereum style
+    RIPEMD160(Ripemd160), // Bitcoin style
 }
 
-/// Compute BLAKE3 hash (32 bytes)
-fn hash_blake3(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Blake3Hasher::new();
-    hasher.update(data);
-    hasher.finalize().as_bytes().to_vec()
-}
+impl IncrementalHasher {
+    /// Create a hasher for the given algorithm
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        let state = match algorithm {
+            HashAlgorithm::SHA1 => HasherState::SHA1(Sha1::new()),
+            HashAlgorithm::SHA256 => HasherState::SHA256(Sha256::new()),
+            HashAlgorithm::SHA512 => HasherState::SHA512(Sha512::new()),
+            HashAlgorithm::SHA3_256 => HasherState::SHA3_256(Sha3_256::new()),
+            HashAlgorithm::SHA3_512 => HasherState::SHA3_512(Sha3_512::new()),
+            HashAlgorithm::MD5 => HasherState::MD5(Md5::new()),
+            HashAlgorithm::CRC32 => HasherState::CRC32(crc32fast::Hasher::new()),
+            HashAlgorithm::BLAKE2b => HasherState::BLAKE2b(Blake2b::<U32>::new()),
+            HashAlgorithm::BLAKE3 => HasherState::BLAKE3(Box::new(Blake3Hasher::new())),
+            HashAlgorithm::Keccak256 => HasherState::Keccak256(Keccak256::new()),
+            HashAlgorithm::RIPEMD160 => HasherState::RIPEMD160(Ripemd160::new()),
+        };
+
+        Self { state }
+    }
 
-/// Compute Keccak-256 hash (32 bytes) - Ethereum style
-fn hash_keccak256(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Keccak256::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
-}
+    /// Feed the next chunk of input
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            HasherState::SHA1(hasher) => hasher.update(data),
+            HasherState::SHA256(hasher) => hasher.update(data),
+            HasherState::SHA512(hasher) => hasher.update(data),
+            HasherState::SHA3_256(hasher) => hasher.update(data),
+            HasherState::SHA3_512(hasher) => hasher.update(data),
+            HasherState::MD5(hasher) => hasher.update(data),
+            HasherState::CRC32(hasher) => hasher.update(data),
+            HasherState::BLAKE2b(hasher) => hasher.update(data),
+            HasherState::BLAKE3(hasher) => {
+                hasher.update(data);
+            }
+            HasherState::Keccak256(hasher) => hasher.update(data),
+            HasherState::RIPEMD160(hasher) => hasher.update(data),
+        }
+    }
 
-/// Compute RIPEMD-160 hash (20 bytes) - Bitcoin style
-fn hash_ripemd160(data: &[u8]) -> Vec<u8> {
-    let mut hasher = Ripemd160::new();
-    hasher.update(data);
-    hasher.finalize().to_vec()
+    /// Consume the hasher and return the hash output
+    pub fn finalize(self) -> Vec<u8> {
+        match self.state {
+            HasherState::SHA1(hasher) => hasher.finalize().to_vec(),
+            HasherState::SHA256(hasher) => hasher.finalize().to_vec(),
+            HasherState::SHA512(hasher) => hasher.finalize().to_vec(),
+            HasherState::SHA3_256(hasher) => hasher.finalize().to_vec(),
+            HasherState::SHA3_512(hasher) => hasher.finalize().to_vec(),
+            HasherState::MD5(hasher) => hasher.finalize().to_vec(),
+            // CRC32 checksum is 4 big-endian bytes
+            HasherState::CRC32(hasher) => hasher.finalize().to_be_bytes().to_vec(),
+            HasherState::BLAKE2b(hasher) => hasher.finalize().to_vec(),
+            HasherState::BLAKE3(hasher) => hasher.finalize().as_bytes().to_vec(),
+            HasherState::Keccak256(hasher) => hasher.finalize().to_vec(),
+            HasherState::RIPEMD160(hasher) => hasher.finalize().to_vec(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -191,6 +193,10 @@ mod tests {
         let data = b"hello";
         let result = hash(HashAlgorithm::CRC32, data).unwrap();
         assert_eq!(result.len(), 4);
+
+        // Standard CRC-32 check value, big-endian
+        let check = hash(HashAlgorithm::CRC32, b"123456789").unwrap();
+        assert_eq!(check, vec![0xcb, 0xf4, 0x39, 0x26]);
     }
 
     #[test]
@@ -264,6 +270,25 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_incremental_matches_one_shot() {
+        let algorithms = [
+            HashAlgorithm::SHA1, HashAlgorithm::SHA256, HashAlgorithm::SHA512,
+            HashAlgorithm::SHA3_256, HashAlgorithm::SHA3_512, HashAlgorithm::MD5,
+            HashAlgorithm::CRC32, HashAlgorithm::BLAKE2b, HashAlgorithm::BLAKE3,
+            HashAlgorithm::Keccak256, HashAlgorithm::RIPEMD160,
+        ];
+
+        for algorithm in algorithms {
+            let mut hasher = IncrementalHasher::new(algorithm);
+            hasher.update(b"test ");
+            hasher.update(b"");
+            hasher.update(b"data");
+
+            assert_eq!(hasher.finalize(), hash(algorithm, b"test data").unwrap(), "{:?}", algorithm);
+        }
+    }
+
     #[test]
     fn test_all_algorithms() {
         // Test that all algorithms produce output of expected length
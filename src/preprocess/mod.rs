@@ -7,14 +7,25 @@
 //!
 //! ## Hash Functions
 //! - SHA-1, SHA-256, SHA-512
-//! - MD5, CRC32
-//! - BLAKE2b
+//! - MD5, CRC32 (4 big-endian bytes, or the checksum as an integer with `crc32_int`)
+//! - BLAKE2b, BLAKE3
 //! - Keccak-256 (Ethereum)
+//! - eth_address (last 20 bytes of Keccak-256, Ethereum address derivation)
 //!
 //! ## Encoding Functions
 //! - hex_encode, base64_encode, base58_encode
 //! - concat (string concatenation)
 //!
+//! ## Byte Operations
+//! - reverse (byte order reversal, e.g. big-endian <-> little-endian)
+//! - xor_bytes (byte-wise XOR of two equal-length values)
+//!
+//! ## Merkle Trees
+//! - merkle_root (SHA-256 root from a leaf, its index and the sibling path)
+//!
+//! ## Big-Integer Arithmetic
+//! - mod_pow (modular exponentiation, e.g. RSA-style commitments)
+//!
 //! ## Format Specifiers (printf-style)
 //! - `{%x}` / `{%X}` - hex lowercase/uppercase
 //! - `{%d}` - decimal
@@ -29,10 +40,241 @@ mod formatter;
 mod hasher;
 
 pub use formatter::format_value;
-pub use hasher::{hash, HashAlgorithm};
+pub use hasher::{hash, HashAlgorithm, IncrementalHasher};
 
+use num_bigint::BigUint;
+use serde::Serialize;
 use std::collections::HashMap;
 
+/// Hash functions that may be called directly inside circuit statements
+///
+/// `keccak256` is absent on purpose: in a circuit statement it is the
+/// in-circuit built-in, which proves the hash instead of trusting it.
+const INLINE_HASH_FUNCTIONS: [&str; 8] = ["sha1", "sha256", "sha512", "md5", "blake2b", "blake3", "crc32", "crc32_int"];
+
+/// Functions whose trailing arguments are options rather than signals
+const PAD_FUNCTIONS: [&str; 2] = ["pad_left", "pad_right"];
+
+/// Runs a preprocessing function on its arguments
+type Executor = fn(&str, &HashMap<String, Vec<u8>>, &HashMap<String, Vec<u8>>) -> Result<Vec<u8>, String>;
+
+/// What a preprocessing function produces
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FunctionKind {
+    /// A digest of the arguments (computed off-circuit)
+    Hash,
+    /// The arguments re-encoded as text
+    Encoding,
+    /// Byte manipulation and arithmetic: concatenation, padding, XOR, Merkle roots, mod_pow
+    Utility,
+}
+
+/// A function preprocessing statements can call
+///
+/// Arity counts the top-level comma-separated arguments; `A|B` and
+/// `concat(A, B)` inside a hash call are one argument.
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionInfo {
+    /// Name in `out<==name(...)`
+    pub name: &'static str,
+    /// Fewest arguments accepted
+    pub min_arity: usize,
+    /// Most arguments accepted, `None` when variadic
+    pub max_arity: Option<usize>,
+    /// Kind of output
+    pub kind: FunctionKind,
+    /// What the output is
+    pub output: &'static str,
+    /// Whether the output may be truncated with a `:N` suffix
+    pub truncatable: bool,
+    #[serde(skip)]
+    execute: Executor,
+}
+
+/// Every function a preprocessing statement can call, as dispatched by `execute_statement`
+const FUNCTIONS: &[FunctionInfo] = &[
+    // Hash functions
+    FunctionInfo { name: "sha1", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "20-byte SHA-1 digest", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::SHA1, args, inputs, outputs) },
+    FunctionInfo { name: "sha256", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "32-byte SHA-256 digest", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::SHA256, args, inputs, outputs) },
+    FunctionInfo { name: "sha512", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "64-byte SHA-512 digest", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::SHA512, args, inputs, outputs) },
+    FunctionInfo { name: "md5", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "16-byte MD5 digest", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::MD5, args, inputs, outputs) },
+    FunctionInfo { name: "blake2b", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "32-byte BLAKE2b digest", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::BLAKE2b, args, inputs, outputs) },
+    FunctionInfo { name: "blake3", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "32-byte BLAKE3 digest", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::BLAKE3, args, inputs, outputs) },
+    FunctionInfo { name: "keccak256", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "32-byte Keccak-256 digest", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::Keccak256, args, inputs, outputs) },
+    FunctionInfo { name: "keccak", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "32-byte Keccak-256 digest (alias of keccak256)", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::Keccak256, args, inputs, outputs) },
+    FunctionInfo { name: "crc32", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "4-byte big-endian CRC32 checksum", truncatable: true,
+        execute: |args, inputs, outputs| execute_hash(HashAlgorithm::CRC32, args, inputs, outputs) },
+    FunctionInfo { name: "crc32_int", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "CRC32 checksum as an integer", truncatable: false,
+        execute: execute_crc32_int },
+    FunctionInfo { name: "eth_address", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Hash, output: "20-byte Ethereum address of a public key", truncatable: true,
+        execute: execute_eth_address },
+
+    // Encoding functions
+    FunctionInfo { name: "hex_encode", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Encoding, output: "lowercase hex text", truncatable: false,
+        execute: execute_hex_encode },
+    FunctionInfo { name: "base64", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Encoding, output: "standard base64 text", truncatable: false,
+        execute: execute_base64_encode },
+    FunctionInfo { name: "base64_encode", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Encoding, output: "standard base64 text (alias of base64)", truncatable: false,
+        execute: execute_base64_encode },
+    FunctionInfo { name: "base58", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Encoding, output: "Bitcoin-alphabet base58 text", truncatable: false,
+        execute: execute_base58_encode },
+    FunctionInfo { name: "base58_encode", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Encoding, output: "Bitcoin-alphabet base58 text (alias of base58)", truncatable: false,
+        execute: execute_base58_encode },
+
+    // Utility
+    FunctionInfo { name: "concat", min_arity: 1, max_arity: None, kind: FunctionKind::Utility, output: "the arguments concatenated", truncatable: false,
+        execute: execute_concat },
+    FunctionInfo { name: "reverse", min_arity: 1, max_arity: Some(1), kind: FunctionKind::Utility, output: "the argument's bytes in reverse order", truncatable: false,
+        execute: execute_reverse },
+    FunctionInfo { name: "xor_bytes", min_arity: 2, max_arity: Some(2), kind: FunctionKind::Utility, output: "byte-wise XOR of two equal-length arguments", truncatable: false,
+        execute: execute_xor_bytes },
+    FunctionInfo { name: "merkle_root", min_arity: 3, max_arity: None, kind: FunctionKind::Utility, output: "32-byte SHA-256 Merkle root of a leaf, its index and the sibling path", truncatable: false,
+        execute: execute_merkle_root },
+    FunctionInfo { name: "pad_left", min_arity: 2, max_arity: Some(3), kind: FunctionKind::Utility, output: "the data left-padded with zero bytes to a length", truncatable: false,
+        execute: |args, inputs, outputs| execute_pad(Side::Left, args, inputs, outputs) },
+    FunctionInfo { name: "pad_right", min_arity: 2, max_arity: Some(3), kind: FunctionKind::Utility, output: "the data right-padded with zero bytes to a length", truncatable: false,
+        execute: |args, inputs, outputs| execute_pad(Side::Right, args, inputs, outputs) },
+    FunctionInfo { name: "mod_pow", min_arity: 3, max_arity: Some(3), kind: FunctionKind::Utility, output: "base^exp mod modulus as an integer", truncatable: false,
+        execute: execute_mod_pow },
+];
+
+/// List every preprocessing function with its arity and output
+///
+/// This is the table `execute_statement` dispatches on, so it cannot drift
+/// from what preprocessing accepts.
+pub fn preprocess_functions() -> Vec<FunctionInfo> {
+    FUNCTIONS.to_vec()
+}
+
+/// Look up a preprocessing function and check its argument count
+fn function_for_call(func_name: &str, args_str: &str) -> Result<&'static FunctionInfo, String> {
+    let function = FUNCTIONS.iter()
+        .find(|function| function.name == func_name)
+        .ok_or_else(|| format!("Unknown function: {}", func_name))?;
+
+    let arity = split_top_level_args(args_str).len();
+    let expected = match function.max_arity {
+        Some(max) if max == function.min_arity => max.to_string(),
+        Some(max) => format!("{} to {}", function.min_arity, max),
+        None => format!("at least {}", function.min_arity),
+    };
+    if arity < function.min_arity || function.max_arity.is_some_and(|max| arity > max) {
+        return Err(format!("{} expects {} argument(s), got {}", func_name, expected, arity));
+    }
+
+    Ok(function)
+}
+
+/// Split arguments on the commas outside parentheses (`concat(a, b), c` is two arguments)
+fn split_top_level_args(args: &str) -> Vec<&str> {
+    if args.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&args[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&args[start..]);
+    parts
+}
+
+/// Move hash calls written inside circuit statements into preprocessing
+///
+/// Each call is replaced by a generated intermediate (`__sha256_0`, ...) and a
+/// matching preprocess statement is appended, so `sha256(secret)==target`
+/// runs as `__sha256_0<==sha256(secret)` followed by `__sha256_0==target`.
+/// The hash is still computed during preprocessing and enters the circuit as a
+/// witness; it is not proven in-circuit.
+///
+/// # Returns
+///
+/// The extended preprocess statements and the rewritten circuit statements.
+pub fn hoist_inline_hashes(preprocess: &[String], circuit: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut preprocess = preprocess.to_vec();
+    let mut counter = 0;
+    let circuit = circuit.iter()
+        .map(|stmt| hoist_hash_calls(stmt, &mut preprocess, &mut counter))
+        .collect();
+
+    (preprocess, circuit)
+}
+
+/// Replace inline hash calls in one statement, innermost calls first
+fn hoist_hash_calls(text: &str, preprocess: &mut Vec<String>, counter: &mut usize) -> String {
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let mut output = String::new();
+    let mut rest = text;
+
+    while let Some((start, func, args_start, args_end)) = find_hash_call(rest, is_ident) {
+        let args = hoist_hash_calls(&rest[args_start..args_end], preprocess, counter);
+        let name = format!("__{}_{}", func, counter);
+        *counter += 1;
+        preprocess.push(format!("{}<=={}({})", name, func, args));
+
+        output.push_str(&rest[..start]);
+        output.push_str(&name);
+        rest = &rest[args_end + 1..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Find the first inline hash call: (start, function, args start, closing paren)
+fn find_hash_call(text: &str, is_ident: impl Fn(char) -> bool) -> Option<(usize, &'static str, usize, usize)> {
+    for (start, c) in text.char_indices() {
+        // Function names start at an identifier boundary
+        if !c.is_ascii_alphabetic() || text[..start].chars().next_back().is_some_and(&is_ident) {
+            continue;
+        }
+
+        for func in INLINE_HASH_FUNCTIONS {
+            let Some(after) = text[start..].strip_prefix(func) else { continue };
+            if after.starts_with(&is_ident) {
+                continue;
+            }
+            let Some(call) = after.trim_start().strip_prefix('(') else { continue };
+
+            // Match the closing parenthesis; an unclosed call is left to the parser
+            let args_start = text.len() - call.len();
+            let mut depth = 1;
+            for (offset, c) in call.char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    return Some((start, func, args_start, args_start + offset));
+                }
+            }
+            return None;
+        }
+    }
+
+    None
+}
+
 /// Execute preprocessing operations on signals
 ///
 /// Takes preprocess statements and signal values, executes operations in order,
@@ -81,57 +323,121 @@ pub fn execute_preprocess(
 /// # Examples
 ///
 /// - `hash<==sha256(A{%x})`
+/// - `short<==sha256(A{%x}):16` - first 16 bytes of the hash
 /// - `encoded<==base64(data{%d})`
 /// - `combined<==concat(A{%x}, B{%d})`
+/// - `fixed<==pad_left(A, 32)`
 fn execute_statement(
     statement: &str,
     input_signals: &HashMap<String, Vec<u8>>,
     intermediate_signals: &HashMap<String, Vec<u8>>,
 ) -> Result<(String, Vec<u8>), String> {
+    let (name, func_name, args_str, truncate) = split_statement(statement)?;
+
+    let output = (function_for_call(func_name, args_str)?.execute)(args_str, input_signals, intermediate_signals)?;
+
+    let output = match truncate {
+        None => output,
+        Some(len) if len > output.len() => {
+            return Err(format!("Cannot truncate {} output to {} bytes: it is only {} bytes", func_name, len, output.len()));
+        }
+        Some(len) => output[..len].to_vec(),
+    };
+
+    Ok((name.to_string(), output))
+}
+
+/// Check the syntax of a preprocessing statement without executing it
+///
+/// Verifies the `name<==function(args)[:N]` shape, that the function exists and
+/// that only hash outputs are truncated. Arguments are not resolved, so a
+/// statement referring to a missing signal still passes.
+pub fn check_statement(statement: &str) -> Result<(), String> {
+    let (_, func_name, args_str, _) = split_statement(statement)?;
+    function_for_call(func_name, args_str)?;
+    if PAD_FUNCTIONS.contains(&func_name) {
+        split_pad_args(func_name, args_str)?;
+    }
+    Ok(())
+}
+
+/// Output name and referenced signal names of a preprocessing statement
+///
+/// Arguments are signal references combined with `|` or `concat(...)`, each
+/// with an optional `{format}` specifier; the names are returned in order.
+pub fn statement_signals(statement: &str) -> Result<(String, Vec<String>), String> {
+    let (name, func_name, args_str, _) = split_statement(statement)?;
+    let args_str = if PAD_FUNCTIONS.contains(&func_name) {
+        split_pad_args(func_name, args_str)?.0
+    } else {
+        args_str
+    };
+
+    let mut inputs = Vec::new();
+    let mut current = String::new();
+    let mut in_format = false;
+    for c in args_str.chars().chain(std::iter::once(' ')) {
+        match c {
+            '{' => in_format = true,
+            '}' => in_format = false,
+            _ if in_format => {}
+            c if c.is_ascii_alphanumeric() || c == '_' => current.push(c),
+            // `concat` is only a name when it's a call
+            '(' => current.clear(),
+            _ => {
+                // Integer literals (`mod_pow(A, 65537, N)`) are not signals
+                let is_literal = current.starts_with(|c: char| c.is_ascii_digit());
+                if !current.is_empty() && !is_literal && !inputs.contains(&current) {
+                    inputs.push(current.clone());
+                }
+                current.clear();
+            }
+        }
+    }
+
+    Ok((name.to_string(), inputs))
+}
+
+/// Split `name<==function(args)[:N]` into its name, function, arguments and truncation length
+fn split_statement(statement: &str) -> Result<(&str, &str, &str, Option<usize>), String> {
     // Parse assignment: name<==operation(args)
     let parts: Vec<&str> = statement.split("<==").collect();
     if parts.len() != 2 {
         return Err(format!("Invalid preprocess statement: {}", statement));
     }
 
-    let name = parts[0].trim().to_string();
+    let name = parts[0].trim();
+    if name.is_empty() {
+        return Err(format!("Missing output name: {}", statement));
+    }
     let operation = parts[1].trim();
 
-    // Parse operation: function_name(args)
-    if let Some(open_paren) = operation.find('(') {
-        if !operation.ends_with(')') {
-            return Err(format!("Missing closing parenthesis: {}", operation));
+    // Optional hash truncation suffix: sha256(x):16
+    let (operation, truncate) = match operation.rsplit_once(':') {
+        Some((call, len)) if call.trim_end().ends_with(')') && !len.trim().is_empty() && len.trim().chars().all(|c| c.is_ascii_digit()) => {
+            let len = len.trim().parse::<usize>()
+                .map_err(|_| format!("Invalid truncation length: {}", len.trim()))?;
+            (call.trim_end(), Some(len))
         }
+        _ => (operation, None),
+    };
 
-        let func_name = operation[..open_paren].trim();
-        let args_str = &operation[open_paren + 1..operation.len() - 1];
-
-        // Execute operation
-        let output = match func_name {
-            // Hash functions
-            "sha1" => execute_hash(HashAlgorithm::SHA1, args_str, input_signals, intermediate_signals)?,
-            "sha256" => execute_hash(HashAlgorithm::SHA256, args_str, input_signals, intermediate_signals)?,
-            "sha512" => execute_hash(HashAlgorithm::SHA512, args_str, input_signals, intermediate_signals)?,
-            "md5" => execute_hash(HashAlgorithm::MD5, args_str, input_signals, intermediate_signals)?,
-            "blake2b" => execute_hash(HashAlgorithm::BLAKE2b, args_str, input_signals, intermediate_signals)?,
-            "keccak256" | "keccak" => execute_hash(HashAlgorithm::Keccak256, args_str, input_signals, intermediate_signals)?,
-            "crc32" => execute_hash(HashAlgorithm::CRC32, args_str, input_signals, intermediate_signals)?,
-
-            // Encoding functions
-            "hex_encode" => execute_hex_encode(args_str, input_signals, intermediate_signals)?,
-            "base64" | "base64_encode" => execute_base64_encode(args_str, input_signals, intermediate_signals)?,
-            "base58" | "base58_encode" => execute_base58_encode(args_str, input_signals, intermediate_signals)?,
-
-            // Utility
-            "concat" => execute_concat(args_str, input_signals, intermediate_signals)?,
-
-            _ => return Err(format!("Unknown function: {}", func_name)),
-        };
+    // Parse operation: function_name(args)
+    let open_paren = operation.find('(')
+        .ok_or_else(|| format!("Invalid operation format: {}", operation))?;
+    if !operation.ends_with(')') {
+        return Err(format!("Missing closing parenthesis: {}", operation));
+    }
 
-        Ok((name, output))
-    } else {
-        Err(format!("Invalid operation format: {}", operation))
+    let func_name = operation[..open_paren].trim();
+    let args_str = &operation[open_paren + 1..operation.len() - 1];
+
+    let truncatable = FUNCTIONS.iter().any(|function| function.name == func_name && function.truncatable);
+    if truncate.is_some() && !truncatable {
+        return Err(format!("Truncation is only supported for hash functions, not {}", func_name));
     }
+
+    Ok((name, func_name, args_str, truncate))
 }
 
 /// Execute hash function on formatted arguments
@@ -141,11 +447,41 @@ fn execute_hash(
     input_signals: &HashMap<String, Vec<u8>>,
     intermediate_signals: &HashMap<String, Vec<u8>>,
 ) -> Result<Vec<u8>, String> {
-    // Parse and format arguments (supports | for inline concat or concat())
-    let data = parse_and_format_args(args, input_signals, intermediate_signals)?;
+    // Feed each formatted argument into the hasher as it is produced
+    // (supports | for inline concat or concat()), so multi-argument hashes
+    // never build the full concatenation in memory
+    let mut hasher = IncrementalHasher::new(algorithm);
+    visit_formatted_args(args, input_signals, intermediate_signals, &mut |chunk| hasher.update(chunk))?;
 
-    // Compute hash
-    hash(algorithm, &data)
+    Ok(hasher.finalize())
+}
+
+/// Execute CRC32 as an integer
+///
+/// `crc32()` always returns the 4 big-endian checksum bytes, leading zeros
+/// included. `crc32_int()` returns the checksum as an integer, without leading
+/// zero bytes, so `{%x}` and `{%d}` format it as a number and
+/// `crc32_int(data) == expected` compares it with a decimal public value.
+fn execute_crc32_int(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let checksum = execute_hash(HashAlgorithm::CRC32, args, input_signals, intermediate_signals)?;
+    Ok(BigUint::from_bytes_be(&checksum).to_bytes_be())
+}
+
+/// Execute Ethereum address derivation
+///
+/// The address is the last 20 bytes of the Keccak-256 hash of the formatted
+/// argument, i.e. of the 64-byte uncompressed public key (without the 0x04 prefix).
+fn execute_eth_address(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let digest = execute_hash(HashAlgorithm::Keccak256, args, input_signals, intermediate_signals)?;
+    Ok(digest[12..].to_vec())
 }
 
 /// Execute hex encoding
@@ -179,22 +515,238 @@ fn execute_base58_encode(
     Ok(bs58::encode(data).into_vec())
 }
 
+/// Execute byte order reversal
+///
+/// Operates on the raw formatted bytes: `reverse(A)` flips the signal's bytes,
+/// while `reverse(A{%x})` flips the characters of its hex string. Use the format
+/// specifier to choose which representation gets reversed.
+fn execute_reverse(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let mut data = parse_and_format_args(args, input_signals, intermediate_signals)?;
+    data.reverse();
+    Ok(data)
+}
+
+/// Execute byte-wise XOR of two equal-length arguments
+///
+/// `xor_bytes(secret{%x}, pad{%x})` formats each comma-separated argument
+/// (which may itself use `|` concatenation) and XORs the results.
+fn execute_xor_bytes(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let parts: Vec<&str> = args.split(',').collect();
+    if parts.len() != 2 {
+        return Err(format!("xor_bytes expects 2 arguments, got {}", parts.len()));
+    }
+
+    let a = parse_and_format_args(parts[0].trim(), input_signals, intermediate_signals)?;
+    let b = parse_and_format_args(parts[1].trim(), input_signals, intermediate_signals)?;
+    if a.len() != b.len() {
+        return Err(format!("xor_bytes arguments must have equal length ({} vs {} bytes)", a.len(), b.len()));
+    }
+
+    Ok(a.iter().zip(&b).map(|(x, y)| x ^ y).collect())
+}
+
+/// Execute a Merkle root computation over a SHA-256 tree
+///
+/// `merkle_root(leaf, index, sibling0, sibling1, ...)` hashes `leaf` up the
+/// tree, one sibling per level from the bottom. Bit `i` of `index` gives the
+/// direction at level `i`: 0 when the current node is the left child
+/// (`sha256(node || sibling)`), 1 when it is the right child
+/// (`sha256(sibling || node)`). The index is an integer argument like those of
+/// `mod_pow`: a literal, an input signal (little-endian) or a preprocessing
+/// output (big-endian).
+fn execute_merkle_root(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let parts: Vec<&str> = args.split(',').collect();
+    if parts.len() < 3 {
+        return Err(format!("merkle_root expects a leaf, an index and at least one sibling, got {} arguments", parts.len()));
+    }
+
+    let mut node = parse_and_format_args(parts[0].trim(), input_signals, intermediate_signals)?;
+    let index = integer_arg(parts[1].trim(), input_signals, intermediate_signals)?;
+    let depth = parts.len() - 2;
+    if index.bits() > depth as u64 {
+        return Err(format!("merkle_root index {} is out of range for a tree of depth {}", index, depth));
+    }
+
+    for (level, part) in parts[2..].iter().enumerate() {
+        let sibling = parse_and_format_args(part.trim(), input_signals, intermediate_signals)?;
+        let mut hasher = IncrementalHasher::new(HashAlgorithm::SHA256);
+        if index.bit(level as u64) {
+            hasher.update(&sibling);
+            hasher.update(&node);
+        } else {
+            hasher.update(&node);
+            hasher.update(&sibling);
+        }
+        node = hasher.finalize();
+    }
+
+    Ok(node)
+}
+
+/// Execute modular exponentiation: `mod_pow(base, exp, modulus)`
+///
+/// Each argument is a signal or an integer literal (decimal or `0x` hex). The
+/// result is `base^exp mod modulus` as an integer, like `crc32_int`, so it can
+/// be compared with a public value in the circuit.
+///
+/// This runs during preprocessing: the circuit only sees the result, it does
+/// not prove that the exponentiation was computed correctly. Arguments larger
+/// than the field are still exact here, but the result is reduced into the
+/// field when it becomes a signal.
+fn execute_mod_pow(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let parts: Vec<&str> = args.split(',').map(str::trim).collect();
+    if parts.len() != 3 {
+        return Err(format!("mod_pow expects 3 arguments, got {}", parts.len()));
+    }
+
+    let base = integer_arg(parts[0], input_signals, intermediate_signals)?;
+    let exp = integer_arg(parts[1], input_signals, intermediate_signals)?;
+    let modulus = integer_arg(parts[2], input_signals, intermediate_signals)?;
+    if modulus.bits() == 0 {
+        return Err("mod_pow modulus must not be zero".to_string());
+    }
+
+    Ok(base.modpow(&exp, &modulus).to_bytes_be())
+}
+
+/// Read an integer argument: a decimal or `0x` hex literal, or a signal
+///
+/// Input signals are little-endian field bytes, while preprocessing outputs are
+/// read as big-endian integers (see `crc32_int`).
+fn integer_arg(
+    arg: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<BigUint, String> {
+    if let Some(hex) = arg.strip_prefix("0x").or_else(|| arg.strip_prefix("0X")) {
+        return BigUint::parse_bytes(hex.as_bytes(), 16)
+            .ok_or_else(|| format!("Invalid hex literal: {}", arg));
+    }
+    if arg.starts_with(|c: char| c.is_ascii_digit()) {
+        return BigUint::parse_bytes(arg.as_bytes(), 10)
+            .ok_or_else(|| format!("Invalid integer literal: {}", arg));
+    }
+
+    if let Some(value) = intermediate_signals.get(arg) {
+        return Ok(BigUint::from_bytes_be(value));
+    }
+    get_signal_value(arg, input_signals, intermediate_signals)
+        .map(|value| BigUint::from_bytes_le(&value))
+}
+
+/// End of the data that `pad_left`/`pad_right` pads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// Pad the formatted data with zero bytes to a fixed length
+///
+/// `pad_left(A, 32)` prepends zero bytes and `pad_right(A, 32)` appends them
+/// until the data is 32 bytes. Data longer than that is an error, unless a
+/// third `truncate` argument is given: `pad_left` then keeps the last bytes and
+/// `pad_right` the first, i.e. the bytes away from the padded side.
+///
+/// Unlike zero-padded format specifiers (`{%064x}`), which pad the encoded
+/// string, this pads the raw bytes.
+fn execute_pad(
+    side: Side,
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+) -> Result<Vec<u8>, String> {
+    let func_name = match side {
+        Side::Left => "pad_left",
+        Side::Right => "pad_right",
+    };
+    let (data_args, len, truncate) = split_pad_args(func_name, args)?;
+    let data = parse_and_format_args(data_args, input_signals, intermediate_signals)?;
+
+    if data.len() > len {
+        if !truncate {
+            return Err(format!(
+                "{} input is {} bytes, longer than {} (add a 'truncate' argument to cut it)",
+                func_name, data.len(), len
+            ));
+        }
+        return Ok(match side {
+            Side::Left => data[data.len() - len..].to_vec(),
+            Side::Right => data[..len].to_vec(),
+        });
+    }
+
+    let padding = vec![0u8; len - data.len()];
+    Ok(match side {
+        Side::Left => [padding, data].concat(),
+        Side::Right => [data, padding].concat(),
+    })
+}
+
+/// Split `data, n[, truncate]` into the data arguments, the length and the truncate flag
+///
+/// The options are taken from the end, so the data may itself contain commas (`concat(a, b)`).
+fn split_pad_args<'a>(func_name: &str, args: &'a str) -> Result<(&'a str, usize, bool), String> {
+    let usage = || format!("{} expects (data, length) or (data, length, truncate)", func_name);
+
+    let (rest, last) = args.rsplit_once(',').ok_or_else(usage)?;
+    let (data, len, truncate) = match last.trim() {
+        "truncate" => {
+            let (data, len) = rest.rsplit_once(',').ok_or_else(usage)?;
+            (data, len.trim(), true)
+        }
+        len => (rest, len, false),
+    };
+
+    let len = len.parse::<usize>()
+        .map_err(|_| format!("{} length must be a number of bytes, got '{}'", func_name, len))?;
+    if data.trim().is_empty() {
+        return Err(usage());
+    }
+
+    Ok((data.trim(), len, truncate))
+}
+
 /// Execute concatenation
 fn execute_concat(
     args: &str,
     input_signals: &HashMap<String, Vec<u8>>,
     intermediate_signals: &HashMap<String, Vec<u8>>,
 ) -> Result<Vec<u8>, String> {
-    // concat() uses comma-separated arguments
-    let parts: Vec<&str> = args.split(',').collect();
     let mut output = Vec::new();
+    visit_concat_args(args, input_signals, intermediate_signals, &mut |chunk| output.extend_from_slice(chunk))?;
+    Ok(output)
+}
 
-    for part in parts {
-        let formatted = parse_and_format_args(part.trim(), input_signals, intermediate_signals)?;
-        output.extend(formatted);
+/// Pass each formatted concat() argument to `sink` in order
+fn visit_concat_args(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+    sink: &mut dyn FnMut(&[u8]),
+) -> Result<(), String> {
+    // concat() uses comma-separated arguments
+    for part in args.split(',') {
+        visit_formatted_args(part.trim(), input_signals, intermediate_signals, sink)?;
     }
 
-    Ok(output)
+    Ok(())
 }
 
 /// Parse and format arguments with format specifiers
@@ -209,7 +761,21 @@ fn parse_and_format_args(
     intermediate_signals: &HashMap<String, Vec<u8>>,
 ) -> Result<Vec<u8>, String> {
     let mut output = Vec::new();
+    visit_formatted_args(args, input_signals, intermediate_signals, &mut |chunk| output.extend_from_slice(chunk))?;
+    Ok(output)
+}
 
+/// Pass each formatted argument to `sink` in order, without concatenating
+///
+/// Accepts the same argument syntax as [`parse_and_format_args`]. Only one
+/// argument is formatted at a time, so streaming consumers (e.g. hashers)
+/// keep peak memory at the size of the largest argument.
+fn visit_formatted_args(
+    args: &str,
+    input_signals: &HashMap<String, Vec<u8>>,
+    intermediate_signals: &HashMap<String, Vec<u8>>,
+    sink: &mut dyn FnMut(&[u8]),
+) -> Result<(), String> {
     // Split by | for inline concatenation (only if not inside nested function)
     let parts: Vec<&str> = if args.contains("concat(") {
         // Has nested concat, don't split by |
@@ -225,16 +791,15 @@ fn parse_and_format_args(
         // Check if this is a nested function call
         if part.starts_with("concat(") && part.ends_with(')') {
             let inner_args = &part[7..part.len()-1];
-            let nested_output = execute_concat(inner_args, input_signals, intermediate_signals)?;
-            output.extend(nested_output);
+            visit_concat_args(inner_args, input_signals, intermediate_signals, sink)?;
         } else {
             // Parse variable and format specifier: A{%x} or just A
             let formatted = format_variable(part, input_signals, intermediate_signals)?;
-            output.extend(formatted);
+            sink(&formatted);
         }
     }
 
-    Ok(output)
+    Ok(())
 }
 
 /// Format a single variable with optional format specifier
@@ -294,6 +859,63 @@ fn get_signal_value(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_hoist_inline_hashes() {
+        let (preprocess, circuit) = hoist_inline_hashes(
+            &["h<==md5(A)".to_string()],
+            &[
+                "sha256(secret{%x}|salt) == target".to_string(),
+                "x<==sha256(sha1(A))".to_string(),
+                "mysha256(A) + sha256A + keccak256(A) + h".to_string(),
+                "crc32_int(data) == checksum".to_string(),
+            ],
+        );
+
+        assert_eq!(preprocess, vec![
+            "h<==md5(A)",
+            "__sha256_0<==sha256(secret{%x}|salt)",
+            "__sha1_1<==sha1(A)",
+            "__sha256_2<==sha256(__sha1_1)",
+            "__crc32_int_3<==crc32_int(data)",
+        ]);
+        assert_eq!(circuit, vec![
+            "__sha256_0 == target",
+            "x<==__sha256_2",
+            "mysha256(A) + sha256A + keccak256(A) + h",
+            "__crc32_int_3 == checksum",
+        ]);
+    }
+
+    #[test]
+    fn test_preprocess_functions() {
+        let functions = preprocess_functions();
+        let find = |name: &str| functions.iter().find(|function| function.name == name).unwrap();
+
+        let sha256 = find("sha256");
+        assert_eq!((sha256.min_arity, sha256.max_arity, sha256.kind), (1, Some(1), FunctionKind::Hash));
+        let concat = find("concat");
+        assert_eq!((concat.min_arity, concat.max_arity, concat.kind), (1, None, FunctionKind::Utility));
+        assert_eq!(find("base64").kind, FunctionKind::Encoding);
+
+        // Every listed function is dispatched (argument values may still be rejected)
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![1; 32]);
+        for function in &functions {
+            let args = vec!["A"; function.min_arity.max(1)].join(", ");
+            let statement = format!("out<=={}({})", function.name, args);
+            if let Err(e) = execute_statement(&statement, &signals, &HashMap::new()) {
+                assert!(!e.starts_with("Unknown function"), "{}: {}", statement, e);
+            }
+        }
+
+        assert_eq!(
+            check_statement("h<==sha256(A, B)").unwrap_err(),
+            "sha256 expects 1 argument(s), got 2"
+        );
+        assert!(check_statement("h<==sha256(concat(A, B))").is_ok());
+        assert!(check_statement("h<==merkle_root(A, B)").is_err());
+    }
+
     #[test]
     fn test_execute_statement_sha256() {
         let mut signals = HashMap::new();
@@ -309,6 +931,158 @@ mod tests {
         assert_eq!(output.len(), 32); // SHA-256 outputs 32 bytes
     }
 
+    #[test]
+    fn test_execute_eth_address() {
+        // Public key of private key 1 (the secp256k1 generator point)
+        let mut signals = HashMap::new();
+        signals.insert("pubkey".to_string(), hex::decode(
+            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+        ).unwrap());
+
+        let (name, output) = execute_statement("addr<==eth_address(pubkey)", &signals, &HashMap::new()).unwrap();
+
+        assert_eq!(name, "addr");
+        assert_eq!(hex::encode(output), "7e5f4552091a69125d5dfcb7b8c2659029395bdf");
+    }
+
+    #[test]
+    fn test_execute_statement_blake3() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![255]);
+
+        let statements = vec!["h<==blake3(A{%x})".to_string(), "e<==base64(h)".to_string()];
+        let outputs = execute_preprocess(&statements, &signals).unwrap();
+
+        // BLAKE3("ff")
+        let expected = hex::decode("df0a0da8cf0e60ba8197d62987438248d63c4108e048a052ac7ee2e47e1cc61d").unwrap();
+        assert_eq!(outputs["h"], expected);
+        assert_eq!(outputs["e"], crate::encoding::bytes_to_base64(&expected).into_bytes());
+    }
+
+    #[test]
+    fn test_execute_statement_xor_bytes() {
+        let mut signals = HashMap::new();
+        signals.insert("x".to_string(), vec![0x12, 0x34, 0x56]);
+        signals.insert("p".to_string(), vec![0xff, 0x0f, 0xa5]);
+
+        let statements = vec!["m<==xor_bytes(x, p)".to_string(), "back<==xor_bytes(m, p)".to_string()];
+        let outputs = execute_preprocess(&statements, &signals).unwrap();
+        assert_eq!(outputs["m"], vec![0xed, 0x3b, 0xf3]);
+        assert_eq!(outputs["back"], signals["x"]);
+
+        signals.insert("short".to_string(), vec![1]);
+        let err = execute_statement("m<==xor_bytes(x, short)", &signals, &HashMap::new()).unwrap_err();
+        assert_eq!(err, "xor_bytes arguments must have equal length (3 vs 1 bytes)");
+        assert!(execute_statement("m<==xor_bytes(x)", &signals, &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_execute_statement_merkle_root() {
+        let sha256 = |parts: &[&[u8]]| {
+            let mut hasher = IncrementalHasher::new(HashAlgorithm::SHA256);
+            parts.iter().for_each(|part| hasher.update(part));
+            hasher.finalize()
+        };
+
+        // Four-leaf tree: root = H(H(l0 || l1) || H(l2 || l3))
+        let leaves: Vec<Vec<u8>> = [b"a", b"b", b"c", b"d"].iter().map(|data| sha256(&[*data])).collect();
+        let left = sha256(&[&leaves[0], &leaves[1]]);
+        let right = sha256(&[&leaves[2], &leaves[3]]);
+        let root = sha256(&[&left, &right]);
+        assert_eq!(
+            hex::encode(&root),
+            "14ede5e8e97ad9372327728f5099b95604a39593cac3bd38a343ad76205213e7"
+        );
+
+        let mut signals = HashMap::new();
+        signals.insert("left".to_string(), left);
+        signals.insert("right".to_string(), right);
+        for (i, leaf) in leaves.iter().enumerate() {
+            signals.insert(format!("leaf{}", i), leaf.clone());
+            signals.insert(format!("index{}", i), vec![i as u8]);
+        }
+
+        // Every leaf proves the same root through its own path
+        let paths = [
+            "leaf0, index0, leaf1, right",
+            "leaf1, index1, leaf0, right",
+            "leaf2, index2, leaf3, left",
+            "leaf3, index3, leaf2, left",
+        ];
+        for path in paths {
+            let statement = format!("root<==merkle_root({})", path);
+            let (_, output) = execute_statement(&statement, &signals, &HashMap::new()).unwrap();
+            assert_eq!(output, root, "{}", path);
+        }
+
+        // A wrong direction gives a different root
+        let (_, output) = execute_statement("root<==merkle_root(leaf2, index0, leaf3, left)", &signals, &HashMap::new()).unwrap();
+        assert_ne!(output, root);
+
+        let err = execute_statement("root<==merkle_root(leaf0, index3, leaf1)", &signals, &HashMap::new()).unwrap_err();
+        assert_eq!(err, "merkle_root index 3 is out of range for a tree of depth 1");
+        assert!(execute_statement("root<==merkle_root(leaf0, index0)", &signals, &HashMap::new()).is_err());
+
+        // An index computed during preprocessing is a big-endian integer
+        let mut intermediates = HashMap::new();
+        for statement in ["p<==mod_pow(5, 3, 41)", "pos<==pad_left(p, 4)"] {
+            let (name, output) = execute_statement(statement, &signals, &intermediates).unwrap();
+            intermediates.insert(name, output);
+        }
+        assert_eq!(intermediates["pos"], vec![0, 0, 0, 2]);
+        let (_, output) = execute_statement("root<==merkle_root(leaf2, pos, leaf3, left)", &signals, &intermediates).unwrap();
+        assert_eq!(output, root);
+        let (_, output) = execute_statement("root<==merkle_root(leaf2, 2, leaf3, left)", &signals, &HashMap::new()).unwrap();
+        assert_eq!(output, root);
+    }
+
+    #[test]
+    fn test_execute_statement_crc32_int() {
+        let mut signals = HashMap::new();
+        signals.insert("data".to_string(), b"123456789".to_vec());
+
+        let statements = vec![
+            "bytes<==crc32(data)".to_string(),
+            "value<==crc32_int(data)".to_string(),
+            "text<==concat(value{%d})".to_string(),
+        ];
+        let outputs = execute_preprocess(&statements, &signals).unwrap();
+
+        // CRC-32 check value 0xCBF43926
+        assert_eq!(outputs["bytes"], vec![0xcb, 0xf4, 0x39, 0x26]);
+        assert_eq!(BigUint::from_bytes_be(&outputs["value"]), BigUint::from(3421780262u32));
+        assert_eq!(outputs["text"], b"3421780262");
+
+        // Leading zero bytes are kept by crc32 but not by crc32_int
+        signals.insert("data".to_string(), vec![]);
+        let (_, bytes) = execute_statement("c<==crc32(data)", &signals, &HashMap::new()).unwrap();
+        let (_, value) = execute_statement("c<==crc32_int(data)", &signals, &HashMap::new()).unwrap();
+        assert_eq!(bytes, vec![0, 0, 0, 0]);
+        assert_eq!(value, vec![0]);
+        assert!(check_statement("c<==crc32_int(data)").is_ok());
+    }
+
+    #[test]
+    fn test_execute_statement_truncated_hash() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![255]);
+
+        let (_, full) = execute_statement("hash<==sha256(A{%x})", &signals, &HashMap::new()).unwrap();
+        let (name, short) = execute_statement("short<==sha256(A{%x}):16", &signals, &HashMap::new()).unwrap();
+
+        assert_eq!(name, "short");
+        assert_eq!(short, full[..16]);
+
+        // Full length is allowed, longer is not
+        assert_eq!(execute_statement("h<==sha256(A{%x}):32", &signals, &HashMap::new()).unwrap().1, full);
+        let err = execute_statement("h<==sha256(A{%x}):33", &signals, &HashMap::new()).unwrap_err();
+        assert_eq!(err, "Cannot truncate sha256 output to 33 bytes: it is only 32 bytes");
+
+        // Only hash outputs can be truncated
+        assert!(execute_statement("e<==hex_encode(A):1", &signals, &HashMap::new()).is_err());
+    }
+
     #[test]
     fn test_execute_concat() {
         let mut signals = HashMap::new();
@@ -325,4 +1099,132 @@ mod tests {
         // Should be "0a14" as bytes
         assert_eq!(String::from_utf8(output).unwrap(), "0a14");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_incremental_hash_matches_concatenation() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![10]);
+        signals.insert("B".to_string(), vec![20]);
+
+        let statements = vec![
+            "inline<==sha256(A{%x}|B{%x})".to_string(),
+            "nested<==sha256(concat(A{%x}, B{%x}))".to_string(),
+            "combined<==concat(A{%x}, B{%x})".to_string(),
+            "whole<==sha256(combined)".to_string(),
+        ];
+        let outputs = execute_preprocess(&statements, &signals).unwrap();
+
+        let expected = hash(HashAlgorithm::SHA256, b"0a14").unwrap();
+        assert_eq!(outputs["inline"], expected);
+        assert_eq!(outputs["nested"], expected);
+        assert_eq!(outputs["whole"], expected);
+    }
+
+    #[test]
+    fn test_execute_mod_pow() {
+        // Signal values arrive as little-endian field bytes
+        let le = |n: u32| {
+            let mut bytes = n.to_le_bytes().to_vec();
+            bytes.resize(32, 0);
+            bytes
+        };
+        let mut signals = HashMap::new();
+        signals.insert("m".to_string(), le(65));
+        signals.insert("e".to_string(), le(17));
+        signals.insert("n".to_string(), le(3233));
+
+        // Textbook RSA: 65^17 mod 3233 = 2790
+        let (_, c) = execute_statement("c<==mod_pow(m, e, n)", &signals, &HashMap::new()).unwrap();
+        assert_eq!(BigUint::from_bytes_be(&c), BigUint::from(2790u32));
+
+        // Literals, and outputs of earlier statements as arguments
+        let statements = vec![
+            "r<==mod_pow(4, 13, 497)".to_string(),
+            "s<==mod_pow(r, 2, 0x3e8)".to_string(),
+            "t<==concat(s{%d})".to_string(),
+        ];
+        let outputs = execute_preprocess(&statements, &signals).unwrap();
+        assert_eq!(BigUint::from_bytes_be(&outputs["r"]), BigUint::from(445u32));
+        assert_eq!(outputs["t"], b"25");
+
+        assert_eq!(
+            execute_statement("c<==mod_pow(m, e, 0)", &signals, &HashMap::new()).unwrap_err(),
+            "mod_pow modulus must not be zero"
+        );
+        assert!(check_statement("c<==mod_pow(m, e)").is_err());
+        assert_eq!(statement_signals("c<==mod_pow(m, 65537, n)").unwrap().1, vec!["m", "n"]);
+    }
+
+    #[test]
+    fn test_execute_pad() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![0x01, 0x02, 0x03]);
+
+        let pad = |statement: &str| execute_statement(statement, &signals, &HashMap::new()).map(|(_, output)| output);
+        assert_eq!(pad("p<==pad_left(A, 8)").unwrap(), vec![0, 0, 0, 0, 0, 0x01, 0x02, 0x03]);
+        assert_eq!(pad("p<==pad_right(A, 8)").unwrap(), vec![0x01, 0x02, 0x03, 0, 0, 0, 0, 0]);
+        assert_eq!(pad("p<==pad_left(A, 3)").unwrap(), signals["A"]);
+
+        // Longer data is cut only on request, away from the padded side
+        assert_eq!(pad("p<==pad_left(A, 2)").unwrap_err(), "pad_left input is 3 bytes, longer than 2 (add a 'truncate' argument to cut it)");
+        assert_eq!(pad("p<==pad_left(A, 2, truncate)").unwrap(), vec![0x02, 0x03]);
+        assert_eq!(pad("p<==pad_right(A, 2, truncate)").unwrap(), vec![0x01, 0x02]);
+
+        // The data may be concatenated; the formatted bytes are padded
+        assert_eq!(pad("p<==pad_right(concat(A, A), 8)").unwrap(), vec![1, 2, 3, 1, 2, 3, 0, 0]);
+        assert_eq!(pad("p<==pad_left(A{%x}, 8)").unwrap(), b"\0\0010203".to_vec());
+
+        assert!(pad("p<==pad_left(A)").is_err());
+        assert!(pad("p<==pad_left(A, many)").is_err());
+    }
+
+    #[test]
+    fn test_pad_statement_signals() {
+        assert_eq!(statement_signals("p<==pad_left(A|B, 32, truncate)").unwrap(), ("p".to_string(), vec!["A".to_string(), "B".to_string()]));
+        assert!(check_statement("p<==pad_right(A, 32)").is_ok());
+        assert!(check_statement("p<==pad_right(A, x)").is_err());
+    }
+
+    #[test]
+    fn test_execute_reverse() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![0x01, 0x02, 0x03]);
+
+        let (name, output) = execute_statement(
+            "le<==reverse(A)",
+            &signals,
+            &HashMap::new()
+        ).unwrap();
+
+        assert_eq!(name, "le");
+        assert_eq!(output, vec![0x03, 0x02, 0x01]);
+    }
+
+    #[test]
+    fn test_reverse_twice_is_identity() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![0xde, 0xad, 0xbe, 0xef]);
+
+        let statements = vec![
+            "once<==reverse(A)".to_string(),
+            "twice<==reverse(once)".to_string(),
+        ];
+        let outputs = execute_preprocess(&statements, &signals).unwrap();
+
+        assert_eq!(outputs["twice"], vec![0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn test_reverse_composes_with_hex_encode() {
+        let mut signals = HashMap::new();
+        signals.insert("A".to_string(), vec![0x12, 0x34]);
+
+        let statements = vec![
+            "le<==reverse(A)".to_string(),
+            "encoded<==hex_encode(le)".to_string(),
+        ];
+        let outputs = execute_preprocess(&statements, &signals).unwrap();
+
+        assert_eq!(String::from_utf8(outputs["encoded"].clone()).unwrap(), "3412");
+    }
+}
@@ -135,7 +135,7 @@ pub fn prove(request_json: &str) -> Result<String, JsValue> {
         .map_err(|e| {
             #[cfg(target_arch = "wasm32")]
             web_sys::console::error_1(&format!("❌ Prove failed: {}", e).into());
-            JsValue::from_str(&e)
+            JsValue::from_str(&e.to_string())
         })?;
 
     // DEBUG: Log success result
@@ -203,7 +203,7 @@ pub fn verify(request_json: &str) -> Result<String, JsValue> {
         .map_err(|e| {
             #[cfg(target_arch = "wasm32")]
             web_sys::console::error_1(&format!("❌ Verification failed: {}", e).into());
-            JsValue::from_str(&e)
+            JsValue::from_str(&e.to_string())
         })?;
 
     // DEBUG: Log verification result
@@ -224,6 +224,82 @@ pub fn verify(request_json: &str) -> Result<String, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize response: {}", e)))
 }
 
+/// Check constraints without generating a proof
+///
+/// Takes a JSON string representing a ProveRequest and returns
+/// a JSON string representing a CheckResponse. Runs the mock prover only,
+/// so it is much faster than `prove` and suited to live form validation.
+///
+/// # Arguments
+///
+/// * `request_json` - JSON string with circuit and signals (same as `prove`)
+///
+/// # Returns
+///
+/// JSON string with `satisfied`, `k`, computed `output`, and constraint `failures`
+///
+/// # Example
+///
+/// ```javascript
+/// const result = JSON.parse(check(JSON.stringify(request)));
+/// if (!result.satisfied) {
+///   console.warn("Constraint failures:", result.failures);
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn check(request_json: &str) -> Result<String, JsValue> {
+    // Parse request
+    let request: ProveRequest = serde_json::from_str(request_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse request: {}", e)))?;
+
+    // Call core check function
+    let response = crate::api::core::check(request)
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    // Serialize response
+    serde_json::to_string(&response)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize response: {}", e)))
+}
+
+/// Evaluate a circuit without checking constraints or generating a proof
+///
+/// Takes a JSON string representing a Program and returns a JSON string
+/// representing an EvaluateResponse. Only the circuit is built, so it is
+/// essentially free and suited to previewing outputs in a UI.
+///
+/// # Arguments
+///
+/// * `program_json` - JSON string representation of Program
+///
+/// # Returns
+///
+/// JSON string with the decimal `output`, `encoded_output` (when the output
+/// signal declares an encoding) and the intermediate `signals`
+///
+/// # Example
+///
+/// ```javascript
+/// import { parse_zircon, evaluate } from './zkplex_core.js';
+///
+/// const program = parse_zircon("1/A:2,B:3,C:7/result:/-/(A+B)*C");
+/// const result = JSON.parse(evaluate(program));
+/// console.log(result.output); // "35"
+/// ```
+#[wasm_bindgen]
+pub fn evaluate(program_json: &str) -> Result<String, JsValue> {
+    use crate::api::Program;
+
+    // Parse Program
+    let program = Program::from_json(program_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse Program: {}", e)))?;
+
+    let response = crate::api::core::evaluate(&program)
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+
+    serde_json::to_string(&response)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize response: {}", e)))
+}
+
 /// Convert zircon program format to JSON
 ///
 /// Takes a zircon format string and returns a JSON representation.
@@ -453,6 +529,49 @@ pub fn parse_json(json: &str) -> Result<String, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize Program: {}", e)))
 }
 
+/// Validate a Program without proving
+///
+/// Checks signal values against their encodings, parses every circuit statement
+/// and checks the syntax of every preprocess statement, reporting all problems
+/// at once so a form can show them inline.
+///
+/// # Arguments
+///
+/// * `program_json` - JSON string representation of Program
+///
+/// # Returns
+///
+/// JSON report `{ "valid": bool, "errors": [string] }`; malformed JSON is
+/// reported as an error in the list
+///
+/// # Example
+///
+/// ```javascript
+/// import { validate_program } from './zkplex_core.js';
+///
+/// const report = JSON.parse(validate_program(programJson));
+/// if (!report.valid) {
+///   report.errors.forEach(e => console.error(e));
+/// }
+/// ```
+#[wasm_bindgen]
+pub fn validate_program(program_json: &str) -> Result<String, JsValue> {
+    use crate::api::Program;
+
+    let errors = match Program::from_json(program_json) {
+        Ok(program) => program.validation_errors(),
+        Err(e) => vec![e],
+    };
+
+    let report = serde_json::json!({
+        "valid": errors.is_empty(),
+        "errors": errors,
+    });
+
+    serde_json::to_string(&report)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize report: {}", e)))
+}
+
 /// Apply signal overrides to a Program
 ///
 /// Replaces '?' placeholders and overrides existing signal values.
@@ -506,6 +625,45 @@ pub fn apply_overrides(program_json: &str, overrides_json: &str) -> Result<Strin
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize Program: {}", e)))
 }
 
+/// Compare signals of two Programs
+///
+/// Reports signals that were added, removed, or changed between two programs.
+/// Useful for checking what `apply_overrides` actually modified, e.g. to catch
+/// a `?` placeholder that was never filled.
+///
+/// # Arguments
+///
+/// * `a_json` - JSON string representation of the original Program
+/// * `b_json` - JSON string representation of the modified Program
+///
+/// # Returns
+///
+/// JSON array of signal changes
+///
+/// # Example
+///
+/// ```javascript
+/// import { parse_zircon, apply_overrides, program_diff } from './zkplex_core.js';
+///
+/// const template = parse_zircon("1/age:?/-/age>=18");
+/// const updated = apply_overrides(template, JSON.stringify({ age: { value: "25", public: false } }));
+///
+/// console.log(JSON.parse(program_diff(template, updated)));
+/// // [{ name: "age", public: false, kind: "changed", old: { value: "?" }, new: { value: "25" } }]
+/// ```
+#[wasm_bindgen]
+pub fn program_diff(a_json: &str, b_json: &str) -> Result<String, JsValue> {
+    use crate::api::Program;
+
+    let a = Program::from_json(a_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse Program: {}", e)))?;
+    let b = Program::from_json(b_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse Program: {}", e)))?;
+
+    serde_json::to_string(&a.diff(&b))
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize diff: {}", e)))
+}
+
 /// Convert Program to ProveRequest
 ///
 /// Converts a Program to a ProveRequest ready for proof generation.
@@ -548,7 +706,7 @@ pub fn program_to_request(program_json: &str, strategy: Option<String>) -> Resul
         s.parse::<Strategy>()
             .map_err(|e| JsValue::from_str(&e))?
     } else {
-        Strategy::Auto
+        program.strategy
     };
 
     // Convert to ProveRequest using shared helper
@@ -592,6 +750,199 @@ pub fn program_to_estimate_request(program_json: &str, strategy: Option<String>)
     program_to_request(program_json, strategy)
 }
 
+/// Get the circuit fingerprint of a Program
+///
+/// Returns a hex SHA-256 over everything that determines the verifying key:
+/// circuit and preprocess statements, strategy, `k`, and max bits. Signal
+/// values are not included, so programs that differ only in their values
+/// (within the same bit width) share a fingerprint.
+///
+/// # Arguments
+///
+/// * `program_json` - JSON string representation of Program
+/// * `strategy` - Optional proof strategy ("auto", "boolean", "lookup", "bitd")
+///
+/// # Returns
+///
+/// 64-character hex fingerprint
+///
+/// # Example
+///
+/// ```javascript
+/// import { parse_zircon, get_circuit_fingerprint } from './zkplex_core.js';
+///
+/// const a = get_circuit_fingerprint(parse_zircon("1/age:25/-/age>=18"), "auto");
+/// const b = get_circuit_fingerprint(parse_zircon("1/age:40/-/age>=18"), "auto");
+/// console.log(a === b); // true: cached params for `a` can be reused for `b`
+/// ```
+#[wasm_bindgen]
+pub fn get_circuit_fingerprint(program_json: &str, strategy: Option<String>) -> Result<String, JsValue> {
+    use crate::api::Program;
+    use crate::circuit::{program_fingerprint, Strategy};
+
+    // Parse Program
+    let program = Program::from_json(program_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse Program: {}", e)))?;
+
+    // Parse strategy
+    let strategy_value = if let Some(s) = strategy {
+        s.parse::<Strategy>()
+            .map_err(|e| JsValue::from_str(&e))?
+    } else {
+        program.strategy
+    };
+
+    program_fingerprint(&program, strategy_value)
+        .map_err(|e| JsValue::from_str(&e))
+}
+
+/// Decode a proof's verification context for inspection
+///
+/// Returns the circuit, preprocess statements, strategy, k, secret signal
+/// names, output signal, and max bits the verifying key is derived from,
+/// without verifying the proof.
+///
+/// # Arguments
+///
+/// * `verify_context` - Base85-encoded `verify_context` from a ProveResponse
+///
+/// # Returns
+///
+/// Pretty-printed JSON string representation of VerifyContext
+///
+/// # Example
+///
+/// ```javascript
+/// import { decode_context } from './zkplex_core.js';
+///
+/// const response = JSON.parse(proveResponseJson);
+/// const context = JSON.parse(decode_context(response.verify_context));
+/// console.log("Circuit:", context.circuit);
+/// ```
+#[wasm_bindgen]
+pub fn decode_context(verify_context: &str) -> Result<String, JsValue> {
+    let context = crate::api::core::decode_verify_context(verify_context)
+        .map_err(|e| JsValue::from_str(&e))?;
+
+    serde_json::to_string_pretty(&context)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize verification context: {}", e)))
+}
+
+/// Report which encodings a signal value is valid under
+///
+/// Helps debug auto-detection surprises: when several encodings accept the
+/// value, give the signal an explicit encoding.
+///
+/// # Arguments
+///
+/// * `value` - Signal value string
+///
+/// # Returns
+///
+/// JSON array of `{"encoding": ..., "valid": ...}` in auto-detection order
+///
+/// # Example
+///
+/// ```javascript
+/// import { detect_encoding } from './zkplex_core.js';
+///
+/// const candidates = JSON.parse(detect_encoding("12345678"));
+/// console.log(candidates.filter(c => c.valid).map(c => c.encoding));
+/// ```
+#[wasm_bindgen]
+pub fn detect_encoding(value: &str) -> Result<String, JsValue> {
+    let candidates: Vec<_> = crate::encoding::detect_encoding(value)
+        .into_iter()
+        .map(|(encoding, valid)| serde_json::json!({ "encoding": encoding, "valid": valid }))
+        .collect();
+
+    serde_json::to_string(&candidates)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize encoding report: {}", e)))
+}
+
+/// List the functions preprocessing statements can call
+///
+/// # Returns
+///
+/// JSON array of `{"name", "min_arity", "max_arity", "kind", "output", "truncatable"}`;
+/// `max_arity` is `null` for variadic functions and `kind` is `hash`,
+/// `encoding` or `utility`
+///
+/// # Example
+///
+/// ```javascript
+/// import { list_preprocess_functions } from './zkplex_core.js';
+///
+/// const hashes = JSON.parse(list_preprocess_functions()).filter(f => f.kind === "hash");
+/// console.log(hashes.map(f => f.name));
+/// ```
+#[wasm_bindgen]
+pub fn list_preprocess_functions() -> Result<String, JsValue> {
+    serde_json::to_string(&crate::preprocess::preprocess_functions())
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize preprocess functions: {}", e)))
+}
+
+/// List the operators circuit expressions accept
+///
+/// # Returns
+///
+/// JSON array of `{"name", "symbols", "category", "precedence", "arity"}`,
+/// loosest-binding first; `precedence` runs from 1 (`OR`) to 7 (unary operators)
+///
+/// # Example
+///
+/// ```javascript
+/// import { list_circuit_operators } from './zkplex_core.js';
+///
+/// const and = JSON.parse(list_circuit_operators()).find(op => op.name === "and");
+/// console.log(and.symbols); // ["AND", "&&"]
+/// ```
+#[wasm_bindgen]
+pub fn list_circuit_operators() -> Result<String, JsValue> {
+    serde_json::to_string(&crate::parser::circuit_operators())
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize circuit operators: {}", e)))
+}
+
+/// List the built-in functions circuit expressions accept
+///
+/// # Returns
+///
+/// JSON array of `{"name", "arity", "description"}`
+///
+/// # Example
+///
+/// ```javascript
+/// import { list_circuit_functions } from './zkplex_core.js';
+///
+/// console.log(JSON.parse(list_circuit_functions()).map(f => f.name));
+/// ```
+#[wasm_bindgen]
+pub fn list_circuit_functions() -> Result<String, JsValue> {
+    serde_json::to_string(&crate::parser::circuit_functions())
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize circuit functions: {}", e)))
+}
+
+/// Get the JSON Schema of the API types
+///
+/// # Returns
+///
+/// JSON Schema (draft 2020-12) document; `$defs` holds `ProveRequest`,
+/// `ProveResponse`, `VerifyRequest`, `VerifyResponse` and `Program`
+///
+/// # Example
+///
+/// ```javascript
+/// import { schemas } from './zkplex_core.js';
+///
+/// const schema = JSON.parse(schemas());
+/// console.log(Object.keys(schema.$defs.ProveRequest.properties));
+/// ```
+#[wasm_bindgen]
+pub fn schemas() -> Result<String, JsValue> {
+    serde_json::to_string_pretty(&crate::api::json_schema())
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize schema: {}", e)))
+}
+
 /// Convert ProveResponse to VerifyRequest
 ///
 /// Extracts verification data from a ProveResponse.
@@ -632,6 +983,7 @@ pub fn response_to_verify_request(prove_response_json: &str) -> Result<String, J
         proof: prove_response.proof,
         verify_context: prove_response.verify_context,
         public_signals: prove_response.public_signals,
+        expected_circuit_hash: None,
     };
 
     // Serialize to JSON
@@ -680,6 +1032,8 @@ pub fn estimate_constraints(zircon: &str) -> Result<u32, JsValue> {
             value: signal.value.clone(),
             encoding: signal.encoding,
             public: false,
+            bits: signal.bits,
+            constant: signal.constant,
         });
     }
 
@@ -689,12 +1043,14 @@ pub fn estimate_constraints(zircon: &str) -> Result<u32, JsValue> {
             value: signal.value.clone(),
             encoding: signal.encoding,
             public: true,
+            bits: signal.bits,
+            constant: signal.constant,
         });
     }
 
     // Build circuit from program
     let circuit = Circuit::from_program(&program)
-        .map_err(|e| JsValue::from_str(&e))?;
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
     // Estimate requirements (use auto strategy for zircon programs)
     let estimate = estimate_circuit_requirements_with_strategy(&circuit, None);
@@ -742,6 +1098,8 @@ pub fn generate_circuit(zircon: &str) -> Result<String, JsValue> {
             value: signal.value.clone(),
             encoding: signal.encoding,
             public: false,
+            bits: signal.bits,
+            constant: signal.constant,
         });
     }
 
@@ -751,12 +1109,14 @@ pub fn generate_circuit(zircon: &str) -> Result<String, JsValue> {
             value: signal.value.clone(),
             encoding: signal.encoding,
             public: true,
+            bits: signal.bits,
+            constant: signal.constant,
         });
     }
 
     // Build circuit from program
     let circuit = Circuit::from_program(&program)
-        .map_err(|e| JsValue::from_str(&e))?;
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
 
     // Estimate requirements to get constraint count (use auto strategy for zircon programs)
     let estimate = estimate_circuit_requirements_with_strategy(&circuit, None);
@@ -773,6 +1133,41 @@ pub fn generate_circuit(zircon: &str) -> Result<String, JsValue> {
         .map_err(|e| JsValue::from_str(&format!("Failed to serialize response: {}", e)))
 }
 
+/// Explain how a circuit expression was parsed
+///
+/// Renders the parsed AST as an indented tree, one node per line,
+/// which makes operator precedence visible.
+///
+/// # Arguments
+///
+/// * `circuit` - Circuit expression (e.g., "A + B * C > D")
+///
+/// # Returns
+///
+/// Multi-line tree string
+///
+/// # Example
+///
+/// ```javascript
+/// import { explain_circuit } from './zkplex_core.js';
+///
+/// console.log(explain_circuit("A + B * C > D"));
+/// // >
+/// // ├── +
+/// // │   ├── A
+/// // │   └── *
+/// // │       ├── B
+/// // │       └── C
+/// // └── D
+/// ```
+#[wasm_bindgen]
+pub fn explain_circuit(circuit: &str) -> Result<String, JsValue> {
+    let expr = crate::parser::parse_circuit(circuit)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse circuit: {}", e)))?;
+
+    Ok(expr.to_tree_string())
+}
+
 #[cfg(all(test, target_arch = "wasm32"))]
 mod tests {
     use super::*;
@@ -0,0 +1,104 @@
+//! Integration tests for `zkplex-cli --batch`
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Write a batch file into the system temp directory
+fn write_batch_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("zkplex_{}_{}.zrc", name, std::process::id()));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn run_batch(path: &PathBuf) -> (bool, Vec<serde_json::Value>) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .arg("--batch")
+        .arg(path)
+        .output()
+        .unwrap();
+    fs::remove_file(path).ok();
+
+    let results: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout).unwrap();
+    (output.status.success(), results)
+}
+
+#[test]
+fn test_batch_two_lines() {
+    // Same circuit on both lines, so params and keys are shared
+    let path = write_batch_file("batch_ok", "1/A:10/out:?/-/A>5\n1/A:3/out:?/-/A>5\n");
+    let (success, results) = run_batch(&path);
+
+    assert!(success);
+    assert_eq!(results.len(), 2);
+    assert!(results[0]["proof"].is_string());
+    assert_eq!(results[0]["public_signals"]["out"]["value"], "1");
+    assert_eq!(results[1]["public_signals"]["out"]["value"], "0");
+}
+
+#[test]
+fn test_batch_reports_error_per_line() {
+    let path = write_batch_file("batch_err", "1/A:10/out:?/-/A>5\nnot a program\n");
+    let (success, results) = run_batch(&path);
+
+    assert!(!success);
+    assert_eq!(results.len(), 2);
+    assert!(results[0]["proof"].is_string());
+    assert_eq!(results[1]["details"], "line 2");
+    assert!(results[1]["error"].is_string());
+}
+
+#[test]
+fn test_batch_proof_out_dir_names_files_by_fingerprint() {
+    let path = write_batch_file("batch_fp", "1/A:10/out:?/-/A>5\n1/A:10/out:?/-/A*2>5\n");
+    let dir = std::env::temp_dir().join(format!("zkplex_fp_{}", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .arg("--batch")
+        .arg(&path)
+        .arg("--proof-out-dir")
+        .arg(&dir)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+    let mut names: Vec<String> = fs::read_dir(&dir).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    fs::remove_dir_all(&dir).ok();
+
+    names.sort();
+    assert_eq!(names.len(), 2, "{:?}", names);
+    assert_ne!(names[0], names[1]);
+    for name in &names {
+        // 64 hex characters of fingerprint
+        assert_eq!(name.len(), "json".len() + 65, "{}", name);
+        assert!(name.ends_with(".json"));
+    }
+}
+
+#[test]
+fn test_batch_proof_out_dir_keeps_proofs_of_the_same_circuit() {
+    let path = write_batch_file("batch_fp_dup", "1/A:10/out:?/-/A>5\n1/A:20/out:?/-/A>5\n");
+    let dir = std::env::temp_dir().join(format!("zkplex_fp_dup_{}", std::process::id()));
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .arg("--batch")
+        .arg(&path)
+        .arg("--proof-out-dir")
+        .arg(&dir)
+        .arg("--quiet")
+        .output()
+        .unwrap();
+    fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+    let mut names: Vec<String> = fs::read_dir(&dir).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    fs::remove_dir_all(&dir).ok();
+
+    names.sort();
+    assert_eq!(names.len(), 2, "{:?}", names);
+    // "-" sorts before ".", so the suffixed name comes first
+    assert_eq!(names[0], names[1].replace(".json", "-2.json"));
+}
@@ -0,0 +1,41 @@
+//! Integration tests for `zkplex-cli --prove --json-compact`
+
+use std::fs;
+use std::process::Command;
+
+fn prove(args: &[&str]) -> Vec<u8> {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/A:10,B:20/out:?/-/A+B>25", "--prove", "--seed", "0x01"])
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    output.stdout
+}
+
+#[test]
+fn test_compact_proof_is_smaller_and_verifies() {
+    let pretty = prove(&[]);
+    let compact = prove(&["--json-compact"]);
+
+    // Same response, without the whitespace
+    assert!(compact.len() < pretty.len());
+    assert_eq!(String::from_utf8_lossy(&compact).trim().lines().count(), 1);
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&compact).unwrap(),
+        serde_json::from_slice::<serde_json::Value>(&pretty).unwrap(),
+    );
+
+    let path = std::env::temp_dir().join(format!("zkplex_json_compact_{}.json", std::process::id()));
+    fs::write(&path, &compact).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--verify", "--proof"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Proof is VALID"));
+}
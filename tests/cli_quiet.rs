@@ -0,0 +1,37 @@
+//! Integration tests for `zkplex-cli --quiet`
+
+use std::process::Command;
+
+fn prove(extra: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/A:10,B:20/out:?/-/A+B>25", "--prove"])
+        .args(extra)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_quiet_prove_prints_nothing_on_stderr() {
+    let output = prove(&["--quiet"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "");
+
+    // The proof JSON is still written to stdout
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(response["proof"].is_string());
+
+    // Without --quiet the progress is shown
+    let output = prove(&[]);
+    assert!(String::from_utf8(output.stderr).unwrap().contains("Generating proving key..."));
+}
+
+#[test]
+fn test_quiet_keeps_errors() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/A:10/out:?/-/A>5", "--prove", "-q", "--seed", "xyz"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("Error generating proof"));
+}
@@ -0,0 +1,67 @@
+//! Integration tests for `zkplex-cli --prove --repeat`
+
+use std::process::Command;
+
+fn prove(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/A:10,B:20/out:?/-/A+B>25", "--prove"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_repeat_reports_stats_and_prints_one_proof() {
+    let output = prove(&["--repeat", "3"]);
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Repeat: 3 runs"), "{}", stderr);
+    for phase in ["Total", "Proof creation"] {
+        let line = stderr.lines().find(|line| line.starts_with(&format!("Time: {}", phase))).unwrap();
+        assert!(line.contains("avg") && line.contains("min") && line.contains("max"), "{}", line);
+    }
+
+    // A single proof object on stdout
+    let response: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(response["public_signals"]["out"]["value"], "1");
+}
+
+#[test]
+fn test_repeat_rejects_zero() {
+    let output = prove(&["--repeat", "0"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("--repeat requires a positive number"));
+}
+
+/// `Run n/N` lines printed for `--repeat`, and how many of them generated keys
+fn runs(stderr: &str) -> (usize, usize) {
+    let runs: Vec<&str> = stderr.lines().filter(|line| line.starts_with("Run ")).collect();
+    let keygens = runs.iter().filter(|line| line.ends_with("(key generation)")).count();
+    (runs.len(), keygens)
+}
+
+#[test]
+fn test_repeat_reports_every_run() {
+    let output = prove(&["--repeat", "3"]);
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(runs(&stderr), (3, 1), "{}", stderr);
+    assert!(stderr.contains("Run 3/3: "), "{}", stderr);
+}
+
+#[test]
+fn test_repeat_with_params_generates_keys_once() {
+    let params = std::env::temp_dir().join(format!("zkplex_repeat_params_{}.bin", std::process::id()));
+    let params_arg = params.to_str().unwrap();
+
+    // The first invocation creates the params file, the second loads it
+    for _ in 0..2 {
+        let output = prove(&["--repeat", "3", "--params", params_arg]);
+        assert!(output.status.success());
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert_eq!(runs(&stderr), (3, 1), "{}", stderr);
+    }
+    std::fs::remove_file(&params).ok();
+}
@@ -0,0 +1,35 @@
+//! Integration tests for `zkplex-cli --seed`
+
+use std::process::Command;
+
+fn prove(seed: &str) -> (serde_json::Value, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/A:10,B:20/out:?/-/A+B>25", "--prove", "--seed", seed])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    (serde_json::from_slice(&output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn test_seed_makes_proof_reproducible() {
+    let (first, stderr) = prove("0x0102030405");
+    let (second, _) = prove("0102030405");
+    let (other, _) = prove("0x0102030406");
+
+    assert!(stderr.contains("--seed makes the proof deterministic"), "{}", stderr);
+    assert_eq!(first["proof"], second["proof"]);
+    assert_ne!(first["proof"], other["proof"]);
+}
+
+#[test]
+fn test_seed_rejects_invalid_hex() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/A:10/out:?/-/A>5", "--prove", "--seed", "xyz"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr).unwrap().contains("Invalid seed 'xyz'"));
+}
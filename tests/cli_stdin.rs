@@ -0,0 +1,52 @@
+//! Integration tests for reading the program from stdin (`--zircon -`, `--from-stdin`)
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const PROGRAM: &str = "1/A:10,B:20/out:?/-/A+B>25\n";
+
+fn run_with_stdin(args: &[&str], stdin: &str) -> std::process::Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The CLI may exit before reading stdin (e.g. on an argument error)
+    let _ = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+fn estimate(args: &[&str], stdin: &str) -> serde_json::Value {
+    let output = run_with_stdin(args, stdin);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_estimate_program_from_stdin() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", PROGRAM.trim(), "--estimate", "--format", "json"])
+        .output()
+        .unwrap();
+    let expected: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+
+    let piped = estimate(&["--zircon", "-", "--estimate", "--format", "json"], PROGRAM);
+    assert_eq!(piped, expected);
+
+    // --from-stdin detects the format
+    let piped = estimate(&["--from-stdin", "--estimate", "--format", "json"], PROGRAM);
+    assert_eq!(piped, expected);
+
+    let json = run_with_stdin(&["--zircon", "-", "--into-json"], PROGRAM);
+    let piped = estimate(&["--from-stdin", "--estimate", "--format", "json"], &String::from_utf8(json.stdout).unwrap());
+    assert_eq!(piped, expected);
+}
+
+#[test]
+fn test_from_stdin_conflicts_with_program_argument() {
+    let output = run_with_stdin(&["--from-stdin", "--zircon", PROGRAM.trim(), "--estimate"], PROGRAM);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--from-stdin cannot be combined"));
+}
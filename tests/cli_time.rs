@@ -0,0 +1,45 @@
+//! Integration tests for `zkplex-cli --time`
+
+use std::fs;
+use std::process::Command;
+
+fn prove(args: &[&str]) -> (serde_json::Value, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/A:10,B:20/out:?/-/A+B>25", "--prove"])
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    (serde_json::from_slice(&output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn test_time_reports_phases_without_changing_proof() {
+    let (plain, plain_stderr) = prove(&[]);
+    let (timed, timed_stderr) = prove(&["--time"]);
+
+    assert!(!plain_stderr.contains("Time:"));
+    for phase in ["VK generation", "PK generation", "Proof creation"] {
+        assert!(timed_stderr.contains(&format!("Time: {}", phase)), "{}", timed_stderr);
+    }
+
+    // Proofs are randomized, but everything the verifier relies on matches
+    assert_eq!(timed["verify_context"], plain["verify_context"]);
+    assert_eq!(timed["public_signals"], plain["public_signals"]);
+
+    let path = std::env::temp_dir().join(format!("zkplex_time_{}.json", std::process::id()));
+    fs::write(&path, timed.to_string()).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--verify", "--time", "--proof"])
+        .arg(&path)
+        .output()
+        .unwrap();
+    fs::remove_file(&path).ok();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().contains("Proof is VALID"));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Time: VK generation"), "{}", stderr);
+    assert!(stderr.contains("Time: Verification"), "{}", stderr);
+}
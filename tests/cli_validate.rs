@@ -0,0 +1,52 @@
+//! Integration tests for `zkplex-cli --validate`
+
+use std::process::Command;
+
+fn validate(zircon: &str) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", zircon, "--validate"])
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_validate_valid_program() {
+    let output = validate("1/A:10,B:20/-/-/sum<==A+B;sum>25");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Program is valid"), "{}", stdout);
+}
+
+#[test]
+fn test_validate_reports_every_error() {
+    let output = validate("1/A:10,B:zz:hex/-/-/A+*B;A>");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Circuit statement 1 'A+*B'"), "{}", stderr);
+    assert!(stderr.contains("Circuit statement 2 'A>'"), "{}", stderr);
+    assert!(stderr.contains("'B'"), "{}", stderr);
+    assert!(stderr.contains("3 error(s) found"), "{}", stderr);
+}
+
+#[test]
+fn test_validate_reports_undeclared_signal() {
+    // Parses, but references a signal that is never defined
+    let output = validate("1/A:10/-/-/A+C>5");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Signal 'C' is used but not declared"), "{}", stderr);
+    assert!(stderr.contains("1 error(s) found"), "{}", stderr);
+}
+
+#[test]
+fn test_validate_reports_circuit_build_error() {
+    // Parses and declares every signal, but divides by zero
+    let output = validate("1/A:10,B:0/-/-/A/B>5");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Error building circuit"), "{}", stderr);
+}
@@ -0,0 +1,46 @@
+//! Integration tests for `zkplex-cli --verify --public` overrides
+
+use std::fs;
+use std::process::{Command, Output};
+
+fn verify(path: &std::path::Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--verify", "--into-json", "--proof"])
+        .arg(path)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_public_override_invalidates_proof() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--zircon", "1/age:25/min:18,ok:?/-/age>=min", "--prove"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let path = std::env::temp_dir().join(format!("zkplex_verify_public_{}.json", std::process::id()));
+    fs::write(&path, &output.stdout).unwrap();
+
+    let original = verify(&path, &[]);
+    let same = verify(&path, &["--public", "min:18"]);
+    let tweaked = verify(&path, &["--public", "min:30"]);
+    let unknown = verify(&path, &["--public", "max:30"]);
+    fs::remove_file(&path).ok();
+
+    let valid = |output: &Output| {
+        let response: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        response["valid"].as_bool().unwrap()
+    };
+
+    assert!(original.status.success());
+    assert!(valid(&original));
+    assert!(valid(&same));
+
+    assert!(!tweaked.status.success());
+    assert!(!valid(&tweaked));
+
+    assert!(!unknown.status.success());
+    assert!(String::from_utf8(unknown.stdout).unwrap().contains("Proof has no public signal 'max'"));
+}
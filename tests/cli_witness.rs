@@ -0,0 +1,21 @@
+//! Integration tests for `zkplex-cli --witness`
+
+use std::process::Command;
+
+#[test]
+fn test_witness_shows_intermediate_sum() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkplex-cli"))
+        .args(["--circuit", "sum<==A+B;sum>25", "--secret", "A:10", "--secret", "B:20", "--witness"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("sum (intermediate) = 30 (0x1e)"), "{}", stdout);
+    assert!(stdout.contains("A (secret) = 10 (0xa)"), "{}", stdout);
+
+    // Privacy warning goes to stderr so it never ends up in redirected output
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("SECRET"));
+}